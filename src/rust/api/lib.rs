@@ -1,10 +1,9 @@
 use std::pin::Pin;
 
 use jsg::ResourceState;
-use jsg::ResourceTemplate;
+use jsg::ToJS;
 
 use crate::dns::DnsUtil;
-use crate::dns::DnsUtilTemplate;
 
 pub mod dns;
 
@@ -30,9 +29,8 @@ pub fn register_nodejs_modules(registry: Pin<&mut ffi::ModuleRegistry>) {
             let dns_util = jsg::Ref::new(DnsUtil {
                 _state: ResourceState::default(),
             });
-            let mut dns_util_template = DnsUtilTemplate::new(&mut lock);
 
-            jsg::wrap_resource(&mut lock, dns_util, &mut dns_util_template).into_ffi()
+            dns_util.to_js(&mut lock).into_ffi()
         },
         jsg::modules::ModuleType::INTERNAL,
     );
@@ -44,6 +42,7 @@ mod tests {
     use jsg_test::Harness;
 
     use super::*;
+    use crate::dns::DnsUtilTemplate;
 
     #[test]
     fn test_wrap_resource_equality() {
@@ -14,6 +14,14 @@ use syn::spanned::Spanned;
 ///
 /// Only public fields are included in the generated JavaScript object.
 /// Use `name` parameter for custom JavaScript class name.
+///
+/// When unwrapping from JavaScript, each public field is read from the object and converted
+/// via `jsg::FromJS`. `Option<T>` and `Nullable<T>` fields may be omitted; all other fields are
+/// required. Non-public fields are initialized with `Default::default()`.
+///
+/// The generated `jsg::Type::memory_info` tracks every field that implements `jsg::Type`, so
+/// heap snapshots include the memory owned by the struct, and the generated
+/// `jsg::Type::visit_for_gc` visits them, so a resource holding the struct traces its values.
 #[proc_macro_attribute]
 pub fn jsg_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
@@ -37,27 +45,38 @@ pub fn jsg_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
         let field_name = field.ident.as_ref()?;
         let field_name_str = field_name.to_string();
         Some(quote! {
-            let #field_name = jsg::v8::ToLocalValue::to_local(&this.#field_name, lock);
+            let #field_name = jsg::ToJS::to_js(this.#field_name, lock);
             obj.set(lock, #field_name_str, #field_name);
         })
     });
 
+    let field_unwraps = fields
+        .named
+        .iter()
+        .filter_map(|field| generate_struct_field_unwrap(field, &class_name));
+
+    let memory_info = generate_memory_info(&data.fields);
+    let visit_for_gc = generate_visit_for_gc(&data.fields);
+
     quote! {
         #input
 
         impl jsg::Type for #name {
             fn class_name() -> &'static str { #class_name }
 
+            fn memory_name() -> &'static str { #class_name }
+
             fn is_exact(value: &jsg::v8::Local<jsg::v8::Value>) -> bool {
                 value.is_object()
             }
+
+            #memory_info
+
+            #visit_for_gc
         }
 
         impl jsg::ToJS for #name {
-            fn to_js<'a, 'b>(self, lock: &'a mut jsg::Lock) -> jsg::v8::Local<'b, jsg::v8::Value>
-            where
-                'b: 'a,
-            {
+            fn to_js<'s>(self, lock: &mut jsg::Lock<'s>) -> jsg::v8::Local<'s, jsg::v8::Value> {
                 // TODO(soon): Use a precached ObjectTemplate instance to create the object,
                 // similar to how C++ JSG optimizes object creation. This would avoid recreating
                 // the object shape on every wrap() call and improve performance.
@@ -73,8 +92,17 @@ pub fn jsg_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
         impl jsg::FromJS for #name {
             type ResultType = Self;
 
-            fn from_js(_lock: &mut jsg::Lock, _value: jsg::v8::Local<jsg::v8::Value>) -> Result<Self::ResultType, jsg::Error> {
-                todo!("Struct from_js is not yet supported")
+            fn from_js(lock: &mut jsg::Lock, value: jsg::v8::Local<jsg::v8::Value>) -> Result<Self::ResultType, jsg::Error> {
+                let Some(obj) = value.try_cast::<jsg::v8::Object>() else {
+                    return Err(jsg::Error::new_type_error(format!(
+                        "Expected an object for '{}' but got {}",
+                        #class_name,
+                        value.type_of(),
+                    )));
+                };
+                Ok(Self {
+                    #(#field_unwraps)*
+                })
             }
         }
 
@@ -83,19 +111,217 @@ pub fn jsg_struct(attr: TokenStream, item: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Generates `jsg::Type::memory_info`, tracking each field whose type implements `jsg::Type` and
+/// skipping the others, such as `jsg::ResourceState`.
+fn generate_memory_info(fields: &Fields) -> impl ToTokens {
+    if fields.is_empty() {
+        return quote! {};
+    }
+
+    let tracked_fields = fields.iter().enumerate().map(|(index, field)| {
+        let (member, edge_name) = if let Some(ident) = &field.ident {
+            (quote! { #ident }, ident.to_string())
+        } else {
+            let index = syn::Index::from(index);
+            (quote! { #index }, index.index.to_string())
+        };
+        quote! {
+            (&jsg::MemoryField(&self.#member)).track_field(tracker, #edge_name);
+        }
+    });
+
+    quote! {
+        fn memory_info(&self, tracker: &mut jsg::MemoryTracker) {
+            #[allow(unused_imports)]
+            use jsg::SkipMemoryField as _;
+            #[allow(unused_imports)]
+            use jsg::TrackMemoryField as _;
+            #(#tracked_fields)*
+        }
+    }
+}
+
+/// Generates `jsg::Type::visit_for_gc`, visiting each field whose type implements `jsg::Type` and
+/// skipping the others.
+fn generate_visit_for_gc(fields: &Fields) -> impl ToTokens {
+    if fields.is_empty() {
+        return quote! {};
+    }
+
+    let visited_fields = fields.iter().enumerate().map(|(index, field)| {
+        let member = if let Some(ident) = &field.ident {
+            quote! { #ident }
+        } else {
+            let index = syn::Index::from(index);
+            quote! { #index }
+        };
+        quote! {
+            (&jsg::GcField(&self.#member)).visit_for_gc(visitor);
+        }
+    });
+
+    quote! {
+        fn visit_for_gc(&self, visitor: &mut jsg::GcVisitor) {
+            #[allow(unused_imports)]
+            use jsg::SkipGcField as _;
+            #[allow(unused_imports)]
+            use jsg::VisitGcField as _;
+            #(#visited_fields)*
+        }
+    }
+}
+
+/// Generates the field initializer used by `#[jsg_struct]` to unwrap `field` from the JS object
+/// bound to `obj`. Non-public fields are not exposed to JavaScript and are default-initialized.
+///
+/// An exception thrown by a getter of the field is returned as the error, like a `jsg::Dict` or
+/// `JSG_STRUCT` in C++ JSG, rather than being reported as a missing field.
+fn generate_struct_field_unwrap(field: &syn::Field, class_name: &str) -> Option<impl ToTokens> {
+    let field_name = field.ident.as_ref()?;
+    if !matches!(field.vis, syn::Visibility::Public(_)) {
+        return Some(quote! { #field_name: Default::default(), });
+    }
+    let field_name_str = field_name.to_string();
+    let ty = &field.ty;
+    let missing_check = if is_optional_type(ty) {
+        quote! {}
+    } else {
+        quote! {
+            if value.is_undefined() {
+                return Err(jsg::Error::new_type_error(format!(
+                    "Required field '{}' is missing on '{}'.",
+                    #field_name_str,
+                    #class_name,
+                )));
+            }
+        }
+    };
+    Some(quote! {
+        #field_name: {
+            let value = obj.get(lock, #field_name_str)?;
+            #missing_check
+            <#ty as jsg::FromJS>::from_js(lock, value).map_err(|err| jsg::Error {
+                message: format!(
+                    "Incorrect type for the '{}' field on '{}': {}",
+                    #field_name_str,
+                    #class_name,
+                    err.message,
+                ),
+                ..err
+            })?
+        },
+    })
+}
+
 /// Generates FFI callback for JSG methods.
 ///
 /// Parameters and return values are handled via `jsg::FromJS`.
 /// See `jsg/wrappable.rs` for supported types.
+///
+/// An `async fn` method returns a promise to JavaScript. The future is awaited on the KJ event
+/// loop via `Lock::await_io` and the promise is resolved with its output, or rejected if it
+/// returns an error. The unwrapped arguments and a strong reference to `self` are moved into the
+/// future, so it must not borrow anything else.
 #[proc_macro_attribute]
 pub fn jsg_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
-    let fn_name = &input_fn.sig.ident;
-    let fn_vis = &input_fn.vis;
-    let fn_sig = &input_fn.sig;
-    let fn_block = &input_fn.block;
-    let callback_name = syn::Ident::new(&format!("{fn_name}_callback"), fn_name.span());
+    if !has_receiver(&input_fn) {
+        return error(
+            &input_fn.sig,
+            "#[jsg_method] methods must take `&self` or `&mut self`; use #[jsg_static_method] for associated functions",
+        );
+    }
+    generate_method_callback(&input_fn)
+}
+
+/// Generates FFI callback for a JSG static method.
+///
+/// Applied to an associated function without a receiver. The function is installed on the
+/// constructor, analogous to `JSG_STATIC_METHOD` in C++ JSG, and can be called without an
+/// instance of the resource.
+#[proc_macro_attribute]
+pub fn jsg_static_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    if has_receiver(&input_fn) {
+        return error(
+            &input_fn.sig,
+            "#[jsg_static_method] functions must not take a receiver",
+        );
+    }
+    generate_method_callback(&input_fn)
+}
+
+/// Marks an associated constant to be exposed as a static constant, analogous to
+/// `JSG_STATIC_CONSTANT` in C++ JSG.
+///
+/// The constant is set as a read-only property on both the constructor and the prototype. Its
+/// type must be one of `i8`, `i16`, `i32`, `u8`, `u16`, `u32`, `f32` or `f64`, which convert to a
+/// JavaScript number without loss; `#[jsg_resource]` rejects any other type. The JavaScript name
+/// defaults to the constant's Rust name; use the `name` parameter to override it.
+#[proc_macro_attribute]
+pub fn jsg_static_constant(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as syn::ImplItemConst);
+    input.into_token_stream().into()
+}
 
+/// Generates FFI callback for a JSG property getter.
+///
+/// The getter takes no arguments besides `&self`. The JavaScript property name defaults to the
+/// method name without its `get_` prefix, converted to `camelCase`. Properties are installed on
+/// the prototype unless `instance` is given, analogous to `JSG_PROTOTYPE_PROPERTY` and
+/// `JSG_INSTANCE_PROPERTY` in C++ JSG. A property without a matching `#[jsg_setter]` is read-only.
+#[proc_macro_attribute]
+pub fn jsg_getter(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    if input_fn.sig.asyncness.is_some() {
+        return error(&input_fn.sig, "#[jsg_getter] methods cannot be async");
+    }
+    if count_params(&input_fn) != 0 {
+        return error(
+            &input_fn.sig,
+            "#[jsg_getter] methods must not take any arguments",
+        );
+    }
+    generate_method_callback(&input_fn)
+}
+
+/// Alias of `#[jsg_getter]`.
+#[proc_macro_attribute]
+pub fn jsg_property(attr: TokenStream, item: TokenStream) -> TokenStream {
+    jsg_getter(attr, item)
+}
+
+/// Generates FFI callback for a JSG property setter.
+///
+/// The setter takes exactly one argument, unwrapped via `jsg::FromJS`. The JavaScript property
+/// name defaults to the method name without its `set_` prefix, converted to `camelCase`, and
+/// must match a `#[jsg_getter]` in the same impl block. The property is installed on the prototype
+/// or the instance according to the getter, so `instance` is rejected here.
+#[proc_macro_attribute]
+pub fn jsg_setter(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    if input_fn.sig.asyncness.is_some() {
+        return error(&input_fn.sig, "#[jsg_setter] methods cannot be async");
+    }
+    if count_params(&input_fn) != 1 {
+        return error(
+            &input_fn.sig,
+            "#[jsg_setter] methods must take exactly one argument",
+        );
+    }
+    generate_method_callback(&input_fn)
+}
+
+/// Generates the statements unwrapping each declared parameter from `args` via `jsg::FromJS`,
+/// along with the expressions passing the unwrapped values to the Rust function.
+///
+/// The generated statements expect `lock` and `args` to be in scope and return early from the
+/// callback after throwing if a parameter cannot be unwrapped. A `&mut jsg::Lock` parameter is
+/// passed the callback's lock instead, like `jsg::Lock&` in C++ JSG, and does not consume an
+/// argument. A trailing `jsg::Arguments<T>` parameter collects all remaining arguments.
+fn generate_arg_unwraps(
+    fn_sig: &syn::Signature,
+) -> (Vec<impl ToTokens + use<>>, Vec<impl ToTokens + use<>>) {
     let params: Vec<_> = fn_sig
         .inputs
         .iter()
@@ -105,13 +331,30 @@ pub fn jsg_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
         })
         .collect();
 
-    let (unwraps, arg_exprs): (Vec<_>, Vec<_>) = params
+    let last_param = params.iter().rposition(|ty| !is_lock_type(ty));
+    let mut index: usize = 0;
+    params
         .iter()
         .enumerate()
-        .map(|(i, ty)| {
-            let arg = syn::Ident::new(&format!("arg{i}"), fn_name.span());
+        .map(|(position, ty)| {
+            if is_lock_type(ty) {
+                return (quote! {}, quote! { &mut lock });
+            }
+            let i = index;
+            index += 1;
+            let arg = syn::Ident::new(&format!("arg{i}"), fn_sig.ident.span());
+            let value = if is_arguments_type(ty) {
+                if Some(position) != last_param {
+                    let err =
+                        syn::Error::new_spanned(ty, "jsg::Arguments<T> must be the last parameter");
+                    return (err.to_compile_error(), quote! { #arg });
+                }
+                quote! { <#ty>::from_args(&mut lock, &args, #i) }
+            } else {
+                quote! { <#ty as jsg::FromJS>::from_js(&mut lock, args.get(#i)) }
+            };
             let unwrap = quote! {
-                let #arg = match <#ty as jsg::FromJS>::from_js(&mut lock, args.get(#i)) {
+                let #arg = match #value {
                     Ok(v) => v,
                     Err(err) => {
                         lock.throw_exception(&err);
@@ -129,11 +372,137 @@ pub fn jsg_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
             };
             (unwrap, arg_expr)
         })
-        .unzip();
+        .unzip()
+}
+
+/// Generates FFI callback for a JSG resource constructor.
+///
+/// Applied to an associated function without a receiver that returns `Self` or
+/// `Result<Self, E>`. Parameters are handled via `jsg::FromJS`. The constructed resource is
+/// attached to the object created by `new`. Resources without a `#[jsg_constructor]` throw
+/// "Illegal constructor" when constructed from JavaScript.
+#[proc_macro_attribute]
+pub fn jsg_constructor(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let fn_name = &input_fn.sig.ident;
+    let fn_vis = &input_fn.vis;
+    let fn_sig = &input_fn.sig;
+    let fn_block = &input_fn.block;
+    let callback_name = syn::Ident::new(&format!("{fn_name}_callback"), fn_name.span());
+
+    if has_receiver(&input_fn) {
+        return error(
+            fn_sig,
+            "#[jsg_constructor] functions must not take a receiver",
+        );
+    }
+    if fn_sig.asyncness.is_some() {
+        return error(fn_sig, "#[jsg_constructor] functions cannot be async");
+    }
+
+    let (unwraps, arg_exprs) = generate_arg_unwraps(fn_sig);
+
+    let is_result = matches!(&fn_sig.output, syn::ReturnType::Type(_, ty) if is_result_type(ty));
+    let result_handling = if is_result {
+        quote! {
+            let resource = match result {
+                Ok(resource) => resource,
+                Err(err) => {
+                    lock.throw_exception(&err.into());
+                    return;
+                }
+            };
+        }
+    } else {
+        quote! { let resource = result; }
+    };
+
+    quote! {
+        #fn_vis #fn_sig { #fn_block }
+
+        #[automatically_derived]
+        extern "C" fn #callback_name(args: *mut jsg::v8::ffi::FunctionCallbackInfo) {
+            let mut lock = unsafe { jsg::Lock::from_args(args) };
+            let args = unsafe { jsg::v8::FunctionCallbackInfo::from_ffi(args) };
+            if !args.is_construct_call() {
+                let msg = format!(
+                    "Failed to construct '{}': Please use the 'new' operator, this object \
+                     constructor cannot be called as a function.",
+                    <Self as jsg::Type>::class_name()
+                );
+                lock.throw_exception(&jsg::Error::new_type_error(msg));
+                return;
+            }
+            #(#unwraps)*
+            let result = Self::#fn_name(#(#arg_exprs),*);
+            #result_handling
+            unsafe { jsg::attach_resource(&mut lock, jsg::Ref::new(resource), args.this().cast_unchecked()) };
+        }
+    }
+    .into()
+}
+
+fn has_receiver(input_fn: &ItemFn) -> bool {
+    input_fn
+        .sig
+        .inputs
+        .iter()
+        .any(|arg| matches!(arg, FnArg::Receiver(_)))
+}
+
+/// Counts the parameters unwrapped from JavaScript arguments, i.e. excluding the receiver and
+/// any `&mut jsg::Lock`.
+fn count_params(input_fn: &ItemFn) -> usize {
+    input_fn
+        .sig
+        .inputs
+        .iter()
+        .filter(|arg| matches!(arg, FnArg::Typed(pat_type) if !is_lock_type(&pat_type.ty)))
+        .count()
+}
+
+/// Checks if a function takes a `&mut jsg::Lock` parameter.
+fn takes_lock(fn_sig: &syn::Signature) -> bool {
+    fn_sig
+        .inputs
+        .iter()
+        .any(|arg| matches!(arg, FnArg::Typed(pat_type) if is_lock_type(&pat_type.ty)))
+}
+
+fn generate_method_callback(input_fn: &ItemFn) -> TokenStream {
+    let fn_name = &input_fn.sig.ident;
+    let fn_vis = &input_fn.vis;
+    let fn_sig = &input_fn.sig;
+    let fn_block = &input_fn.block;
+    let callback_name = syn::Ident::new(&format!("{fn_name}_callback"), fn_name.span());
+
+    let (unwraps, arg_exprs) = generate_arg_unwraps(fn_sig);
 
     // Check if return type is Result<T, E>
     let is_result = matches!(&fn_sig.output, syn::ReturnType::Type(_, ty) if is_result_type(ty));
 
+    if fn_sig.asyncness.is_some() {
+        if takes_lock(fn_sig) {
+            return error(
+                fn_sig,
+                "async methods cannot take a jsg::Lock, as the lock is released while awaiting",
+            );
+        }
+        let invocation = generate_async_invocation(input_fn, &arg_exprs, is_result);
+        return quote! {
+            #fn_vis #fn_sig { #fn_block }
+
+            #[automatically_derived]
+            extern "C" fn #callback_name(args: *mut jsg::v8::ffi::FunctionCallbackInfo) {
+                let mut lock = unsafe { jsg::Lock::from_args(args) };
+                let mut args = unsafe { jsg::v8::FunctionCallbackInfo::from_ffi(args) };
+                #(#unwraps)*
+                #invocation
+            }
+        }
+        .into();
+    }
+
     let result_handling = if is_result {
         quote! {
             match result {
@@ -147,6 +516,32 @@ pub fn jsg_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     };
 
+    // Static methods have no receiver and are invoked without unwrapping `this`. Methods taking
+    // the lock hold a strong reference to the resource instead of borrowing it from the lock.
+    let receiver = input_fn.sig.inputs.iter().find_map(|arg| match arg {
+        FnArg::Receiver(receiver) => Some(receiver),
+        FnArg::Typed(_) => None,
+    });
+    let invocation = if let Some(receiver) = receiver
+        && takes_lock(fn_sig)
+    {
+        let mutability = receiver.mutability;
+        quote! {
+            let #mutability this = jsg::unwrap_resource_ref::<Self>(&mut lock, args.this());
+            let result = this.#fn_name(#(#arg_exprs),*);
+        }
+    } else if receiver.is_some() {
+        quote! {
+            let this = args.this();
+            let self_ = jsg::unwrap_resource::<Self>(&mut lock, this);
+            let result = self_.#fn_name(#(#arg_exprs),*);
+        }
+    } else {
+        quote! {
+            let result = Self::#fn_name(#(#arg_exprs),*);
+        }
+    };
+
     quote! {
         #fn_vis #fn_sig { #fn_block }
 
@@ -155,18 +550,58 @@ pub fn jsg_method(_attr: TokenStream, item: TokenStream) -> TokenStream {
             let mut lock = unsafe { jsg::Lock::from_args(args) };
             let mut args = unsafe { jsg::v8::FunctionCallbackInfo::from_ffi(args) };
             #(#unwraps)*
-            let this = args.this();
-            let self_ = jsg::unwrap_resource::<Self>(&mut lock, this);
-            let result = self_.#fn_name(#(#arg_exprs),*);
+            #invocation
             #result_handling
         }
     }
     .into()
 }
 
+/// Generates the body of the callback for an `async fn` method, which returns a promise settled
+/// with the method's output via `Lock::await_io`.
+///
+/// The future must be `'static`, so instance methods are invoked on a strong `jsg::Ref` to the
+/// resource that is moved into the future along with the unwrapped arguments.
+fn generate_async_invocation<T: ToTokens>(
+    input_fn: &ItemFn,
+    arg_exprs: &[T],
+    is_result: bool,
+) -> impl ToTokens + use<T> {
+    let fn_name = &input_fn.sig.ident;
+    let receiver = input_fn.sig.inputs.iter().find_map(|arg| match arg {
+        FnArg::Receiver(receiver) => Some(receiver),
+        FnArg::Typed(_) => None,
+    });
+    let future = if let Some(receiver) = receiver {
+        let mutability = receiver.mutability;
+        quote! {
+            let #mutability this = jsg::unwrap_resource_ref::<Self>(&mut lock, args.this());
+            let future = async move { this.#fn_name(#(#arg_exprs),*).await };
+        }
+    } else {
+        quote! {
+            let future = async move { Self::#fn_name(#(#arg_exprs),*).await };
+        }
+    };
+    let callback = if is_result {
+        quote! { |_lock: &mut jsg::Lock, result| result.map_err(Into::into) }
+    } else {
+        quote! { |_lock: &mut jsg::Lock, result| Ok(result) }
+    };
+    quote! {
+        #future
+        let promise = lock.await_io(future, #callback);
+        args.set_return_value(promise.into());
+    }
+}
+
 /// Generates boilerplate for JSG resources.
 ///
-/// On structs: generates `jsg::Type` and `ResourceTemplate`.
+/// On structs: generates `jsg::Type` and `ResourceTemplate`. The generated
+/// `jsg::Type::memory_info` tracks every field that implements `jsg::Type`, including
+/// `jsg::Ref<R>` fields, which become edges to the referenced resources in heap snapshots. The
+/// generated `jsg::Type::visit_for_gc` visits the same fields, so that `jsg::V8Ref<T>` fields are
+/// traced through the resource's wrapper instead of keeping their values alive.
 /// On impl blocks: generates `Resource` trait with method registrations.
 #[proc_macro_attribute]
 pub fn jsg_resource(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -179,12 +614,15 @@ pub fn jsg_resource(attr: TokenStream, item: TokenStream) -> TokenStream {
     let class_name = extract_name_attribute(&attr.to_string()).unwrap_or_else(|| name.to_string());
     let template_name = syn::Ident::new(&format!("{name}Template"), name.span());
 
-    if !matches!(&input.data, Data::Struct(_)) {
+    let Data::Struct(data) = &input.data else {
         return error(
             &input,
             "#[jsg_resource] can only be applied to structs or impl blocks",
         );
-    }
+    };
+
+    let memory_info = generate_memory_info(&data.fields);
+    let visit_for_gc = generate_visit_for_gc(&data.fields);
 
     quote! {
         #input
@@ -193,18 +631,21 @@ pub fn jsg_resource(attr: TokenStream, item: TokenStream) -> TokenStream {
         impl jsg::Type for #name {
             fn class_name() -> &'static str { #class_name }
 
+            fn memory_name() -> &'static str { #class_name }
+
             fn is_exact(value: &jsg::v8::Local<jsg::v8::Value>) -> bool {
                 value.is_object()
             }
+
+            #memory_info
+
+            #visit_for_gc
         }
 
         #[automatically_derived]
         impl jsg::ToJS for #name {
-            fn to_js<'a, 'b>(self, _lock: &'a mut jsg::Lock) -> jsg::v8::Local<'b, jsg::v8::Value>
-            where
-                'b: 'a,
-            {
-                todo!("Resource to_js is not yet supported")
+            fn to_js<'s>(self, lock: &mut jsg::Lock<'s>) -> jsg::v8::Local<'s, jsg::v8::Value> {
+                jsg::ToJS::to_js(jsg::Ref::new(self), lock)
             }
         }
 
@@ -212,8 +653,8 @@ pub fn jsg_resource(attr: TokenStream, item: TokenStream) -> TokenStream {
         impl jsg::FromJS for #name {
             type ResultType = jsg::Ref<Self>;
 
-            fn from_js(_lock: &mut jsg::Lock, _value: jsg::v8::Local<jsg::v8::Value>) -> Result<Self::ResultType, jsg::Error> {
-                todo!("Resource from_js is not yet supported")
+            fn from_js(lock: &mut jsg::Lock, value: jsg::v8::Local<jsg::v8::Value>) -> Result<Self::ResultType, jsg::Error> {
+                <jsg::Ref<Self> as jsg::FromJS>::from_js(lock, value)
             }
         }
 
@@ -225,7 +666,7 @@ pub fn jsg_resource(attr: TokenStream, item: TokenStream) -> TokenStream {
         #[automatically_derived]
         impl jsg::ResourceTemplate for #template_name {
             fn new(lock: &mut jsg::Lock) -> Self {
-                Self { constructor: jsg::create_resource_constructor::<#name>(lock) }
+                Self { constructor: lock.get_template::<#name>() }
             }
 
             fn get_constructor(&self) -> &jsg::v8::Global<jsg::v8::FunctionTemplate> {
@@ -236,34 +677,176 @@ pub fn jsg_resource(attr: TokenStream, item: TokenStream) -> TokenStream {
     .into()
 }
 
-fn generate_resource_impl(impl_block: &ItemImpl) -> TokenStream {
-    let self_ty = &impl_block.self_ty;
+/// Generates the `jsg::Member` registrations for the annotated methods of a resource impl block.
+///
+/// Getters and setters sharing a JavaScript name are combined into a single property.
+fn generate_member_registrations(
+    impl_block: &ItemImpl,
+) -> Result<Vec<impl ToTokens + use<>>, TokenStream> {
+    let mut method_registrations = Vec::new();
+    let mut properties: Vec<PropertyRegistration> = Vec::new();
+    let mut has_constructor = false;
+    for item in &impl_block.items {
+        if let syn::ImplItem::Const(constant) = item {
+            if let Some(js_name) = find_static_constant_name(constant) {
+                if !is_static_constant_type(&constant.ty) {
+                    return Err(error(
+                        &constant.ty,
+                        "#[jsg_static_constant] requires a constant of type i8, i16, i32, u8, u16, \
+                         u32, f32 or f64, which converts to a JavaScript number without loss",
+                    ));
+                }
+                let rust_name = &constant.ident;
+                method_registrations.push(quote! {
+                    jsg::Member::StaticConstant {
+                        name: #js_name.to_owned(),
+                        value: f64::from(Self::#rust_name),
+                    }
+                });
+            }
+            continue;
+        }
+        let syn::ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let Some((kind, attr)) = find_member_attribute(method) else {
+            continue;
+        };
+
+        let rust_name = &method.sig.ident;
+        let attr_str = attr.meta.to_token_stream().to_string();
+        let callback = syn::Ident::new(&format!("{rust_name}_callback"), rust_name.span());
+
+        match kind {
+            MemberKind::Constructor => {
+                if has_constructor {
+                    return Err(error(
+                        method,
+                        "a resource can only have one #[jsg_constructor]",
+                    ));
+                }
+                has_constructor = true;
+                method_registrations.push(quote! {
+                    jsg::Member::Constructor { callback: Self::#callback }
+                });
+            }
+            MemberKind::Method => {
+                let js_name = extract_name_attribute(&attr_str)
+                    .unwrap_or_else(|| snake_to_camel(&rust_name.to_string()));
+                method_registrations.push(quote! {
+                    jsg::Member::Method { name: #js_name.to_owned(), callback: Self::#callback }
+                });
+            }
+            MemberKind::StaticMethod => {
+                let js_name = extract_name_attribute(&attr_str)
+                    .unwrap_or_else(|| snake_to_camel(&rust_name.to_string()));
+                method_registrations.push(quote! {
+                    jsg::Member::StaticMethod { name: #js_name.to_owned(), callback: Self::#callback }
+                });
+            }
+            MemberKind::Setter if has_flag_attribute(&attr_str, "instance") => {
+                return Err(error(
+                    attr,
+                    "`instance` is not supported on #[jsg_setter]; the property is installed \
+                     where its #[jsg_getter] puts it",
+                ));
+            }
+            MemberKind::Getter | MemberKind::Setter => {
+                record_property(&mut properties, kind, rust_name, &attr_str, callback);
+            }
+        }
+    }
 
-    let method_registrations: Vec<_> = impl_block
-        .items
+    for property in &properties {
+        let Some(getter) = &property.getter else {
+            return Err(error(
+                &impl_block.self_ty,
+                &format!(
+                    "#[jsg_setter] for '{}' has no matching #[jsg_getter]",
+                    property.name
+                ),
+            ));
+        };
+        let name = &property.name;
+        let setter = property
+            .setter
+            .as_ref()
+            .map_or_else(|| quote! { None }, |setter| quote! { Some(Self::#setter) });
+        let variant = if property.instance {
+            quote! { InstanceProperty }
+        } else {
+            quote! { Property }
+        };
+        method_registrations.push(quote! {
+            jsg::Member::#variant {
+                name: #name.to_owned(),
+                getter_callback: Self::#getter,
+                setter_callback: #setter,
+            }
+        });
+    }
+
+    Ok(method_registrations)
+}
+
+/// Returns the JavaScript name of an associated constant annotated with `#[jsg_static_constant]`,
+/// or `None` if the constant is not annotated.
+fn find_static_constant_name(constant: &syn::ImplItemConst) -> Option<String> {
+    let attr = constant.attrs.iter().find(|a| {
+        a.path()
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "jsg_static_constant")
+    })?;
+    let attr_str = attr.meta.to_token_stream().to_string();
+    Some(extract_name_attribute(&attr_str).unwrap_or_else(|| constant.ident.to_string()))
+}
+
+/// Records a getter or setter in `properties`, pairing it with an existing entry of the same
+/// JavaScript name.
+fn record_property(
+    properties: &mut Vec<PropertyRegistration>,
+    kind: MemberKind,
+    rust_name: &syn::Ident,
+    attr_str: &str,
+    callback: syn::Ident,
+) {
+    let prefix = if kind == MemberKind::Getter {
+        "get_"
+    } else {
+        "set_"
+    };
+    let js_name = extract_name_attribute(attr_str).unwrap_or_else(|| {
+        let name = rust_name.to_string();
+        snake_to_camel(name.strip_prefix(prefix).unwrap_or(&name))
+    });
+    let index = properties
         .iter()
-        .filter_map(|item| {
-            let syn::ImplItem::Fn(method) = item else {
-                return None;
-            };
-            let attr = method.attrs.iter().find(|a| {
-                a.path().is_ident("jsg")
-                    || a.path()
-                        .segments
-                        .last()
-                        .is_some_and(|s| s.ident == "jsg_method")
-            })?;
-
-            let rust_name = &method.sig.ident;
-            let js_name = extract_name_attribute(&attr.meta.to_token_stream().to_string())
-                .unwrap_or_else(|| snake_to_camel(&rust_name.to_string()));
-            let callback = syn::Ident::new(&format!("{rust_name}_callback"), rust_name.span());
-
-            Some(quote! {
-                jsg::Member::Method { name: #js_name.to_owned(), callback: Self::#callback }
-            })
-        })
-        .collect();
+        .position(|p| p.name == js_name)
+        .unwrap_or_else(|| {
+            properties.push(PropertyRegistration {
+                name: js_name,
+                getter: None,
+                setter: None,
+                instance: false,
+            });
+            properties.len() - 1
+        });
+    let property = &mut properties[index];
+    if kind == MemberKind::Getter {
+        property.getter = Some(callback);
+        property.instance = has_flag_attribute(attr_str, "instance");
+    } else {
+        property.setter = Some(callback);
+    }
+}
+
+fn generate_resource_impl(impl_block: &ItemImpl) -> TokenStream {
+    let self_ty = &impl_block.self_ty;
+    let method_registrations = match generate_member_registrations(impl_block) {
+        Ok(registrations) => registrations,
+        Err(err) => return err,
+    };
 
     let type_name = match &**self_ty {
         syn::Type::Path(p) => p
@@ -302,6 +885,40 @@ fn generate_resource_impl(impl_block: &ItemImpl) -> TokenStream {
     .into()
 }
 
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum MemberKind {
+    Constructor,
+    Method,
+    StaticMethod,
+    Getter,
+    Setter,
+}
+
+struct PropertyRegistration {
+    name: String,
+    getter: Option<syn::Ident>,
+    setter: Option<syn::Ident>,
+    instance: bool,
+}
+
+/// Finds the JSG member attribute (`#[jsg_method]`, `#[jsg_getter]`, ...) on a method.
+fn find_member_attribute(method: &syn::ImplItemFn) -> Option<(MemberKind, &syn::Attribute)> {
+    method.attrs.iter().find_map(|a| {
+        if a.path().is_ident("jsg") {
+            return Some((MemberKind::Method, a));
+        }
+        let kind = match a.path().segments.last()?.ident.to_string().as_str() {
+            "jsg_constructor" => MemberKind::Constructor,
+            "jsg_method" => MemberKind::Method,
+            "jsg_static_method" => MemberKind::StaticMethod,
+            "jsg_getter" | "jsg_property" => MemberKind::Getter,
+            "jsg_setter" => MemberKind::Setter,
+            _ => return None,
+        };
+        Some((kind, a))
+    })
+}
+
 fn error(tokens: &impl ToTokens, msg: &str) -> TokenStream {
     syn::Error::new_spanned(tokens, msg)
         .to_compile_error()
@@ -320,6 +937,16 @@ fn extract_name_attribute(attr_str: &str) -> Option<String> {
         .map(str::to_owned)
 }
 
+/// Checks if an attribute contains a bare flag, e.g. `instance` in `#[jsg_getter(instance)]`.
+fn has_flag_attribute(attr_str: &str, flag: &str) -> bool {
+    let Some(args) = attr_str.split_once('(').map(|(_, args)| args) else {
+        return false;
+    };
+    args.trim_end_matches(')')
+        .split(',')
+        .any(|arg| arg.trim() == flag)
+}
+
 fn snake_to_camel(s: &str) -> String {
     let mut result = String::new();
     let mut cap_next = false;
@@ -337,6 +964,30 @@ fn snake_to_camel(s: &str) -> String {
     result
 }
 
+/// Checks if a type is `&mut Lock`, e.g. `&mut jsg::Lock`.
+fn is_lock_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Reference(reference) = ty
+        && reference.mutability.is_some()
+        && let syn::Type::Path(type_path) = reference.elem.as_ref()
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        return segment.ident == "Lock";
+    }
+    false
+}
+
+/// Checks if a type is `jsg::Arguments<T>`, which collects the remaining arguments. The type must
+/// be written with its `jsg::` path, so that other types named `Arguments` are unwrapped as usual.
+fn is_arguments_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && let [module, name] = type_path.path.segments.iter().collect::<Vec<_>>()[..]
+    {
+        return module.ident == "jsg" && name.ident == "Arguments";
+    }
+    false
+}
+
 /// Checks if a type is `Result<T, E>`.
 fn is_result_type(ty: &syn::Type) -> bool {
     if let syn::Type::Path(type_path) = ty
@@ -347,6 +998,29 @@ fn is_result_type(ty: &syn::Type) -> bool {
     false
 }
 
+/// Checks if a type is `Option<T>` or `Nullable<T>`, i.e. a struct field that may be omitted.
+fn is_optional_type(ty: &syn::Type) -> bool {
+    if let syn::Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        return segment.ident == "Option" || segment.ident == "Nullable";
+    }
+    false
+}
+
+/// Checks if a type is a primitive that `f64::from()` converts to a JavaScript number without
+/// loss, i.e. one that a `#[jsg_static_constant]` may have.
+fn is_static_constant_type(ty: &syn::Type) -> bool {
+    const NUMBER_TYPES: [&str; 8] = ["i8", "i16", "i32", "u8", "u16", "u32", "f32", "f64"];
+    if let syn::Type::Path(type_path) = ty
+        && type_path.qself.is_none()
+        && let Some(ident) = type_path.path.get_ident()
+    {
+        return NUMBER_TYPES.iter().any(|name| ident == name);
+    }
+    false
+}
+
 /// Generates `jsg::Type` and `jsg::FromJS` implementations for union types.
 ///
 /// This macro automatically implements the traits needed for enums with
@@ -428,6 +1102,20 @@ pub fn jsg_oneof(_attr: TokenStream, item: TokenStream) -> TokenStream {
         })
         .collect();
 
+    let tracked_variants: Vec<_> = variants
+        .iter()
+        .map(|(variant_name, _)| {
+            quote! { Self::#variant_name(value) => jsg::Type::track_memory(value, tracker, edge_name), }
+        })
+        .collect();
+
+    let visited_variants: Vec<_> = variants
+        .iter()
+        .map(|(variant_name, _)| {
+            quote! { Self::#variant_name(value) => jsg::Type::visit_for_gc(value, visitor), }
+        })
+        .collect();
+
     let error_msg = quote! {
         let expected: Vec<&str> = vec![#(#type_names),*];
         let msg = format!(
@@ -450,6 +1138,18 @@ pub fn jsg_oneof(_attr: TokenStream, item: TokenStream) -> TokenStream {
             fn is_exact(value: &jsg::v8::Local<jsg::v8::Value>) -> bool {
                 #(#is_exact_checks)||*
             }
+
+            fn track_memory(&self, tracker: &mut jsg::MemoryTracker, edge_name: &str) {
+                match self {
+                    #(#tracked_variants)*
+                }
+            }
+
+            fn visit_for_gc(&self, visitor: &mut jsg::GcVisitor) {
+                match self {
+                    #(#visited_variants)*
+                }
+            }
         }
 
         #[automatically_derived]
@@ -1,5 +1,7 @@
 #include "ffi.h"
 
+#include <workerd/jsg/dom-exception.h>
+#include <workerd/jsg/memory.h>
 #include <workerd/jsg/setup.h>
 #include <workerd/rust/jsg-test/lib.rs.h>
 #include <workerd/rust/jsg/ffi-inl.h>
@@ -10,6 +12,7 @@
 #include <v8.h>
 
 #include <kj/common.h>
+#include <kj/vector.h>
 
 using namespace kj_rs;
 
@@ -22,7 +25,9 @@ bool initPredictableMode = (workerd::setPredictableModeForTest(), true);
 namespace workerd {
 
 struct TestContext: public jsg::Object, public jsg::ContextGlobal {
-  JSG_RESOURCE_TYPE(TestContext) {}
+  JSG_RESOURCE_TYPE(TestContext) {
+    JSG_NESTED_TYPE_NAMED(jsg::DOMException, DOMException);
+  }
 };
 JSG_DECLARE_ISOLATE_TYPE(TestIsolate, TestContext);
 
@@ -34,22 +39,114 @@ static ::workerd::jsg::V8System& getV8System() {
   return v8System;
 }
 
+namespace {
+// Resumes I/O awaited by Rust code like an embedder does: the continuation takes its own lock once
+// the event loop completes the I/O. `EvalContext::run_event_loop()` runs the event loop while the
+// test already holds the lock, in which case the continuation runs under that lock instead.
+class TestIoScheduler final: public ::workerd::rust::jsg::IoScheduler,
+                             private kj::TaskSet::ErrorHandler {
+ public:
+  TestIoScheduler(TestIsolate& isolate, v8::Local<v8::Context> context)
+      : isolate(isolate),
+        context(isolate.getIsolate(), context),
+        tasks(*this) {}
+
+  bool awaitIo(kj::Promise<kj::Function<void(::workerd::jsg::Lock&)>> promise) override {
+    tasks.add(
+        promise.then([this](kj::Function<void(::workerd::jsg::Lock&)> continuation) mutable {
+      auto v8Isolate = isolate.getIsolate();
+      if (v8Isolate->GetData(::workerd::jsg::SET_DATA_LOCK) != nullptr) {
+        resume(::workerd::jsg::Lock::from(v8Isolate), continuation);
+      } else {
+        isolate.runInLockScope([&](TestIsolate::Lock& lock) { resume(lock, continuation); });
+      }
+    }));
+    return true;
+  }
+
+ private:
+  void resume(
+      ::workerd::jsg::Lock& js, kj::Function<void(::workerd::jsg::Lock&)>& continuation) {
+    js.withinHandleScope([&] {
+      v8::Context::Scope contextScope(context.Get(js.v8Isolate));
+      continuation(js);
+      js.v8Isolate->PerformMicrotaskCheckpoint();
+    });
+  }
+
+  void taskFailed(kj::Exception&& exception) override {
+    KJ_LOG(ERROR, "Rust JSG I/O task failed", exception);
+  }
+
+  TestIsolate& isolate;
+  v8::Global<v8::Context> context;
+  kj::TaskSet tasks;
+};
+}  // namespace
+
 TestHarness::TestHarness(::workerd::jsg::V8StackScope&)
-    : isolate(kj::heap<TestIsolate>(getV8System(), kj::heap<::workerd::jsg::IsolateObserver>())),
+    : waitScope(eventLoop),
+      isolate(kj::heap<TestIsolate>(getV8System(), kj::heap<::workerd::jsg::IsolateObserver>())),
       locker(isolate->getIsolate()),
-      isolateScope(isolate->getIsolate()),
-      realm(::workerd::rust::jsg::realm_create(isolate->getIsolate())) {
-  isolate->getIsolate()->SetData(::workerd::jsg::SetDataIndex::SET_DATA_RUST_REALM, &*realm);
-}
+      isolateScope(isolate->getIsolate()) {}
 
 kj::Own<TestHarness> create_test_harness() {
   return ::workerd::jsg::runInV8Stack(
       [](::workerd::jsg::V8StackScope& stackScope) { return kj::heap<TestHarness>(stackScope); });
 }
 
-EvalContext::EvalContext(v8::Isolate* isolate, v8::Local<v8::Context> context)
+EvalContext::EvalContext(v8::Isolate* isolate,
+    v8::Local<v8::Context> context,
+    kj::EventLoop& eventLoop,
+    kj::WaitScope& waitScope)
     : v8Isolate(isolate),
-      v8Context(isolate, context) {}
+      v8Context(isolate, context),
+      eventLoop(eventLoop),
+      waitScope(waitScope) {}
+
+void EvalContext::run_event_loop() const {
+  // Microtasks may wake futures awaiting promises, which in turn may queue more microtasks, so
+  // alternate between the two until neither has work left.
+  do {
+    v8Isolate->PerformMicrotaskCheckpoint();
+    waitScope.poll();
+  } while (eventLoop.isRunnable());
+  v8Isolate->PerformMicrotaskCheckpoint();
+}
+
+void EvalContext::request_gc() const {
+  v8Isolate->RequestGarbageCollectionForTesting(
+      v8::Isolate::GarbageCollectionType::kFullGarbageCollection);
+}
+
+void EvalContext::run_without_lock(size_t data, ::rust::Fn<void(size_t)> callback) const {
+  v8::Unlocker unlocker(v8Isolate);
+  callback(data);
+}
+
+void EvalContext::run_without_jsg_lock(size_t data, ::rust::Fn<void(size_t)> callback) const {
+  auto jsgLock = v8Isolate->GetData(::workerd::jsg::SET_DATA_LOCK);
+  v8Isolate->SetData(::workerd::jsg::SET_DATA_LOCK, nullptr);
+  KJ_DEFER(v8Isolate->SetData(::workerd::jsg::SET_DATA_LOCK, jsgLock));
+  callback(data);
+}
+
+::rust::String EvalContext::take_heap_snapshot() const {
+  kj::Vector<char> serialized;
+  ::workerd::jsg::HeapSnapshotActivity activity([](auto, auto) { return true; });
+  ::workerd::jsg::HeapSnapshotWriter writer([&](kj::Maybe<kj::ArrayPtr<char>> maybeChunk) {
+    KJ_IF_SOME(chunk, maybeChunk) {
+      serialized.addAll(chunk);
+    }
+    return true;
+  });
+
+  ::workerd::jsg::HeapSnapshotDeleter deleter;
+  auto snapshot = kj::Own<const v8::HeapSnapshot>(
+      v8Isolate->GetHeapProfiler()->TakeHeapSnapshot(&activity, nullptr, true, true), deleter);
+  snapshot->Serialize(&writer, v8::HeapSnapshot::kJSON);
+  return ::rust::String(serialized.begin(), serialized.size());
+}
 
 void EvalContext::set_global(::rust::Str name, ::workerd::rust::jsg::Local value) const {
   auto ctx = v8Context.Get(v8Isolate);
@@ -89,12 +186,40 @@ EvalResult EvalContext::eval(::rust::Str code) const {
 
 void TestHarness::run_in_context(
     size_t data, ::rust::Fn<void(size_t, Isolate*, EvalContext&)> callback) const {
+  kj::Maybe<::workerd::jsg::JsContext<TestContext>> context;
+  isolate->runInLockScope([&](TestIsolate::Lock& lock) {
+    auto& jsContext = context.emplace(lock.newContext<TestContext>());
+    v8::Local<v8::Context> v8Context = jsContext.getHandle(lock.v8Isolate);
+    v8::Context::Scope contextScope(v8Context);
+    ::workerd::rust::jsg::realm_create_for_context(
+        lock.v8Isolate, v8Context, kj::heap<TestIoScheduler>(*isolate, v8Context));
+
+    EvalContext evalContext(lock.v8Isolate, v8Context, eventLoop, waitScope);
+    callback(data, lock.v8Isolate, evalContext);
+  });
+
+  // Like the events of a real embedder, I/O still pending once the callback returns completes
+  // outside of the lock, and its continuations take their own.
+  while (eventLoop.isRunnable()) {
+    waitScope.poll();
+  }
+
+  isolate->runInLockScope([&](TestIsolate::Lock& lock) {
+    auto& jsContext = KJ_ASSERT_NONNULL(context);
+    v8::Local<v8::Context> v8Context = jsContext.getHandle(lock.v8Isolate);
+    v8::Context::Scope contextScope(v8Context);
+    ::workerd::rust::jsg::realm_dispose(v8Context);
+    context = kj::none;
+  });
+}
+
+void TestHarness::run_in_plain_context(
+    size_t data, ::rust::Fn<void(size_t, Isolate*, EvalContext&)> callback) const {
   isolate->runInLockScope([&](TestIsolate::Lock& lock) {
-    auto context = lock.newContext<TestContext>();
-    v8::Local<v8::Context> v8Context = context.getHandle(lock.v8Isolate);
+    auto v8Context = v8::Context::New(lock.v8Isolate);
     v8::Context::Scope contextScope(v8Context);
 
-    EvalContext evalContext(lock.v8Isolate, v8Context);
+    EvalContext evalContext(lock.v8Isolate, v8Context, eventLoop, waitScope);
     callback(data, lock.v8Isolate, evalContext);
   });
 }
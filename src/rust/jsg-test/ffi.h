@@ -8,6 +8,7 @@
 #include <rust/cxx.h>
 #include <v8.h>
 
+#include <kj/async.h>
 #include <kj/function.h>
 #include <kj/memory.h>
 
@@ -22,13 +23,25 @@ struct EvalResult;
 
 class EvalContext {
  public:
-  EvalContext(v8::Isolate* isolate, v8::Local<v8::Context> context);
+  EvalContext(v8::Isolate* isolate,
+      v8::Local<v8::Context> context,
+      kj::EventLoop& eventLoop,
+      kj::WaitScope& waitScope);
 
   EvalResult eval(::rust::Str code) const;
   void set_global(::rust::Str name, ::workerd::rust::jsg::Local value) const;
+  void run_event_loop() const;
+  void request_gc() const;
+  // Runs a callback with the isolate lock released, for Rust code that must run without it.
+  void run_without_lock(size_t data, ::rust::Fn<void(size_t)> callback) const;
+  // Runs a callback with the isolate locked but without a jsg::Lock, as in a GC callback.
+  void run_without_jsg_lock(size_t data, ::rust::Fn<void(size_t)> callback) const;
+  ::rust::String take_heap_snapshot() const;
 
   v8::Isolate* v8Isolate;
   v8::Global<v8::Context> v8Context;
+  kj::EventLoop& eventLoop;
+  kj::WaitScope& waitScope;
 };
 
 // Testing harness that provides a simple V8 isolate for Rust JSG testing
@@ -41,11 +54,20 @@ class TestHarness {
   // The callback receives the data pointer, isolate and a context
   void run_in_context(size_t data, ::rust::Fn<void(size_t, Isolate*, EvalContext&)> callback) const;
 
+  // Like `run_in_context()`, but in a context created with `v8::Context::New()`, which has no Rust
+  // Realm until Rust code running in it needs one. The context is not disposed, so its Realm is
+  // only dropped once a later GC collects it.
+  void run_in_plain_context(
+      size_t data, ::rust::Fn<void(size_t, Isolate*, EvalContext&)> callback) const;
+
  private:
+  // The event loop drives futures passed to `Lock::await_io()` and must outlive the Realm of each
+  // context.
+  mutable kj::EventLoop eventLoop;
+  mutable kj::WaitScope waitScope;
   mutable kj::Own<TestIsolate> isolate;
   mutable v8::Locker locker;
   mutable v8::Isolate::Scope isolateScope;
-  mutable ::rust::Box<::workerd::rust::jsg::Realm> realm;
 };
 
 kj::Own<TestHarness> create_test_harness();
@@ -35,9 +35,27 @@ mod ffi {
             data: usize, /* callback */
             callback: unsafe fn(usize /* callback */, *mut Isolate, Pin<&mut EvalContext>),
         );
+        pub unsafe fn run_in_plain_context(
+            self: &TestHarness,
+            data: usize, /* callback */
+            callback: unsafe fn(usize /* callback */, *mut Isolate, Pin<&mut EvalContext>),
+        );
 
         pub unsafe fn eval(self: &EvalContext, code: &str) -> EvalResult;
         pub unsafe fn set_global(self: &EvalContext, name: &str, value: Local);
+        pub unsafe fn run_event_loop(self: &EvalContext);
+        pub unsafe fn request_gc(self: &EvalContext);
+        pub unsafe fn run_without_lock(
+            self: &EvalContext,
+            data: usize, /* callback */
+            callback: unsafe fn(usize /* callback */),
+        );
+        pub unsafe fn run_without_jsg_lock(
+            self: &EvalContext,
+            data: usize, /* callback */
+            callback: unsafe fn(usize /* callback */),
+        );
+        pub unsafe fn take_heap_snapshot(self: &EvalContext) -> String;
     }
 }
 
@@ -74,7 +92,7 @@ impl EvalError<'_> {
     }
 }
 impl EvalContext<'_> {
-    pub fn eval<T>(&self, lock: &mut jsg::Lock, code: &str) -> Result<T, EvalError<'_>>
+    pub fn eval<'s, T>(&self, lock: &mut jsg::Lock<'s>, code: &str) -> Result<T, EvalError<'s>>
     where
         T: jsg::FromJS<ResultType = T>,
     {
@@ -109,6 +127,45 @@ impl EvalContext<'_> {
     pub fn set_global(&self, name: &str, value: v8::Local<v8::Value>) {
         unsafe { self.inner.set_global(name, value.into_ffi()) }
     }
+
+    /// Runs the KJ event loop and the V8 microtask queue until neither has work left, completing
+    /// any I/O started with `Lock::await_io` and any promise reactions that can make progress.
+    pub fn run_event_loop(&self) {
+        unsafe { self.inner.run_event_loop() }
+    }
+
+    /// Runs a full garbage collection. Objects referenced only by local handles created in the
+    /// current callback stay alive, so tests should create the objects they expect to be
+    /// collected in JavaScript.
+    pub fn request_gc(&self) {
+        unsafe { self.inner.request_gc() }
+    }
+
+    /// Runs `callback` with the isolate lock released, like a future on the KJ event loop of an
+    /// embedder. The callback must not call into V8.
+    pub fn run_without_lock<F: FnOnce()>(&self, callback: F) {
+        let mut callback = Some(callback);
+        unsafe {
+            self.inner
+                .run_without_lock(&raw mut callback as usize, without_lock_trampoline::<F>);
+        }
+    }
+
+    /// Runs `callback` with the isolate still locked but without the embedder's `jsg::Lock`, like
+    /// Rust code running in a GC callback.
+    pub fn run_without_jsg_lock<F: FnOnce()>(&self, callback: F) {
+        let mut callback = Some(callback);
+        unsafe {
+            self.inner
+                .run_without_jsg_lock(&raw mut callback as usize, without_lock_trampoline::<F>);
+        }
+    }
+
+    /// Takes a heap snapshot, including the embedder graph built from `Type::memory_info()`, and
+    /// returns it serialized as JSON in the `.heapsnapshot` format.
+    pub fn take_heap_snapshot(&self) -> String {
+        unsafe { self.inner.take_heap_snapshot() }
+    }
 }
 
 impl Harness {
@@ -128,32 +185,54 @@ impl Harness {
     where
         F: FnOnce(&mut jsg::Lock, &mut EvalContext) -> Result<(), jsg::Error>,
     {
-        #[expect(clippy::needless_pass_by_value)]
-        fn trampoline<F>(
-            data: usize,
-            isolate: *mut v8::ffi::Isolate,
-            context: Pin<&mut ffi::EvalContext>,
-        ) where
-            F: FnOnce(&mut jsg::Lock, &mut EvalContext) -> Result<(), jsg::Error>,
-        {
-            let cb = unsafe { &mut *(data as *mut Option<F>) };
-            if let Some(callback) = cb.take() {
-                let isolate_ptr = unsafe { v8::IsolatePtr::from_ffi(isolate) };
-                let mut eval_context = EvalContext {
-                    inner: &context,
-                    isolate: isolate_ptr,
-                };
-                let mut lock = unsafe { jsg::Lock::from_isolate_ptr(isolate) };
-                if let Err(e) = callback(&mut lock, &mut eval_context) {
-                    panic!("Test failed: {}: {}", e.name, e.message);
-                }
-            }
+        let mut callback = Some(callback);
+        unsafe {
+            self.0
+                .run_in_context(&raw mut callback as usize, context_trampoline::<F>);
         }
+    }
 
+    /// Runs a callback within a context created with `v8::Context::New()`, which unlike the
+    /// contexts of `run_in_context()` was not set up through `realm_create_for_context()`, like
+    /// contexts created by other C++ code. Nothing refers to the context once the callback returns,
+    /// so its Realm is dropped by the next full GC.
+    pub fn run_in_plain_context<F>(&self, callback: F)
+    where
+        F: FnOnce(&mut jsg::Lock, &mut EvalContext) -> Result<(), jsg::Error>,
+    {
         let mut callback = Some(callback);
         unsafe {
             self.0
-                .run_in_context(&raw mut callback as usize, trampoline::<F>);
+                .run_in_plain_context(&raw mut callback as usize, context_trampoline::<F>);
+        }
+    }
+}
+
+fn without_lock_trampoline<F: FnOnce()>(data: usize) {
+    let cb = unsafe { &mut *(data as *mut Option<F>) };
+    if let Some(callback) = cb.take() {
+        callback();
+    }
+}
+
+#[expect(clippy::needless_pass_by_value)]
+fn context_trampoline<F>(
+    data: usize,
+    isolate: *mut v8::ffi::Isolate,
+    context: Pin<&mut ffi::EvalContext>,
+) where
+    F: FnOnce(&mut jsg::Lock, &mut EvalContext) -> Result<(), jsg::Error>,
+{
+    let cb = unsafe { &mut *(data as *mut Option<F>) };
+    if let Some(callback) = cb.take() {
+        let isolate_ptr = unsafe { v8::IsolatePtr::from_ffi(isolate) };
+        let mut eval_context = EvalContext {
+            inner: &context,
+            isolate: isolate_ptr,
+        };
+        let mut lock = unsafe { jsg::Lock::from_isolate_ptr(isolate) };
+        if let Err(e) = callback(&mut lock, &mut eval_context) {
+            panic!("Test failed: {}: {}", e.name, e.message);
         }
     }
 }
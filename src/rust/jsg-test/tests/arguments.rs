@@ -0,0 +1,126 @@
+//! Tests for optional trailing parameters and `jsg::Arguments<T>` rest parameters.
+
+use jsg::NonCoercible;
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+mod options {
+    use jsg_macros::jsg_struct;
+
+    /// A struct that happens to be named `Arguments`, unwrapped like any other parameter.
+    #[jsg_struct]
+    pub struct Arguments {
+        pub prefix: String,
+    }
+}
+
+#[jsg_resource]
+struct Formatter {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Formatter {
+    #[jsg_method]
+    pub fn join(&self, separator: String, parts: jsg::Arguments<String>) -> String {
+        parts.join(&separator)
+    }
+
+    #[jsg_method]
+    pub fn sum(&self, values: jsg::Arguments<f64>) -> f64 {
+        values.into_iter().sum()
+    }
+
+    /// The lock does not consume an argument, even when it precedes the rest parameter.
+    #[jsg_method]
+    pub fn count_strings(
+        &self,
+        _lock: &mut jsg::Lock,
+        values: jsg::Arguments<NonCoercible<String>>,
+    ) -> u32 {
+        u32::try_from(values.len()).unwrap()
+    }
+
+    #[jsg_method]
+    pub fn describe(&self, name: String, first: Option<f64>, second: Option<String>) -> String {
+        format!("{name} {first:?} {second:?}")
+    }
+
+    #[jsg_method]
+    pub fn count(&self, first: Option<String>, rest: jsg::Arguments<jsg::Value>) -> String {
+        format!("{first:?} {}", rest.len())
+    }
+
+    #[jsg_method]
+    pub fn prefix(&self, options: options::Arguments, value: String) -> String {
+        format!("{}{value}", options.prefix)
+    }
+}
+
+/// Evaluates `code` with a `Formatter` installed as `formatter`, expecting it to return
+/// `expected`.
+fn check_with_formatter(code: &str, expected: &str) {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let formatter = jsg::Ref::new(Formatter {
+            _state: ResourceState::default(),
+        });
+        let formatter = formatter.to_js(lock);
+        ctx.set_global("formatter", formatter);
+
+        let result: String = ctx.eval(lock, code).unwrap();
+        assert_eq!(result, expected);
+        Ok(())
+    });
+}
+
+#[test]
+fn arguments_collect_remaining_arguments() {
+    check_with_formatter("formatter.join('-', 'a', 'b', 'c')", "a-b-c");
+    check_with_formatter("formatter.join('-')", "");
+    check_with_formatter("String(formatter.sum(1, 2, 3.5))", "6.5");
+    check_with_formatter("String(formatter.sum())", "0");
+    check_with_formatter("String(formatter.countStrings('a', 'b'))", "2");
+}
+
+#[test]
+fn arguments_report_unwrap_errors() {
+    check_with_formatter(
+        "try { formatter.countStrings('a', 2); 'no error' } \
+         catch (e) { `${e.name} ${e.message.startsWith('Incorrect type for argument 2: ')}` }",
+        "TypeError true",
+    );
+    check_with_formatter(
+        "try { formatter.countStrings('a', 'b', 3); 'no error' } \
+         catch (e) { `${e.name} ${e.message.startsWith('Incorrect type for argument 3: ')}` }",
+        "TypeError true",
+    );
+}
+
+#[test]
+fn omitted_trailing_options_are_none() {
+    check_with_formatter("formatter.describe('a')", "a None None");
+    check_with_formatter("formatter.describe('a', 1)", "a Some(1.0) None");
+    check_with_formatter(
+        "formatter.describe('a', undefined, 'b')",
+        "a None Some(\"b\")",
+    );
+    // Extra arguments are ignored.
+    check_with_formatter(
+        "formatter.describe('a', 1, 'b', 'c', 'd')",
+        "a Some(1.0) Some(\"b\")",
+    );
+}
+
+#[test]
+fn other_types_named_arguments_are_not_rest_parameters() {
+    check_with_formatter("formatter.prefix({ prefix: '> ' }, 'a')", "> a");
+}
+
+#[test]
+fn options_followed_by_arguments() {
+    check_with_formatter("formatter.count()", "None 0");
+    check_with_formatter("formatter.count('a', 1, {}, null)", "Some(\"a\") 3");
+}
@@ -0,0 +1,163 @@
+//! Tests for binary data: `jsg::BufferSource`, `&[u8]` and `Vec<u8>`.
+
+use jsg::BufferSource;
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ResourceTemplate;
+use jsg::ToJS;
+use jsg_macros::jsg_resource;
+use jsg_macros::jsg_static_method;
+
+use crate::EvalError;
+
+#[jsg_resource]
+struct Bytes {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Bytes {
+    #[jsg_static_method]
+    pub fn sum(data: &[u8]) -> f64 {
+        data.iter().map(|&byte| f64::from(byte)).sum()
+    }
+
+    #[jsg_static_method]
+    pub fn reverse(mut data: Vec<u8>) -> Vec<u8> {
+        data.reverse();
+        data
+    }
+
+    #[jsg_static_method]
+    pub fn byte_length(data: BufferSource) -> f64 {
+        #[expect(clippy::cast_precision_loss)]
+        let length = data.len() as f64;
+        length
+    }
+}
+
+fn install_bytes(lock: &mut jsg::Lock, ctx: &crate::EvalContext) {
+    let template = BytesTemplate::new(lock);
+    let constructor = template.get_constructor().as_local(lock).get_function(lock);
+    ctx.set_global("Bytes", constructor);
+}
+
+#[test]
+fn slice_parameters_borrow_buffer_sources() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        install_bytes(lock, ctx);
+
+        for (code, expected) in [
+            ("Bytes.sum(new Uint8Array([1, 2, 3]))", 6.0),
+            ("Bytes.sum(new Uint8Array([1, 2, 3]).buffer)", 6.0),
+            (
+                "Bytes.sum(new Uint8Array([10, 20, 30, 40]).subarray(1, 3))",
+                50.0,
+            ),
+            (
+                "Bytes.sum(new DataView(new Uint8Array([5, 6, 7]).buffer, 1))",
+                13.0,
+            ),
+            ("Bytes.sum(new Uint16Array([0x0101]))", 2.0),
+            ("Bytes.sum(new ArrayBuffer(0))", 0.0),
+            ("Bytes.byteLength(new Float64Array(2))", 16.0),
+        ] {
+            let result: f64 = ctx.eval(lock, code).unwrap();
+            assert!((result - expected).abs() < f64::EPSILON, "{code}");
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn vec_u8_is_copied_and_returned_as_uint8_array() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        install_bytes(lock, ctx);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "const input = new Uint8Array([1, 2, 3]); \
+                 const output = Bytes.reverse(input); \
+                 output instanceof Uint8Array && output.join() === '3,2,1' && \
+                 input.join() === '1,2,3' && output.buffer !== input.buffer",
+            )
+            .unwrap();
+        assert!(result);
+
+        let bytes: Vec<u8> = ctx.eval(lock, "new Uint8Array([7, 8, 9]).buffer").unwrap();
+        assert_eq!(bytes, [7, 8, 9]);
+
+        ctx.set_global("wrapped", vec![0_u8, 255].to_js(lock));
+        let result: bool = ctx
+            .eval(
+                lock,
+                "wrapped instanceof Uint8Array && wrapped.length === 2 && wrapped[1] === 255",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn non_buffer_sources_are_rejected() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        install_bytes(lock, ctx);
+
+        for code in [
+            "Bytes.sum([1, 2, 3])",
+            "Bytes.reverse('abc')",
+            "Bytes.byteLength({ byteLength: 1 })",
+        ] {
+            let err = ctx
+                .eval::<bool>(lock, code)
+                .unwrap_err()
+                .unwrap_jsg_err(lock);
+            assert_eq!(err.name, ExceptionType::TypeError, "{code}");
+            assert!(
+                err.message
+                    .starts_with("Expected an ArrayBuffer or ArrayBufferView but got "),
+                "{code}: {}",
+                err.message
+            );
+        }
+
+        match ctx.eval::<Vec<u8>>(lock, "[1, 2, 3]") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Expected an ArrayBuffer or ArrayBufferView but got object"
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(value) => panic!("Expected an error but got {value:?}"),
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn buffer_source_views_live_backing_store() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let source: BufferSource = ctx
+            .eval(lock, "globalThis.view = new Uint8Array([1, 2, 3]); view")
+            .unwrap();
+        assert_eq!(&*source, [1, 2, 3]);
+
+        ctx.eval::<bool>(lock, "view[0] = 42; true").unwrap();
+        assert_eq!(source.as_slice(), [42, 2, 3]);
+
+        ctx.set_global("roundTrip", source.to_js(lock));
+        let result: bool = ctx.eval(lock, "roundTrip === view").unwrap();
+        assert!(result);
+
+        let source: BufferSource = ctx.eval(lock, "view").unwrap();
+        ctx.eval::<bool>(lock, "view.buffer.transfer(); true")
+            .unwrap();
+        assert!(source.is_empty());
+        Ok(())
+    });
+}
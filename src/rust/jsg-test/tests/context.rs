@@ -0,0 +1,204 @@
+//! Tests for `v8::Context` handles and the per-context state of `jsg::Realm`.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg::v8::ToLocalValue;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Session {
+    _state: ResourceState,
+    drops: Rc<Cell<u32>>,
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[jsg_resource]
+impl Session {}
+
+/// A resource referring to another one, which Rust code then holds a `Ref` to.
+#[jsg_resource]
+struct Holder {
+    _state: ResourceState,
+    session: jsg::Ref<Session>,
+}
+
+#[jsg_resource]
+impl Holder {}
+
+/// Context data that counts how many times it is dropped.
+struct Counter {
+    value: u32,
+    drops: Rc<Cell<u32>>,
+}
+
+impl Drop for Counter {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[test]
+fn current_context_is_the_entered_context() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let value = "marked".to_local(lock);
+        ctx.set_global("marker", value);
+
+        let context = lock.current_context();
+        assert!(context == lock.current_context());
+        let mut global = context.global(lock);
+        assert_eq!(global.get(lock, "marker").unwrap().to_string(), "marked");
+
+        let value = 42.to_local(lock);
+        global.set(lock, "answer", value);
+        let result: String = ctx.eval(lock, "`${globalThis.answer}`").unwrap();
+        assert_eq!(result, "42");
+        Ok(())
+    });
+}
+
+#[test]
+fn context_data_is_stored_per_context() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, _ctx| {
+        assert!(lock.context_data::<Counter>().is_none());
+        let previous = lock.set_context_data(Counter {
+            value: 1,
+            drops: drops.clone(),
+        });
+        assert!(previous.is_none());
+
+        lock.context_data::<Counter>().unwrap().value += 1;
+        assert_eq!(lock.context_data::<Counter>().unwrap().value, 2);
+        assert!(lock.context_data::<u32>().is_none());
+
+        let previous = lock.set_context_data(Counter {
+            value: 10,
+            drops: drops.clone(),
+        });
+        assert_eq!(previous.as_ref().map(|counter| counter.value), Some(2));
+        drop(previous);
+        assert_eq!(drops.get(), 1);
+
+        let counter = lock.take_context_data::<Counter>().unwrap();
+        assert_eq!(counter.value, 10);
+        assert!(lock.context_data::<Counter>().is_none());
+        Ok(())
+    });
+    assert_eq!(drops.get(), 2);
+
+    harness.run_in_context(|lock, _ctx| {
+        lock.set_context_data(Counter {
+            value: 1,
+            drops: drops.clone(),
+        });
+        Ok(())
+    });
+    // The data is dropped with its context, before the isolate is.
+    assert_eq!(drops.get(), 3);
+
+    harness.run_in_context(|lock, _ctx| {
+        assert!(lock.context_data::<Counter>().is_none());
+        Ok(())
+    });
+}
+
+#[test]
+fn resources_are_dropped_when_their_context_is_disposed() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let session = jsg::Ref::new(Session {
+            _state: ResourceState::default(),
+            drops: drops.clone(),
+        });
+        let session = session.to_js(lock);
+        ctx.set_global("session", session);
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+        Ok(())
+    });
+    assert_eq!(drops.get(), 1);
+
+    // Resources wrapped in another context of the same isolate are tracked separately.
+    harness.run_in_context(|lock, ctx| {
+        let session = jsg::Ref::new(Session {
+            _state: ResourceState::default(),
+            drops: drops.clone(),
+        });
+        let session = session.to_js(lock);
+        ctx.set_global("session", session);
+        let result: String = ctx.eval(lock, "typeof session").unwrap();
+        assert_eq!(result, "object");
+        Ok(())
+    });
+    assert_eq!(drops.get(), 2);
+    drop(harness);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn rust_code_runs_in_contexts_not_set_up_by_the_embedder() {
+    let session_drops = Rc::new(Cell::new(0));
+    let counter_drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, _ctx| {
+        lock.set_context_data(Counter {
+            value: 1,
+            drops: counter_drops.clone(),
+        });
+        Ok(())
+    });
+    assert_eq!(counter_drops.get(), 1);
+
+    harness.run_in_plain_context(|lock, ctx| {
+        // The Realm is created on first use, without the data of any other context.
+        assert!(lock.context_data::<Counter>().is_none());
+
+        let session = jsg::Ref::new(Session {
+            _state: ResourceState::default(),
+            drops: session_drops.clone(),
+        });
+        let holder = jsg::Ref::new(Holder {
+            _state: ResourceState::default(),
+            session: session.clone(),
+        });
+        let session = session.to_js(lock);
+        ctx.set_global("session", session);
+        let holder = holder.to_js(lock);
+        ctx.set_global("holder", holder);
+        let result: String = ctx.eval(lock, "typeof session").unwrap();
+        assert_eq!(result, "object");
+
+        // There is no scheduler to resume I/O in the context, so the promise is rejected.
+        let promise = lock.await_io(async {}, |_lock, ()| Ok(true));
+        ctx.set_global("promise", promise.into());
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.message = null; promise.catch(err => { message = err.message; }); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+        let message: String = ctx.eval(lock, "message").unwrap();
+        assert_eq!(message, "I/O cannot be awaited in this context");
+        Ok(())
+    });
+    assert_eq!(session_drops.get(), 0);
+
+    // The Realm holds the wrapper of the session weakly even though the holder refers to it, so
+    // nothing keeps the context alive and the next GC collects it along with its resources.
+    harness.run_in_context(|_lock, ctx| {
+        ctx.request_gc();
+        Ok(())
+    });
+    assert_eq!(session_drops.get(), 1);
+}
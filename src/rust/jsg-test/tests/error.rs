@@ -0,0 +1,182 @@
+//! Tests for throwing `jsg::Error` to JavaScript and converting JavaScript errors back.
+
+use jsg::Error;
+use jsg::ErrorProperty;
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Thrower {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Thrower {
+    #[jsg_method]
+    pub fn throw_named(&self, name: String, message: String) -> jsg::Result<()> {
+        Err(Error::new(&name, &message))
+    }
+
+    #[jsg_method]
+    pub fn throw_with_cause(&self) -> jsg::Result<()> {
+        Err(
+            Error::new_type_error("The \"name\" argument must be of type string")
+                .with_property("code", "ERR_INVALID_ARG_TYPE")
+                .with_cause(Error::new_range_error("inner")),
+        )
+    }
+}
+
+/// Evaluates `code` with a `Thrower` installed as `thrower`, expecting it to return `expected`.
+fn check_with_thrower(code: &str, expected: &str) {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let thrower = jsg::Ref::new(Thrower {
+            _state: ResourceState::default(),
+        });
+        let thrower = thrower.to_js(lock);
+        ctx.set_global("thrower", thrower);
+
+        let result: String = ctx.eval(lock, code).unwrap();
+        assert_eq!(result, expected);
+        Ok(())
+    });
+}
+
+#[test]
+fn dom_exception_names_are_thrown_as_dom_exceptions() {
+    check_with_thrower(
+        "['AbortError', 'NotFoundError', 'DataCloneError', 'InvalidStateError', 'DataError'] \
+           .map((name) => { \
+             try { thrower.throwNamed(name, 'failed'); } \
+             catch (e) { \
+               return `${e instanceof DOMException} ${e instanceof Error} ${e.name} ${e.code}`; \
+             } \
+           }).join()",
+        "true true AbortError 20,true true NotFoundError 8,true true DataCloneError 25,\
+         true true InvalidStateError 11,true true DataError 0",
+    );
+}
+
+#[test]
+fn javascript_error_names_are_thrown_as_native_errors() {
+    check_with_thrower(
+        "['TypeError', 'RangeError', 'SyntaxError', 'Error'].map((name) => { \
+           try { thrower.throwNamed(name, 'failed'); } \
+           catch (e) { \
+             return `${e instanceof DOMException} ${e.constructor.name} ${e.message}`; \
+           } \
+         }).join()",
+        "false TypeError failed,false RangeError failed,false SyntaxError failed,false Error failed",
+    );
+}
+
+#[test]
+fn cause_and_properties_are_set_on_thrown_errors() {
+    check_with_thrower(
+        "try { thrower.throwWithCause(); } \
+         catch (e) { \
+           `${e.code} ${e.cause instanceof RangeError} ${e.cause.message} ${Object.keys(e)}`; \
+         }",
+        "ERR_INVALID_ARG_TYPE true inner code",
+    );
+}
+
+#[test]
+fn dom_exceptions_round_trip() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let error: Error = ctx
+            .eval(lock, "new DOMException('gone', 'NotFoundError')")
+            .unwrap();
+        assert_eq!(error.name, ExceptionType::NotFoundError);
+        assert_eq!(error.message, "gone");
+        assert!(error.properties.is_empty());
+
+        ctx.set_global("error", error.to_local(lock));
+        let result: String = ctx
+            .eval(
+                lock,
+                "`${error instanceof DOMException} ${error.name} ${error.code} ${error.message}`",
+            )
+            .unwrap();
+        assert_eq!(result, "true NotFoundError 8 gone");
+        Ok(())
+    });
+}
+
+#[test]
+fn dom_errors_are_plain_errors_without_the_jsg_lock() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        ctx.run_without_jsg_lock(|| {
+            let error = Error::new("NotFoundError", "gone").to_local(lock);
+            ctx.set_global("error", error);
+            let error: Error = ctx.eval(lock, "error").unwrap();
+            assert_eq!(error.name, ExceptionType::NotFoundError);
+            assert_eq!(error.message, "gone");
+        });
+        let result: String = ctx
+            .eval(
+                lock,
+                "`${error instanceof DOMException} ${error instanceof Error} ${error.name} \
+                 ${error.message} ${Object.keys(error)}`",
+            )
+            .unwrap();
+        assert_eq!(result, "false true NotFoundError gone ");
+        Ok(())
+    });
+}
+
+#[test]
+fn from_js_reads_cause_and_code() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let error: Error = ctx
+            .eval(
+                lock,
+                "const error = new TypeError('outer', { cause: new RangeError('inner') }); \
+                 error.code = 'ERR_INVALID_ARG_TYPE'; \
+                 error",
+            )
+            .unwrap();
+        assert_eq!(error.name, ExceptionType::TypeError);
+        assert_eq!(
+            error.property("code"),
+            Some(&ErrorProperty::from("ERR_INVALID_ARG_TYPE"))
+        );
+        let cause = error.cause.unwrap();
+        assert_eq!(cause.name, ExceptionType::RangeError);
+        assert_eq!(cause.message, "inner");
+
+        // Cyclic causes are followed up to a limit, and causes that are not errors are ignored.
+        let error: Error = ctx
+            .eval(
+                lock,
+                "const cyclic = new Error('cyclic'); cyclic.cause = cyclic; cyclic",
+            )
+            .unwrap();
+        assert!(error.cause.is_some());
+        let error: Error = ctx.eval(lock, "new Error('plain', { cause: 42 })").unwrap();
+        assert!(error.cause.is_none());
+        Ok(())
+    });
+}
+
+#[test]
+fn with_property_replaces_earlier_values() {
+    let error = Error::new_error("failed")
+        .with_property("code", "ERR_FIRST")
+        .with_property("errno", -2)
+        .with_property("code", "ERR_SECOND");
+    assert_eq!(error.properties.len(), 2);
+    assert_eq!(
+        error.property("code"),
+        Some(&ErrorProperty::from("ERR_SECOND"))
+    );
+    assert_eq!(error.property("errno"), Some(&ErrorProperty::Number(-2.0)));
+    assert_eq!(error.property("missing"), None);
+}
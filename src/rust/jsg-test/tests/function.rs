@@ -0,0 +1,185 @@
+//! Tests for `jsg::Function<F>`.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use jsg::ExceptionType;
+use jsg::Function;
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+type Listener = Function<fn(String) -> bool>;
+
+#[jsg_resource]
+struct Registry {
+    _state: ResourceState,
+    names: Vec<String>,
+    listener: Option<Listener>,
+    drops: Rc<Cell<u32>>,
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[jsg_resource]
+impl Registry {
+    #[jsg_method]
+    pub fn for_each(
+        &self,
+        lock: &mut jsg::Lock,
+        callback: Function<fn(String, u32)>,
+    ) -> jsg::Result<()> {
+        for (index, name) in (0..).zip(&self.names) {
+            callback.call(lock, name.clone(), index)?;
+        }
+        Ok(())
+    }
+
+    #[jsg_method]
+    pub fn on_add(&mut self, listener: Listener) {
+        self.listener = Some(listener);
+    }
+
+    #[jsg_method]
+    pub fn add(&mut self, lock: &mut jsg::Lock, name: String) -> jsg::Result<bool> {
+        self.names.push(name.clone());
+        match &self.listener {
+            Some(listener) => listener.call(lock, name),
+            None => Ok(false),
+        }
+    }
+}
+
+fn set_registry(lock: &mut jsg::Lock, ctx: &crate::EvalContext, drops: &Rc<Cell<u32>>) {
+    let registry = jsg::Ref::new(Registry {
+        _state: ResourceState::default(),
+        names: vec!["a".to_owned(), "b".to_owned()],
+        listener: None,
+        drops: drops.clone(),
+    });
+    let registry = registry.to_js(lock);
+    ctx.set_global("registry", registry);
+}
+
+#[test]
+fn function_from_js_can_be_called() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let add: Function<fn(f64, f64) -> f64> = ctx.eval(lock, "(a, b) => a + b").unwrap();
+        let result = add.call(lock, 40.0, 2.0)?;
+        assert!((result - 42.0).abs() < f64::EPSILON);
+
+        let greet: Function<fn() -> String> = ctx
+            .eval(lock, "function greet() { return 'hello'; }; greet")
+            .unwrap();
+        assert_eq!(greet.call(lock)?, "hello");
+        Ok(())
+    });
+}
+
+#[test]
+fn function_exceptions_become_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let fail: Function<fn(String)> = ctx
+            .eval(lock, "(message) => { throw new RangeError(message); }")
+            .unwrap();
+        let err = fail.call(lock, "out of range".to_owned()).unwrap_err();
+        assert_eq!(err.name, ExceptionType::RangeError);
+        assert_eq!(err.message, "out of range");
+
+        // A result that cannot be unwrapped is reported the same way.
+        let number: Function<fn() -> f64> = ctx.eval(lock, "() => 1n").unwrap();
+        assert_eq!(
+            number.call(lock).unwrap_err().name,
+            ExceptionType::TypeError
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn function_from_js_rejects_non_functions() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx, &Rc::new(Cell::new(0)));
+
+        let result: String = ctx
+            .eval(
+                lock,
+                "try { registry.forEach({}); 'no error' } catch (e) { e.name + ': ' + e.message }",
+            )
+            .unwrap();
+        assert_eq!(result, "TypeError: Expected Function but got object");
+        Ok(())
+    });
+}
+
+#[test]
+fn methods_call_functions_under_lock() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx, &Rc::new(Cell::new(0)));
+
+        let result: String = ctx
+            .eval(
+                lock,
+                "const seen = []; registry.forEach((name, index) => { seen.push(name + index); }); \
+                 seen.join()",
+            )
+            .unwrap();
+        assert_eq!(result, "a0,b1");
+
+        let result: String = ctx
+            .eval(
+                lock,
+                "try { registry.forEach(() => { throw new Error('stop'); }); 'no error' } \
+                 catch (e) { e.message }",
+            )
+            .unwrap();
+        assert_eq!(result, "stop");
+        Ok(())
+    });
+}
+
+#[test]
+fn stored_functions_can_be_called_later() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx, &Rc::new(Cell::new(0)));
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "const added = []; registry.onAdd((name) => { added.push(name); return true; }); \
+                 registry.add('c') && added.join() === 'c'",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn stored_functions_do_not_keep_resource_alive() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx, &drops);
+
+        // The listener refers back to the registry's wrapper, which holds the listener.
+        ctx.eval::<bool>(
+            lock,
+            "registry.onAdd(((r) => () => r !== null)(registry)); delete globalThis.registry; true",
+        )
+        .unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 1);
+        Ok(())
+    });
+}
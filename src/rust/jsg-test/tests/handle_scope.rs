@@ -0,0 +1,111 @@
+//! Tests for `Lock::within_handle_scope()` and `Lock::within_escapable_handle_scope()`.
+
+use std::collections::BTreeMap;
+
+use jsg::ToJS;
+use jsg::v8;
+use jsg::v8::ToLocalValue;
+
+/// Creates `{ [key]: value }` in its own scope, returning it to the caller's scope.
+fn to_entry<'p>(lock: &mut jsg::Lock<'p>, key: &str, value: f64) -> v8::Local<'p, v8::Object> {
+    lock.within_escapable_handle_scope(|lock| {
+        let mut entry = lock.new_object();
+        let value = value.to_local(lock);
+        entry.set(lock, key, value);
+        entry
+    })
+}
+
+#[test]
+fn handle_scopes_release_handles_created_in_loops() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let mut total = lock.new_object();
+        for i in 0..100_000_u32 {
+            lock.within_handle_scope(|lock| {
+                let mut object = lock.new_object();
+                let value = f64::from(i).to_local(lock);
+                object.set(lock, "value", value);
+                if i % 10_000 == 0 {
+                    let value = object.get(lock, "value").unwrap();
+                    total.set(lock, i / 10_000, value);
+                }
+            });
+        }
+
+        ctx.set_global("total", total.into());
+        let result: String = ctx.eval(lock, "Object.values(total).join()").unwrap();
+        assert_eq!(
+            result,
+            "0,10000,20000,30000,40000,50000,60000,70000,80000,90000"
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn escaped_handles_outlive_their_scope() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let entries: Vec<v8::Local<v8::Value>> = (0..3_u32)
+            .map(|i| to_entry(lock, &format!("key{i}"), f64::from(i)).into())
+            .collect();
+        let entries = v8::Local::<v8::Array>::new(lock, entries);
+
+        ctx.set_global("entries", entries.into());
+        let result: String = ctx.eval(lock, "JSON.stringify(entries)").unwrap();
+        assert_eq!(result, r#"[{"key0":0},{"key1":1},{"key2":2}]"#);
+        Ok(())
+    });
+}
+
+#[test]
+fn handle_scopes_can_be_nested() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        lock.within_handle_scope(|outer| {
+            let mut object = outer.new_object();
+            let entry = outer.within_escapable_handle_scope(|inner| {
+                inner.within_handle_scope(|innermost| {
+                    let value = "nested".to_local(innermost);
+                    object.set(innermost, "name", value);
+                });
+                to_entry(inner, "depth", 3.0)
+            });
+            object.set(outer, "entry", entry.into());
+
+            ctx.set_global("object", object.into());
+            let result: String = ctx
+                .eval(outer, "`${object.name} ${object.entry.depth}`")
+                .unwrap();
+            assert_eq!(result, "nested 3");
+        });
+        Ok(())
+    });
+}
+
+#[test]
+fn large_collections_convert_in_handle_scopes() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let records: Vec<BTreeMap<String, f64>> = (0..10_000_u32)
+            .map(|i| BTreeMap::from([("index".to_owned(), f64::from(i))]))
+            .collect();
+        let records = records.to_js(lock);
+        ctx.set_global("records", records);
+        let result: String = ctx
+            .eval(lock, "`${records.length} ${records[9999].index}`")
+            .unwrap();
+        assert_eq!(result, "10000 9999");
+
+        let records: Vec<BTreeMap<String, f64>> = ctx
+            .eval(
+                lock,
+                "Array.from({ length: 10000 }, (_, i) => ({ index: i }))",
+            )
+            .unwrap();
+        assert_eq!(records.len(), 10_000);
+        assert!((records[9_999]["index"] - 9_999.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
@@ -0,0 +1,174 @@
+//! Tests for integer conversions, `BigInt`, and the `EnforceRange` / `Clamp` wrappers.
+
+use jsg::Clamp;
+use jsg::EnforceRange;
+use jsg::ToJS;
+
+use crate::EvalError;
+
+fn uncoercible_message(result: Result<impl std::fmt::Debug, EvalError<'_>>) -> String {
+    match result {
+        Err(EvalError::UncoercibleResult { message, .. }) => message,
+        Err(e) => panic!("Unexpected error: {e:?}"),
+        Ok(value) => panic!("Expected an error but got {value:?}"),
+    }
+}
+
+#[test]
+fn integers_wrap_by_default() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        assert_eq!(ctx.eval::<u8>(lock, "300").unwrap(), 44);
+        assert_eq!(ctx.eval::<u8>(lock, "-1").unwrap(), 255);
+        assert_eq!(ctx.eval::<i8>(lock, "200").unwrap(), -56);
+        assert_eq!(ctx.eval::<i8>(lock, "-129").unwrap(), 127);
+        assert_eq!(ctx.eval::<i8>(lock, "-3.9").unwrap(), -3);
+        assert_eq!(ctx.eval::<u16>(lock, "65536 + 7").unwrap(), 7);
+        assert_eq!(ctx.eval::<i16>(lock, "32768").unwrap(), i16::MIN);
+        assert_eq!(ctx.eval::<i32>(lock, "2 ** 31").unwrap(), i32::MIN);
+        assert_eq!(ctx.eval::<u32>(lock, "'42'").unwrap(), 42);
+        assert_eq!(ctx.eval::<u32>(lock, "NaN").unwrap(), 0);
+        assert_eq!(ctx.eval::<i32>(lock, "-Infinity").unwrap(), 0);
+        assert_eq!(ctx.eval::<usize>(lock, "2 ** 40").unwrap(), 1 << 40);
+        Ok(())
+    });
+}
+
+#[test]
+fn integers_wrap_to_numbers() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        ctx.set_global("a", (-5_i8).to_js(lock));
+        ctx.set_global("b", u16::MAX.to_js(lock));
+        ctx.set_global("c", i32::MIN.to_js(lock));
+        let result: bool = ctx
+            .eval(
+                lock,
+                "[a, b, c].every(v => typeof v === 'number') && \
+                 a === -5 && b === 65535 && c === -(2 ** 31)",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn big_ints_convert_losslessly() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        assert_eq!(ctx.eval::<i64>(lock, "-(2n ** 63n)").unwrap(), i64::MIN);
+        assert_eq!(ctx.eval::<i64>(lock, "2n ** 63n - 1n").unwrap(), i64::MAX);
+        assert_eq!(ctx.eval::<u64>(lock, "2n ** 64n - 1n").unwrap(), u64::MAX);
+        assert_eq!(ctx.eval::<i64>(lock, "42").unwrap(), 42);
+        assert_eq!(ctx.eval::<u64>(lock, "-1").unwrap(), u64::MAX);
+        assert_eq!(
+            ctx.eval::<usize>(lock, "2n ** 53n + 1n").unwrap(),
+            (1 << 53) + 1
+        );
+
+        ctx.set_global("signed", i64::MIN.to_js(lock));
+        ctx.set_global("unsigned", u64::MAX.to_js(lock));
+        ctx.set_global("size", ((1_usize << 53) + 1).to_js(lock));
+        let result: bool = ctx
+            .eval(
+                lock,
+                "typeof signed === 'bigint' && signed === -(2n ** 63n) && \
+                 typeof unsigned === 'bigint' && unsigned === 2n ** 64n - 1n && \
+                 typeof size === 'bigint' && size === 2n ** 53n + 1n",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn lossy_big_ints_throw_range_error() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        assert_eq!(
+            uncoercible_message(ctx.eval::<i64>(lock, "2n ** 63n")),
+            "RangeError: BigInt value 9223372036854775808 is out of range for i64"
+        );
+        assert_eq!(
+            uncoercible_message(ctx.eval::<u64>(lock, "-1n")),
+            "RangeError: BigInt value -1 is out of range for u64"
+        );
+        assert_eq!(
+            uncoercible_message(ctx.eval::<EnforceRange<u64>>(lock, "2n ** 64n")),
+            "RangeError: BigInt value 18446744073709551616 is out of range for u64"
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn enforce_range_rejects_out_of_range_values() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        assert_eq!(*ctx.eval::<EnforceRange<u8>>(lock, "255").unwrap(), 255);
+        assert_eq!(*ctx.eval::<EnforceRange<u8>>(lock, "1.9").unwrap(), 1);
+        assert_eq!(*ctx.eval::<EnforceRange<i8>>(lock, "-128").unwrap(), -128);
+        assert_eq!(
+            *ctx.eval::<EnforceRange<i64>>(lock, "2n ** 60n").unwrap(),
+            1 << 60
+        );
+        assert_eq!(
+            *ctx.eval::<EnforceRange<i64>>(lock, "Number.MIN_SAFE_INTEGER")
+                .unwrap(),
+            -(1 << 53) + 1
+        );
+
+        assert_eq!(
+            uncoercible_message(ctx.eval::<EnforceRange<u8>>(lock, "256")),
+            "TypeError: Value 256 is outside the range of u8 (0 to 255)"
+        );
+        assert_eq!(
+            uncoercible_message(ctx.eval::<EnforceRange<i16>>(lock, "-32769")),
+            "TypeError: Value -32769 is outside the range of i16 (-32768 to 32767)"
+        );
+        assert_eq!(
+            uncoercible_message(ctx.eval::<EnforceRange<u32>>(lock, "NaN")),
+            "TypeError: Value must be a finite number to convert to u32"
+        );
+        assert_eq!(
+            uncoercible_message(ctx.eval::<EnforceRange<u64>>(lock, "2 ** 53")),
+            "TypeError: Value 9007199254740992 is outside the range of u64 (0 to \
+             9007199254740991)"
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn clamp_saturates_and_rounds() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        assert_eq!(*ctx.eval::<Clamp<u8>>(lock, "300").unwrap(), 255);
+        assert_eq!(*ctx.eval::<Clamp<u8>>(lock, "-5").unwrap(), 0);
+        assert_eq!(*ctx.eval::<Clamp<u8>>(lock, "2.5").unwrap(), 2);
+        assert_eq!(*ctx.eval::<Clamp<u8>>(lock, "3.5").unwrap(), 4);
+        assert_eq!(*ctx.eval::<Clamp<u8>>(lock, "NaN").unwrap(), 0);
+        assert_eq!(*ctx.eval::<Clamp<i8>>(lock, "-1000").unwrap(), -128);
+        assert_eq!(*ctx.eval::<Clamp<u32>>(lock, "Infinity").unwrap(), u32::MAX);
+        assert_eq!(
+            *ctx.eval::<Clamp<i64>>(lock, "1e300").unwrap(),
+            (1 << 53) - 1
+        );
+        assert_eq!(
+            *ctx.eval::<Clamp<i64>>(lock, "2n ** 70n").unwrap(),
+            i64::MAX
+        );
+        assert_eq!(
+            *ctx.eval::<Clamp<i64>>(lock, "-(2n ** 70n)").unwrap(),
+            i64::MIN
+        );
+        assert_eq!(*ctx.eval::<Clamp<u64>>(lock, "-1n").unwrap(), 0);
+        assert_eq!(
+            *ctx.eval::<Clamp<u64>>(lock, "2n ** 64n").unwrap(),
+            u64::MAX
+        );
+        Ok(())
+    });
+}
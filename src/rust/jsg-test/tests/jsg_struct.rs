@@ -1,10 +1,13 @@
 use jsg::Error;
 use jsg::ExceptionType;
+use jsg::FromJS;
+use jsg::NonCoercible;
 use jsg::ToJS;
-use jsg::v8;
 use jsg::v8::ToLocalValue;
 use jsg_macros::jsg_struct;
 
+use crate::EvalError;
+
 #[jsg_struct]
 struct TestStruct {
     pub str: String,
@@ -22,6 +25,25 @@ struct NestedStruct {
     pub inner: String,
 }
 
+#[jsg_struct]
+struct OptionalFieldsStruct {
+    pub required: String,
+    pub optional: Option<String>,
+    pub nullable: jsg::Nullable<bool>,
+    hidden: u32,
+}
+
+#[jsg_struct]
+struct StrictStruct {
+    pub value: NonCoercible<String>,
+}
+
+#[jsg_struct]
+struct OuterStruct {
+    pub name: String,
+    pub strict: StrictStruct,
+}
+
 #[test]
 fn objects_can_be_wrapped_and_unwrapped() {
     let harness = crate::Harness::new();
@@ -30,7 +52,7 @@ fn objects_can_be_wrapped_and_unwrapped() {
             str: "test".to_owned(),
         };
         let wrapped = instance.to_js(lock);
-        let mut obj: v8::Local<'_, v8::Object> = wrapped.into();
+        let mut obj = wrapped.try_into_object().unwrap();
         assert!(obj.has(lock, "str"));
         let str_value = obj.get(lock, "str");
         assert!(str_value.unwrap().is_string());
@@ -53,21 +75,21 @@ fn struct_with_multiple_properties() {
             active: "true".to_owned(),
         };
         let wrapped = instance.to_js(lock);
-        let obj: v8::Local<'_, v8::Object> = wrapped.into();
+        let obj = wrapped.try_into_object().unwrap();
 
         assert!(obj.has(lock, "name"));
         assert!(obj.has(lock, "age"));
         assert!(obj.has(lock, "active"));
 
         let name_value = obj.get(lock, "name");
-        assert!(name_value.is_some());
+        assert!(name_value.is_ok());
         assert!(name_value.unwrap().is_string());
 
         let age_value = obj.get(lock, "age");
-        assert!(age_value.is_some());
+        assert!(age_value.is_ok());
 
         let active_value = obj.get(lock, "active");
-        assert!(active_value.is_some());
+        assert!(active_value.is_ok());
         assert!(active_value.unwrap().is_string());
         Ok(())
     });
@@ -95,7 +117,7 @@ fn empty_object_and_property_setting() {
         let mut obj = lock.new_object();
 
         assert!(!obj.has(lock, "nonexistent"));
-        assert!(obj.get(lock, "nonexistent").is_none());
+        assert!(obj.get(lock, "nonexistent").unwrap().is_undefined());
 
         let str_value = "hello".to_local(lock);
         obj.set(lock, "key1", str_value);
@@ -106,11 +128,11 @@ fn empty_object_and_property_setting() {
         assert!(obj.has(lock, "key2"));
 
         let val1 = obj.get(lock, "key1");
-        assert!(val1.is_some());
+        assert!(val1.is_ok());
         assert!(val1.unwrap().is_string());
 
         let val2 = obj.get(lock, "key2");
-        assert!(val2.is_some());
+        assert!(val2.is_ok());
         Ok(())
     });
 }
@@ -143,13 +165,13 @@ fn nested_object_properties() {
 
         assert!(outer.has(lock, "nested"));
         let nested_val = outer.get(lock, "nested");
-        assert!(nested_val.is_some());
+        assert!(nested_val.is_ok());
 
-        let nested_obj: v8::Local<'_, v8::Object> = nested_val.unwrap().into();
+        let nested_obj = nested_val.unwrap().try_into_object().unwrap();
         assert!(nested_obj.has(lock, "inner"));
 
         let inner_val = nested_obj.get(lock, "inner");
-        assert!(inner_val.is_some());
+        assert!(inner_val.is_ok());
         assert!(inner_val.unwrap().is_string());
         Ok(())
     });
@@ -203,3 +225,130 @@ fn type_of_returns_correct_js_types() {
         Ok(())
     });
 }
+
+#[test]
+fn struct_from_js_reads_public_fields() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let result: MultiPropertyStruct = ctx
+            .eval(lock, "({ name: 'Alice', age: 30, active: 'yes' })")
+            .unwrap();
+        assert_eq!(result.name, "Alice");
+        assert_eq!(result.age, 30);
+        assert_eq!(result.active, "yes");
+        Ok(())
+    });
+}
+
+#[test]
+fn struct_round_trips_through_js() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, _ctx| {
+        let instance = MultiPropertyStruct {
+            name: "Bob".to_owned(),
+            age: 42,
+            active: "no".to_owned(),
+        };
+        let wrapped = instance.to_js(lock);
+        let result = MultiPropertyStruct::from_js(lock, wrapped)?;
+        assert_eq!(result.name, "Bob");
+        assert_eq!(result.age, 42);
+        assert_eq!(result.active, "no");
+        Ok(())
+    });
+}
+
+#[test]
+fn struct_from_js_allows_missing_optional_fields() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let result: OptionalFieldsStruct = ctx.eval(lock, "({ required: 'x' })").unwrap();
+        assert_eq!(result.required, "x");
+        assert!(result.optional.is_none());
+        assert!(result.nullable.is_undefined());
+        assert_eq!(result.hidden, 0);
+
+        let result: OptionalFieldsStruct = ctx
+            .eval(
+                lock,
+                "({ required: 'x', optional: 'y', nullable: null, hidden: 5 })",
+            )
+            .unwrap();
+        assert_eq!(result.optional.as_deref(), Some("y"));
+        assert!(result.nullable.is_null());
+        assert_eq!(result.hidden, 0);
+        Ok(())
+    });
+}
+
+#[test]
+fn struct_from_js_rejects_missing_required_field() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let Err(EvalError::UncoercibleResult { message, .. }) =
+            ctx.eval::<OptionalFieldsStruct>(lock, "({ optional: 'y' })")
+        else {
+            panic!("Expected conversion to fail");
+        };
+        assert_eq!(
+            message,
+            "TypeError: Required field 'required' is missing on 'OptionalFieldsStruct'."
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn struct_from_js_propagates_exceptions_thrown_by_getters() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let Err(EvalError::UncoercibleResult { message, .. }) = ctx.eval::<OptionalFieldsStruct>(
+            lock,
+            "({ get required() { throw new RangeError('not ready'); } })",
+        ) else {
+            panic!("Expected conversion to fail");
+        };
+        assert_eq!(message, "RangeError: not ready");
+
+        // The exception was caught, so JavaScript keeps running.
+        let result: String = ctx.eval(lock, "'still running'").unwrap();
+        assert_eq!(result, "still running");
+        Ok(())
+    });
+}
+
+#[test]
+fn struct_from_js_reports_nested_field_path() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let Err(EvalError::UncoercibleResult { message, .. }) =
+            ctx.eval::<OuterStruct>(lock, "({ name: 'outer', strict: { value: 42 } })")
+        else {
+            panic!("Expected conversion to fail");
+        };
+        assert_eq!(
+            message,
+            "TypeError: Incorrect type for the 'strict' field on 'OuterStruct': \
+             Incorrect type for the 'value' field on 'StrictStruct': \
+             Expected a string value but got number"
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn struct_from_js_rejects_non_objects() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let Err(EvalError::UncoercibleResult { message, .. }) =
+            ctx.eval::<TestStruct>(lock, "'not an object'")
+        else {
+            panic!("Expected conversion to fail");
+        };
+        assert_eq!(
+            message,
+            "TypeError: Expected an object for 'TestStruct' but got string"
+        );
+        Ok(())
+    });
+}
@@ -1,6 +1,28 @@
+mod arguments;
+mod buffer_source;
+mod context;
+mod error;
 mod eval;
+mod function;
+mod handle_scope;
+mod integer;
 mod jsg_oneof;
 mod jsg_struct;
+mod node_error;
 mod non_coercible;
+mod object;
+mod promise;
+mod record;
+mod resource_async;
 mod resource_callback;
+mod resource_constructor;
+mod resource_gc;
+mod resource_memory;
+mod resource_property;
+mod resource_static;
+mod resource_trace;
+mod resource_wrap;
+mod sequence;
+mod try_catch;
 mod unwrap;
+mod value_types;
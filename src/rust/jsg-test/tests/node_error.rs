@@ -0,0 +1,184 @@
+//! Tests for the Node.js-style errors in `jsg::node`.
+
+use jsg::ErrorProperty;
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg::node::Error;
+use jsg::node::Received;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Validator {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Validator {
+    #[jsg_method]
+    pub fn validate_string(&self, name: String, value: Received) -> Result<(), Error> {
+        match value {
+            Received::String(_) => Ok(()),
+            value => Err(Error::invalid_arg_type(name, &["string"], value)),
+        }
+    }
+
+    #[jsg_method]
+    pub fn validate_port(&self, port: f64) -> Result<f64, Error> {
+        if (0.0..=65535.0).contains(&port) {
+            Ok(port)
+        } else {
+            Err(Error::out_of_range("port", ">= 0 and <= 65535", port))
+        }
+    }
+}
+
+/// Evaluates `code` with a `Validator` installed as `validator`, expecting it to return
+/// `expected`.
+fn check_with_validator(code: &str, expected: &str) {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let validator = jsg::Ref::new(Validator {
+            _state: ResourceState::default(),
+        });
+        let validator = validator.to_js(lock);
+        ctx.set_global("validator", validator);
+
+        let result: String = ctx.eval(lock, code).unwrap();
+        assert_eq!(result, expected);
+        Ok(())
+    });
+}
+
+#[test]
+fn invalid_arg_type_messages() {
+    assert_eq!(
+        Error::invalid_arg_type("name", &["string"], 42).to_string(),
+        "The \"name\" argument must be of type string. Received type number (42)"
+    );
+    assert_eq!(
+        Error::invalid_arg_type("options.port", &["number", "string"], Received::Null).to_string(),
+        "The \"options.port\" property must be one of type number or string. Received null"
+    );
+    assert_eq!(
+        Error::invalid_arg_type(
+            "buffer",
+            &["string", "Buffer", "TypedArray", "DataView"],
+            Received::Object(Some("Foo".to_owned())),
+        )
+        .to_string(),
+        "The \"buffer\" argument must be of type string or an instance of Buffer, TypedArray, \
+         or DataView. Received an instance of Foo"
+    );
+    assert_eq!(
+        Error::invalid_arg_type("first argument", &["object", "Buffer"], "a".repeat(30))
+            .to_string(),
+        "The first argument must be an instance of Buffer or Object. \
+         Received type string ('aaaaaaaaaaaaaaaaaaaaaaaa...)"
+    );
+    assert_eq!(
+        Error::invalid_arg_type("cb", &["Function"], Received::Function(None)).to_string(),
+        "The \"cb\" argument must be of type function. \
+         Received type function ([Function (anonymous)])"
+    );
+    assert_eq!(
+        Error::invalid_arg_type("hint", &["a valid hint"], true).to_string(),
+        "The \"hint\" argument must be a valid hint. Received type boolean (true)"
+    );
+}
+
+#[test]
+fn invalid_arg_value_and_out_of_range_messages() {
+    assert_eq!(
+        Error::invalid_arg_value("family", "IPv7", "must be one of: 0, 4, 6").to_string(),
+        "The argument 'family' must be one of: 0, 4, 6. Received 'IPv7'"
+    );
+    assert_eq!(
+        Error::invalid_arg_value("options.hints", "it's", "is invalid").to_string(),
+        "The property 'options.hints' is invalid. Received \"it's\""
+    );
+    assert_eq!(
+        Error::out_of_range("offset", "an integer", 1e10).to_string(),
+        "The value of \"offset\" is out of range. It must be an integer. Received 10_000_000_000"
+    );
+    assert_eq!(
+        Error::out_of_range(
+            "value",
+            "< 2n ** 63n",
+            Received::BigInt("-9223372036854775809".to_owned()),
+        )
+        .to_string(),
+        "The value of \"value\" is out of range. It must be < 2n ** 63n. \
+         Received -9_223_372_036_854_775_809n"
+    );
+    assert_eq!(
+        Error::out_of_range("ratio", "<= 1", 1.5e-7).to_string(),
+        "The value of \"ratio\" is out of range. It must be <= 1. Received 1.5e-7"
+    );
+    assert_eq!(
+        Error::out_of_range("delay", ">= 0", -0.0).to_string(),
+        "The value of \"delay\" is out of range. It must be >= 0. Received -0"
+    );
+}
+
+#[test]
+fn missing_args_and_invalid_this_messages() {
+    assert_eq!(
+        Error::missing_args(&["hostname"]).to_string(),
+        "The \"hostname\" argument must be specified"
+    );
+    assert_eq!(
+        Error::missing_args(&["a", "b", "c"]).to_string(),
+        "The \"a\", \"b\", and \"c\" arguments must be specified"
+    );
+    assert_eq!(
+        Error::invalid_this("Resolver").to_string(),
+        "Value of \"this\" must be of type Resolver"
+    );
+}
+
+#[test]
+fn node_errors_convert_to_errors_with_code() {
+    let err: jsg::Error = Error::out_of_range("port", ">= 0", -1).into();
+    assert_eq!(err.name, ExceptionType::RangeError);
+    assert_eq!(
+        err.property("code"),
+        Some(&ErrorProperty::from("ERR_OUT_OF_RANGE"))
+    );
+
+    let err: jsg::Error = Error::missing_args(&["name"]).into();
+    assert_eq!(err.name, ExceptionType::TypeError);
+    assert_eq!(err.message, "The \"name\" argument must be specified");
+}
+
+#[test]
+fn node_errors_are_thrown_from_methods() {
+    check_with_validator(
+        "try { validator.validatePort(70000); } \
+         catch (e) { `${e instanceof RangeError} ${e.code} ${e.message}` }",
+        "true ERR_OUT_OF_RANGE The value of \"port\" is out of range. \
+         It must be >= 0 and <= 65535. Received 70000",
+    );
+}
+
+#[test]
+fn received_describes_javascript_values() {
+    check_with_validator(
+        "class Foo {}; \
+         [undefined, 42n, Symbol('id'), Symbol(), new Foo(), Object.create(null), \
+          function named() {}, () => {}] \
+           .map((value) => { \
+             try { validator.validateString('value', value); } \
+             catch (e) { return `${e.code}: ${e.message.split('. ')[1]}`; } \
+           }).join('\\n')",
+        "ERR_INVALID_ARG_TYPE: Received undefined\n\
+         ERR_INVALID_ARG_TYPE: Received type bigint (42n)\n\
+         ERR_INVALID_ARG_TYPE: Received type symbol (Symbol(id))\n\
+         ERR_INVALID_ARG_TYPE: Received type symbol (Symbol())\n\
+         ERR_INVALID_ARG_TYPE: Received an instance of Foo\n\
+         ERR_INVALID_ARG_TYPE: Received [Object: null prototype]\n\
+         ERR_INVALID_ARG_TYPE: Received function named\n\
+         ERR_INVALID_ARG_TYPE: Received type function ([Function (anonymous)])",
+    );
+}
@@ -0,0 +1,228 @@
+//! Tests for property access, property definition and prototypes on `Local<Object>`.
+
+use jsg::ExceptionType;
+use jsg::v8;
+use jsg::v8::PropertyAttributes;
+use jsg::v8::ToLocalValue;
+
+/// Evaluates `code` as an object.
+fn eval_object<'s>(
+    lock: &mut jsg::Lock<'s>,
+    ctx: &crate::EvalContext<'_>,
+    code: &str,
+) -> v8::Local<'s, v8::Object> {
+    let value = ctx.eval::<jsg::Value>(lock, code).unwrap().as_local(lock);
+    value.try_into_object().unwrap()
+}
+
+#[test]
+fn properties_accept_string_index_and_symbol_keys() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let mut object = lock.new_object();
+        let id = v8::Local::<v8::Symbol>::new(lock, "id");
+        let value = "name".to_local(lock);
+        object.set(lock, "name", value);
+        let value = "first".to_local(lock);
+        object.set(lock, 0, value);
+        let value = 42.to_local(lock);
+        object.set(lock, &id, value);
+
+        assert!(object.has(lock, "name"));
+        assert!(object.has(lock, 0));
+        assert!(object.has(lock, "0"));
+        assert!(object.has(lock, &id));
+        assert!(object.has(lock, "toString"));
+        assert!(!object.has(lock, 1));
+
+        assert_eq!(object.get(lock, "0").unwrap().to_string(), "first");
+        assert_eq!(object.get(lock, &id).unwrap().to_string(), "42");
+        assert!(object.get(lock, "missing").unwrap().is_undefined());
+
+        // Like in JavaScript, only `has()` tells a missing property from an undefined one.
+        let value = v8::Local::<v8::Value>::undefined(lock);
+        object.set(lock, "empty", value);
+        assert!(object.get(lock, "empty").unwrap().is_undefined());
+        assert!(object.has(lock, "empty"));
+
+        ctx.set_global("object", object.into());
+        let result: String = ctx
+            .eval(
+                lock,
+                "`${object.name} ${object[0]} ${Object.getOwnPropertySymbols(object).length}`",
+            )
+            .unwrap();
+        assert_eq!(result, "name first 1");
+        Ok(())
+    });
+}
+
+#[test]
+fn getting_a_property_runs_only_the_get_trap() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let object = eval_object(
+            lock,
+            ctx,
+            "globalThis.traps = []; new Proxy({}, { \
+               has(target, key) { traps.push('has'); return false; }, \
+               get(target, key) { traps.push('get'); return key; } })",
+        );
+        assert_eq!(object.get(lock, "name").unwrap().to_string(), "name");
+        let traps: String = ctx.eval(lock, "traps.join()").unwrap();
+        assert_eq!(traps, "get");
+        Ok(())
+    });
+}
+
+#[test]
+fn exceptions_thrown_by_getters_are_returned() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let object = eval_object(
+            lock,
+            ctx,
+            "({ get broken() { throw new RangeError('no value'); } })",
+        );
+        let err = object.get(lock, "broken").unwrap_err();
+        assert_eq!(err.name, ExceptionType::RangeError);
+        assert_eq!(err.message, "no value");
+
+        // The exception was caught, so JavaScript keeps running.
+        let result: String = ctx.eval(lock, "'still running'").unwrap();
+        assert_eq!(result, "still running");
+        Ok(())
+    });
+}
+
+#[test]
+fn properties_can_be_deleted() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let mut object = eval_object(
+            lock,
+            ctx,
+            "Object.defineProperty({ a: 1, 2: 'b' }, 'fixed', { value: 3 })",
+        );
+        assert!(object.delete(lock, "a"));
+        assert!(!object.has(lock, "a"));
+        assert!(object.delete(lock, 2));
+        assert!(!object.has(lock, 2));
+        // Deleting a missing property succeeds, like in JavaScript.
+        assert!(object.delete(lock, "missing"));
+        assert!(!object.delete(lock, "fixed"));
+        assert!(object.has(lock, "fixed"));
+        Ok(())
+    });
+}
+
+#[test]
+fn defined_properties_have_attributes() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let mut object = eval_object(lock, ctx, "({ set kind(value) { throw new Error(); } })");
+        let value = "resource".to_local(lock);
+        object.define_property(lock, "kind", value, PropertyAttributes::default());
+        let value = 1.to_local(lock);
+        object.define_property(
+            lock,
+            "count",
+            value,
+            PropertyAttributes {
+                writable: true,
+                enumerable: true,
+                configurable: true,
+            },
+        );
+        let tag = v8::Local::<v8::Symbol>::new(lock, "tag");
+        let value = true.to_local(lock);
+        object.define_property(
+            lock,
+            &tag,
+            value,
+            PropertyAttributes {
+                enumerable: true,
+                ..PropertyAttributes::default()
+            },
+        );
+        let value = "zero".to_local(lock);
+        object.define_property(
+            lock,
+            0,
+            value,
+            PropertyAttributes {
+                enumerable: true,
+                ..PropertyAttributes::default()
+            },
+        );
+
+        assert_eq!(object.get_own_property_names(lock), ["0", "count"]);
+        let keys = object.get_own_property_keys(lock);
+        let keys: Vec<String> = keys
+            .iter()
+            .map(|key| match key.try_cast::<v8::Symbol>() {
+                Some(symbol) => format!("Symbol({})", symbol.description(lock).unwrap()),
+                None => key.to_string(),
+            })
+            .collect();
+        assert_eq!(keys, ["0", "kind", "count", "Symbol(tag)"]);
+
+        ctx.set_global("object", object.into());
+        let result: String = ctx
+            .eval(
+                lock,
+                "'use strict'; \
+                 const { writable, enumerable, configurable } = \
+                   Object.getOwnPropertyDescriptor(object, 'kind'); \
+                 let error; \
+                 try { object.kind = 'other'; } catch (e) { error = e.name; } \
+                 `${object.kind} ${writable} ${enumerable} ${configurable} ${error}`",
+            )
+            .unwrap();
+        assert_eq!(result, "resource false false false TypeError");
+        Ok(())
+    });
+}
+
+#[test]
+fn prototypes_can_be_read_and_replaced() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let mut object = lock.new_object();
+        let prototype = eval_object(lock, ctx, "({ greet() { return 'hello'; } })");
+        assert!(object.get_prototype(lock).is_object());
+        object.set_prototype(lock, prototype.into()).unwrap();
+        assert!(object.has(lock, "greet"));
+        let prototype = object.get_prototype(lock).try_into_object().unwrap();
+        assert!(prototype.has(lock, "greet"));
+
+        ctx.set_global("object", object.clone().into());
+        let result: String = ctx.eval(lock, "object.greet()").unwrap();
+        assert_eq!(result, "hello");
+
+        let null = v8::Local::<v8::Value>::null(lock);
+        object.set_prototype(lock, null).unwrap();
+        assert!(object.get_prototype(lock).is_null());
+        assert!(!object.has(lock, "toString"));
+        Ok(())
+    });
+}
+
+#[test]
+fn invalid_prototypes_are_type_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, _ctx| {
+        let mut object = lock.new_object();
+        let value = "proto".to_local(lock);
+        let err = object.set_prototype(lock, value).unwrap_err();
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "Expected an object or null but got string");
+
+        // The prototype chain cannot contain a cycle.
+        let mut child = lock.new_object();
+        child.set_prototype(lock, object.clone().into()).unwrap();
+        let err = object.set_prototype(lock, child.into()).unwrap_err();
+        assert_eq!(err.name, ExceptionType::TypeError);
+        Ok(())
+    });
+}
@@ -0,0 +1,220 @@
+//! Tests for `jsg::Promise<T>`.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use jsg::ExceptionType;
+use jsg::Promise;
+use jsg::ToJS;
+
+#[test]
+fn promise_from_js_then_runs_continuation() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let promise: Promise<f64> = ctx.eval(lock, "Promise.resolve(21)").unwrap();
+        let derived = promise.then(lock, |_lock, value| Ok(value * 2.0));
+        ctx.set_global("derived", derived.to_js(lock));
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.result = null; derived.then(value => { result = value; }); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: f64 = ctx.eval(lock, "result").unwrap();
+        assert!((result - 42.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
+
+#[test]
+fn promise_from_js_wraps_non_promise_values() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let promise: Promise<String> = ctx.eval(lock, "'plain value'").unwrap();
+        assert!(promise.as_local(lock).is_promise());
+
+        let derived = promise.then(lock, |_lock, value| Ok(value.to_uppercase()));
+        ctx.set_global("derived", derived.to_js(lock));
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.result = null; derived.then(value => { result = value; }); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: String = ctx.eval(lock, "result").unwrap();
+        assert_eq!(result, "PLAIN VALUE");
+        Ok(())
+    });
+}
+
+#[test]
+fn promise_catch_receives_rejection_as_error() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let promise: Promise<String> = ctx
+            .eval(lock, "Promise.reject(new RangeError('bad value'))")
+            .unwrap();
+        let recovered = promise.catch(lock, |_lock, err| {
+            Ok(format!("recovered from {}: {}", err.name, err.message))
+        });
+        ctx.set_global("recovered", recovered.to_js(lock));
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.result = null; recovered.then(value => { result = value; }); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: String = ctx.eval(lock, "result").unwrap();
+        assert_eq!(result, "recovered from RangeError: bad value");
+        Ok(())
+    });
+}
+
+#[test]
+fn promise_then_propagates_rejections_and_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let rejected: Promise<f64> = ctx
+            .eval(lock, "Promise.reject(new TypeError('upstream'))")
+            .unwrap();
+        let derived = rejected.then(lock, |_lock, value| Ok(value + 1.0));
+        ctx.set_global("propagated", derived.to_js(lock));
+
+        let fulfilled: Promise<f64> = ctx.eval(lock, "Promise.resolve(1)").unwrap();
+        let derived = fulfilled.then(lock, |_lock, _value| -> jsg::Result<f64> {
+            Err(jsg::Error::new_range_error("from continuation"))
+        });
+        ctx.set_global("failed", derived.to_js(lock));
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.errors = []; \
+             propagated.catch(err => { errors.push(err.message); }); \
+             failed.catch(err => { errors.push(err.name + ': ' + err.message); }); \
+             true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: String = ctx.eval(lock, "errors.join(', ')").unwrap();
+        assert_eq!(result, "upstream, RangeError: from continuation");
+        Ok(())
+    });
+}
+
+#[test]
+fn promise_resolver_settles_promise() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let (promise, resolver) = lock.new_promise_and_resolver::<String>();
+        ctx.set_global("pending", promise.to_js(lock));
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.result = null; pending.then(value => { result = value; }); true",
+        )
+        .unwrap();
+
+        ctx.run_event_loop();
+        let result: bool = ctx.eval(lock, "result === null").unwrap();
+        assert!(result);
+
+        resolver.resolve(lock, "done".to_owned());
+        ctx.run_event_loop();
+        let result: String = ctx.eval(lock, "result").unwrap();
+        assert_eq!(result, "done");
+        Ok(())
+    });
+}
+
+#[test]
+fn promise_rejected_creates_rejected_promise() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let promise =
+            Promise::<f64>::rejected(lock, &jsg::Error::new_type_error("rejected from Rust"));
+        ctx.set_global("rejected", promise.to_js(lock));
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.error = null; rejected.catch(err => { error = err; }); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let err = ctx
+            .eval::<bool>(lock, "throw error")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "rejected from Rust");
+        Ok(())
+    });
+}
+
+#[test]
+fn promise_into_future_can_be_awaited() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let promise: Promise<f64> = ctx
+            .eval(
+                lock,
+                "globalThis.settle = null; new Promise(resolve => { settle = resolve; })",
+            )
+            .unwrap();
+        let future = promise.into_future(lock);
+        let result = lock.await_io(future, |_lock, value| value.map(|value| value + 1.0));
+        ctx.set_global("result", result.into());
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.output = null; result.then(value => { output = value; }); \
+             settle(41); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let output: f64 = ctx.eval(lock, "output").unwrap();
+        assert!((output - 42.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
+
+/// Counts its drops, to observe when the reaction that captured it is freed.
+struct DropCounter(Rc<Cell<u32>>);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn reactions_to_pending_promises_are_dropped_with_the_context() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let settled = Promise::resolved(lock, 1.0);
+        let counter = DropCounter(drops.clone());
+        let _: Promise<f64> = settled.then(lock, move |_lock, value| {
+            let _counter = &counter;
+            Ok(value)
+        });
+
+        // The resolver is dropped without ever settling the promise.
+        let (pending, _) = lock.new_promise_and_resolver::<f64>();
+        let counter = DropCounter(drops.clone());
+        let _: Promise<f64> = pending.then(lock, move |_lock, value| {
+            let _counter = &counter;
+            Ok(value)
+        });
+
+        ctx.run_event_loop();
+        assert_eq!(drops.get(), 1);
+        Ok(())
+    });
+    assert_eq!(drops.get(), 2);
+}
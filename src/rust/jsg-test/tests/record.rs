@@ -0,0 +1,145 @@
+//! Tests for `record<DOMString, T>` conversions via `HashMap` and `BTreeMap`.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use jsg::NonCoercible;
+use jsg::ToJS;
+
+use crate::EvalError;
+
+#[test]
+fn maps_unwrap_own_enumerable_string_keys() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let map: BTreeMap<String, f64> = ctx.eval(lock, "({ a: 1, b: 2, 10: 3 })").unwrap();
+        assert_eq!(
+            map,
+            BTreeMap::from([
+                ("10".to_owned(), 3.0),
+                ("a".to_owned(), 1.0),
+                ("b".to_owned(), 2.0),
+            ])
+        );
+
+        let code = "(() => { \
+                        const obj = Object.create({ inherited: 'no' }); \
+                        obj.own = 'yes'; \
+                        obj[Symbol('symbol')] = 'no'; \
+                        Object.defineProperty(obj, 'hidden', { value: 'no', enumerable: false }); \
+                        return obj; \
+                    })()";
+        let map: HashMap<String, String> = ctx.eval(lock, code).unwrap();
+        assert_eq!(map, HashMap::from([("own".to_owned(), "yes".to_owned())]));
+
+        let empty: HashMap<String, bool> = ctx.eval(lock, "({})").unwrap();
+        assert!(empty.is_empty());
+        Ok(())
+    });
+}
+
+#[test]
+fn maps_unwrap_nested_values() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let map: BTreeMap<String, Vec<String>> =
+            ctx.eval(lock, "({ a: ['x', 'y'], b: [] })").unwrap();
+        assert_eq!(map["a"], ["x", "y"]);
+        assert!(map["b"].is_empty());
+
+        let map: HashMap<String, Option<BTreeMap<String, f64>>> = ctx
+            .eval(lock, "({ present: { n: 1 }, missing: undefined })")
+            .unwrap();
+        assert_eq!(
+            map["present"],
+            Some(BTreeMap::from([("n".to_owned(), 1.0)]))
+        );
+        assert_eq!(map["missing"], None);
+        Ok(())
+    });
+}
+
+#[test]
+fn maps_reject_non_objects() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        for (code, type_of) in [("'abc'", "string"), ("42", "number")] {
+            match ctx.eval::<HashMap<String, String>>(lock, code) {
+                Err(EvalError::UncoercibleResult { message, .. }) => {
+                    assert_eq!(
+                        message,
+                        format!("TypeError: Expected an object but got {type_of}")
+                    );
+                }
+                Err(e) => panic!("Unexpected error for {code}: {e:?}"),
+                Ok(value) => panic!("Expected {code} to be rejected but got {value:?}"),
+            }
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn value_errors_include_key() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        match ctx.eval::<BTreeMap<String, NonCoercible<String>>>(lock, "({ good: 'a', bad: 1 })") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Incorrect type for the 'bad' property: Expected a string value but \
+                 got number"
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn exceptions_thrown_by_getters_are_returned() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        match ctx.eval::<HashMap<String, String>>(
+            lock,
+            "({ good: 'a', get bad() { throw new RangeError('not ready'); } })",
+        ) {
+            Err(EvalError::UncoercibleResult { message, .. }) => {
+                assert_eq!(message, "RangeError: not ready");
+            }
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(value) => panic!("Expected an error but got {value:?}"),
+        }
+
+        // The exception was caught, so JavaScript keeps running.
+        let result: String = ctx.eval(lock, "'still running'").unwrap();
+        assert_eq!(result, "still running");
+        Ok(())
+    });
+}
+
+#[test]
+fn maps_wrap_to_plain_objects() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let map = BTreeMap::from([
+            ("first".to_owned(), vec![1.0, 2.0]),
+            ("second".to_owned(), vec![]),
+        ]);
+        ctx.set_global("btree", map.to_js(lock));
+        let result: String = ctx.eval(lock, "JSON.stringify(btree)").unwrap();
+        assert_eq!(result, r#"{"first":[1,2],"second":[]}"#);
+
+        let map = HashMap::from([("key".to_owned(), "value".to_owned())]);
+        ctx.set_global("hash", map.to_js(lock));
+        let result: bool = ctx
+            .eval(
+                lock,
+                "Object.getPrototypeOf(hash) === Object.prototype && \
+                 Object.keys(hash).length === 1 && hash.key === 'value'",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
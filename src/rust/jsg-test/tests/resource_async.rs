@@ -0,0 +1,276 @@
+//! Tests for `Lock::await_io` and `async fn` methods on Rust resources.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::Context;
+use std::task::Poll;
+
+use jsg::ExceptionType;
+use jsg::FromJS;
+use jsg::ResourceState;
+use jsg::ResourceTemplate;
+use jsg::v8;
+use jsg::v8::ToLocalValue;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+use jsg_macros::jsg_static_method;
+
+/// A future that is pending the first time it is polled, so that completing it requires a turn of
+/// the event loop.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn yield_now() -> YieldNow {
+    YieldNow(false)
+}
+
+#[jsg_resource]
+struct AsyncResource {
+    _state: ResourceState,
+    count: f64,
+}
+
+#[jsg_resource]
+impl AsyncResource {
+    #[jsg_method]
+    pub async fn echo(&self, value: String) -> String {
+        yield_now().await;
+        value
+    }
+
+    #[jsg_method]
+    pub async fn increment_later(&mut self) -> f64 {
+        yield_now().await;
+        self.count += 1.0;
+        self.count
+    }
+
+    #[jsg_method]
+    pub async fn fail(&self, message: &str) -> Result<String, jsg::Error> {
+        yield_now().await;
+        Err(jsg::Error::new_range_error(message))
+    }
+
+    #[jsg_static_method]
+    pub async fn double(value: f64) -> f64 {
+        yield_now().await;
+        value * 2.0
+    }
+}
+
+/// Records values passed from JavaScript, so that tests can check them once the context is gone.
+#[jsg_resource]
+struct Recorder {
+    _state: ResourceState,
+    entries: Rc<RefCell<Vec<String>>>,
+}
+
+#[jsg_resource]
+impl Recorder {
+    #[jsg_method]
+    pub fn record(&self, entry: String) {
+        self.entries.borrow_mut().push(entry);
+    }
+}
+
+fn wrap_async_resource<'s>(lock: &mut jsg::Lock<'s>) -> v8::Local<'s, v8::Value> {
+    let resource = jsg::Ref::new(AsyncResource {
+        _state: ResourceState::default(),
+        count: 0.0,
+    });
+    let mut template = AsyncResourceTemplate::new(lock);
+    unsafe { jsg::wrap_resource(lock, resource, &mut template) }
+}
+
+#[test]
+fn async_method_returns_promise() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_async_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        let result: bool = ctx
+            .eval(lock, "resource.echo('hello') instanceof Promise")
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn async_method_resolves_after_event_loop_runs() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_async_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "globalThis.result = 'pending'; \
+                 resource.echo('hello').then(value => { result = value; }); \
+                 result === 'pending'",
+            )
+            .unwrap();
+        assert!(result);
+
+        ctx.run_event_loop();
+
+        let result: String = ctx.eval(lock, "result").unwrap();
+        assert_eq!(result, "hello");
+        Ok(())
+    });
+}
+
+#[test]
+fn async_method_can_mutate_resource() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_async_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.results = []; \
+             resource.incrementLater().then(value => results.push(value)); \
+             resource.incrementLater().then(value => results.push(value)); \
+             true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: String = ctx.eval(lock, "results.join(',')").unwrap();
+        assert_eq!(result, "1,2");
+        Ok(())
+    });
+}
+
+#[test]
+fn async_method_error_rejects_promise() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_async_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.error = null; \
+             resource.fail('out of range').catch(err => { error = err; }); \
+             true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let err = ctx
+            .eval::<bool>(lock, "throw error")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::RangeError);
+        assert_eq!(err.message, "out of range");
+        Ok(())
+    });
+}
+
+#[test]
+fn async_static_method_resolves() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = AsyncResourceTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("AsyncResource", constructor);
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.result = null; \
+             AsyncResource.double(21).then(value => { result = value; }); \
+             true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: f64 = ctx.eval(lock, "result").unwrap();
+        assert!((result - 42.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
+
+#[test]
+fn await_io_runs_callback_under_lock() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let promise = lock.await_io(
+            async {
+                yield_now().await;
+                "from io".to_owned()
+            },
+            |lock, value| {
+                // The lock is usable again once the future has completed.
+                let local = value.to_local(lock);
+                let value = String::from_js(lock, local)?;
+                Ok(format!("{value}!"))
+            },
+        );
+        ctx.set_global("promise", promise.into());
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.result = null; promise.then(value => { result = value; }); true",
+        )
+        .unwrap();
+        ctx.run_event_loop();
+
+        let result: String = ctx.eval(lock, "result").unwrap();
+        assert_eq!(result, "from io!");
+        Ok(())
+    });
+}
+
+#[test]
+fn await_io_resumes_under_a_new_lock_once_the_callback_returns() {
+    let entries = Rc::new(RefCell::new(Vec::new()));
+    let resumed = Rc::new(Cell::new(false));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let recorder = jsg::Ref::new(Recorder {
+            _state: ResourceState::default(),
+            entries: entries.clone(),
+        });
+        let mut template = RecorderTemplate::new(lock);
+        let recorder = unsafe { jsg::wrap_resource(lock, recorder, &mut template) };
+        ctx.set_global("recorder", recorder);
+
+        let resumed_in_callback = resumed.clone();
+        let promise = lock.await_io(yield_now(), move |_lock, ()| -> jsg::Result<()> {
+            resumed_in_callback.set(true);
+            Err(jsg::Error::new_range_error("The operation was aborted"))
+        });
+        ctx.set_global("promise", promise.into());
+        ctx.eval::<bool>(
+            lock,
+            "promise.catch(err => recorder.record(`${err.name} ${err.message}`)); true",
+        )
+        .unwrap();
+
+        // The event loop has not run, so the I/O is still pending when the callback returns.
+        assert!(!resumed.get());
+        Ok(())
+    });
+
+    assert!(resumed.get());
+    assert_eq!(*entries.borrow(), ["RangeError The operation was aborted"]);
+}
@@ -0,0 +1,160 @@
+//! Tests for constructing Rust resources from JavaScript via `#[jsg_constructor]`.
+
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ResourceTemplate;
+use jsg_macros::jsg_constructor;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Greeter {
+    _state: ResourceState,
+    greeting: String,
+}
+
+#[jsg_resource]
+impl Greeter {
+    #[jsg_constructor]
+    pub fn constructor(greeting: String) -> Result<Self, jsg::Error> {
+        if greeting.is_empty() {
+            return Err(jsg::Error::new_range_error("greeting must not be empty"));
+        }
+        Ok(Self {
+            _state: ResourceState::default(),
+            greeting,
+        })
+    }
+
+    #[jsg_method]
+    pub fn greet(&self, name: &str) -> String {
+        format!("{}, {name}!", self.greeting)
+    }
+}
+
+#[jsg_resource]
+struct Counter {
+    _state: ResourceState,
+    value: f64,
+}
+
+#[jsg_resource]
+impl Counter {
+    #[jsg_constructor]
+    pub fn new(initial: Option<f64>) -> Self {
+        Self {
+            _state: ResourceState::default(),
+            value: initial.unwrap_or_default(),
+        }
+    }
+
+    #[jsg_method]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+}
+
+#[jsg_resource]
+struct NotConstructible {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl NotConstructible {
+    #[jsg_method]
+    pub fn ping(&self) -> String {
+        "pong".to_owned()
+    }
+}
+
+#[test]
+fn constructor_creates_resource() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = GreeterTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("Greeter", constructor);
+
+        let result: String = ctx
+            .eval(lock, "new Greeter('Hello').greet('World')")
+            .unwrap();
+        assert_eq!(result, "Hello, World!");
+
+        let result: bool = ctx
+            .eval(lock, "new Greeter('Hi') instanceof Greeter")
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn constructor_accepts_optional_arguments() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = CounterTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("Counter", constructor);
+
+        let result: f64 = ctx.eval(lock, "new Counter().value()").unwrap();
+        assert!(result.abs() < f64::EPSILON);
+
+        let result: f64 = ctx.eval(lock, "new Counter(5).value()").unwrap();
+        assert!((result - 5.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
+
+#[test]
+fn constructor_propagates_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = GreeterTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("Greeter", constructor);
+
+        let err = ctx
+            .eval::<bool>(lock, "new Greeter('')")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::RangeError);
+        assert_eq!(err.message, "greeting must not be empty");
+        Ok(())
+    });
+}
+
+#[test]
+fn constructor_requires_new() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = GreeterTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("Greeter", constructor);
+
+        let err = ctx
+            .eval::<bool>(lock, "Greeter('Hello')")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert!(err.message.contains("Please use the 'new' operator"));
+        Ok(())
+    });
+}
+
+#[test]
+fn resource_without_constructor_is_illegal_to_construct() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = NotConstructibleTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("NotConstructible", constructor);
+
+        let err = ctx
+            .eval::<bool>(lock, "new NotConstructible()")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "Illegal constructor");
+        Ok(())
+    });
+}
@@ -0,0 +1,199 @@
+//! Tests for dropping Rust resources when V8 garbage collects their wrappers.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Tracked {
+    _state: ResourceState,
+    drops: Rc<Cell<u32>>,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[jsg_resource]
+impl Tracked {}
+
+#[jsg_resource]
+struct Factory {
+    _state: ResourceState,
+    drops: Rc<Cell<u32>>,
+    kept: Option<jsg::Ref<Tracked>>,
+}
+
+#[jsg_resource]
+impl Factory {
+    #[jsg_method]
+    pub fn create(&self) -> Tracked {
+        Tracked {
+            _state: ResourceState::default(),
+            drops: self.drops.clone(),
+        }
+    }
+
+    #[jsg_method]
+    pub fn keep(&mut self) -> jsg::Ref<Tracked> {
+        let tracked = jsg::Ref::new(self.create());
+        self.kept = Some(tracked.clone());
+        tracked
+    }
+
+    #[jsg_method]
+    pub fn kept(&self) -> Option<jsg::Ref<Tracked>> {
+        self.kept.clone()
+    }
+
+    #[jsg_method]
+    pub fn release(&mut self) {
+        self.kept = None;
+    }
+}
+
+fn set_factory(lock: &mut jsg::Lock, ctx: &crate::EvalContext, drops: &Rc<Cell<u32>>) {
+    let factory = jsg::Ref::new(Factory {
+        _state: ResourceState::default(),
+        drops: drops.clone(),
+        kept: None,
+    });
+    let factory = factory.to_js(lock);
+    ctx.set_global("factory", factory);
+}
+
+#[test]
+fn unreachable_resources_are_dropped_on_gc() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_factory(lock, ctx, &drops);
+
+        ctx.eval::<bool>(lock, "for (let i = 0; i < 10; i++) factory.create(); true")
+            .unwrap();
+        assert_eq!(drops.get(), 0);
+
+        ctx.request_gc();
+        assert_eq!(drops.get(), 10);
+        Ok(())
+    });
+    drop(harness);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn reachable_resources_are_not_dropped_on_gc() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_factory(lock, ctx, &drops);
+
+        ctx.eval::<bool>(lock, "globalThis.tracked = factory.create(); true")
+            .unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+
+        ctx.eval::<bool>(lock, "delete globalThis.tracked").unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 1);
+        Ok(())
+    });
+    drop(harness);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn resources_held_by_rust_keep_their_wrapper_across_gc() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_factory(lock, ctx, &drops);
+
+        // Only Rust refers to the resource once the wrapper is no longer reachable from
+        // JavaScript, which must not lose its expando properties or its identity.
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.registry = new WeakMap(); \
+             (() => { \
+               const tracked = factory.keep(); \
+               tracked.expando = 42; \
+               registry.set(tracked, 'entry'); \
+             })(); \
+             true",
+        )
+        .unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+
+        let result: String = ctx
+            .eval(
+                lock,
+                "(() => { \
+                   const kept = factory.kept(); \
+                   return `${kept.expando} ${registry.get(kept)} ${kept === factory.kept()}`; \
+                 })()",
+            )
+            .unwrap();
+        assert_eq!(result, "42 entry true");
+
+        // The wrapper becomes weak once Rust releases the resource.
+        ctx.eval::<bool>(lock, "factory.release(); true").unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 1);
+        Ok(())
+    });
+    drop(harness);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn resources_released_without_the_lock_are_dropped_on_gc() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let tracked = jsg::Ref::new(Tracked {
+            _state: ResourceState::default(),
+            drops: drops.clone(),
+        });
+        // Once the scope is closed, only the `Ref` held here keeps the wrapper alive.
+        lock.within_handle_scope(|lock| {
+            let _wrapper = tracked.clone().to_js(lock);
+        });
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+
+        // The wrapper cannot be made weak without the lock, which the GC takes again.
+        ctx.run_without_lock(|| drop(tracked));
+        ctx.request_gc();
+        assert_eq!(drops.get(), 1);
+        Ok(())
+    });
+    drop(harness);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn realm_drops_resources_not_collected() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_factory(lock, ctx, &drops);
+
+        ctx.eval::<bool>(
+            lock,
+            "globalThis.tracked = [factory.create(), factory.create()]; true",
+        )
+        .unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+        Ok(())
+    });
+    drop(harness);
+    assert_eq!(drops.get(), 2);
+}
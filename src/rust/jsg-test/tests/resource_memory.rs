@@ -0,0 +1,138 @@
+//! Tests for reporting Rust resources in V8 heap snapshots.
+
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+use jsg_macros::jsg_struct;
+
+#[jsg_struct]
+struct Options {
+    pub label: String,
+    pub tags: Vec<String>,
+}
+
+#[jsg_resource]
+struct Leaf {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Leaf {}
+
+#[jsg_resource]
+struct Bud {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Bud {}
+
+#[jsg_resource(name = "Tree")]
+struct Branch {
+    _state: ResourceState,
+    options: Options,
+    leaf: jsg::Ref<Leaf>,
+    spare: Option<jsg::Ref<Leaf>>,
+}
+
+#[jsg_resource]
+impl Branch {
+    #[jsg_method]
+    pub fn grow(&self) -> Bud {
+        Bud {
+            _state: ResourceState::default(),
+        }
+    }
+}
+
+fn set_tree(lock: &mut jsg::Lock, ctx: &crate::EvalContext) {
+    let leaf = jsg::Ref::new(Leaf {
+        _state: ResourceState::default(),
+    });
+    let tree = jsg::Ref::new(Branch {
+        _state: ResourceState::default(),
+        options: Options {
+            label: "oak".to_owned(),
+            tags: vec!["tall".to_owned()],
+        },
+        leaf: leaf.clone(),
+        spare: Some(leaf),
+    });
+    let tree = tree.to_js(lock);
+    ctx.set_global("tree", tree);
+}
+
+/// Takes a heap snapshot and returns the names of its native nodes, i.e. the nodes added by
+/// embedders. Strings in the snapshot are not included, so names used by the test itself do not
+/// show up.
+fn native_nodes(lock: &mut jsg::Lock, ctx: &crate::EvalContext) -> Vec<String> {
+    let snapshot = ctx.take_heap_snapshot().to_js(lock);
+    ctx.set_global("snapshot", snapshot);
+    ctx.eval(
+        lock,
+        "(() => {
+           const { snapshot: { meta }, nodes, strings } = JSON.parse(globalThis.snapshot);
+           delete globalThis.snapshot;
+           const types = meta.node_types[0];
+           const fields = meta.node_fields.length;
+           const names = [];
+           for (let i = 0; i < nodes.length; i += fields) {
+             if (types[nodes[i]] === 'native') names.push(strings[nodes[i + 1]]);
+           }
+           return names;
+         })()",
+    )
+    .unwrap()
+}
+
+fn count(nodes: &[String], name: &str) -> usize {
+    nodes.iter().filter(|node| *node == name).count()
+}
+
+#[test]
+fn wrapped_resources_appear_in_heap_snapshot() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_tree(lock, ctx);
+
+        let nodes = native_nodes(lock, ctx);
+        assert_eq!(count(&nodes, "workerd / Tree"), 1);
+        Ok(())
+    });
+}
+
+#[test]
+fn resource_fields_appear_in_heap_snapshot() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_tree(lock, ctx);
+
+        let nodes = native_nodes(lock, ctx);
+        // `leaf` and `spare` refer to the same resource, which is tracked once.
+        assert_eq!(count(&nodes, "workerd / Leaf"), 1);
+        assert_eq!(count(&nodes, "workerd / Options"), 1);
+        // `label` and the single element of `tags`.
+        assert_eq!(count(&nodes, "workerd / alloc::string::String"), 2);
+        Ok(())
+    });
+}
+
+#[test]
+fn collected_resources_do_not_appear_in_heap_snapshot() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_tree(lock, ctx);
+
+        ctx.eval::<bool>(lock, "globalThis.bud = tree.grow(); true")
+            .unwrap();
+        let nodes = native_nodes(lock, ctx);
+        assert_eq!(count(&nodes, "workerd / Bud"), 1);
+
+        ctx.eval::<bool>(lock, "delete globalThis.bud").unwrap();
+        ctx.request_gc();
+        let nodes = native_nodes(lock, ctx);
+        assert_eq!(count(&nodes, "workerd / Bud"), 0);
+        Ok(())
+    });
+}
@@ -0,0 +1,151 @@
+//! Tests for resource properties declared with `#[jsg_getter]` and `#[jsg_setter]`.
+
+use jsg::ResourceState;
+use jsg::ResourceTemplate;
+use jsg_macros::jsg_getter;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+use jsg_macros::jsg_setter;
+
+#[jsg_resource]
+struct PropertyResource {
+    _state: ResourceState,
+    name: String,
+    count: f64,
+}
+
+#[jsg_resource]
+impl PropertyResource {
+    #[jsg_getter]
+    pub fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    #[jsg_setter]
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    #[jsg_getter(name = "total")]
+    pub fn count(&self) -> f64 {
+        self.count
+    }
+
+    #[jsg_getter(instance)]
+    pub fn get_label(&self) -> String {
+        format!("label:{}", self.name)
+    }
+
+    #[jsg_method]
+    pub fn increment(&mut self) {
+        self.count += 1.0;
+    }
+}
+
+fn wrap_property_resource<'s>(lock: &mut jsg::Lock<'s>) -> jsg::v8::Local<'s, jsg::v8::Value> {
+    let resource = jsg::Ref::new(PropertyResource {
+        _state: ResourceState::default(),
+        name: "initial".to_owned(),
+        count: 1.0,
+    });
+    let mut template = PropertyResourceTemplate::new(lock);
+    unsafe { jsg::wrap_resource(lock, resource, &mut template) }
+}
+
+#[test]
+fn prototype_property_getter_and_setter() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_property_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        let result: String = ctx.eval(lock, "resource.name").unwrap();
+        assert_eq!(result, "initial");
+
+        let result: String = ctx
+            .eval(lock, "resource.name = 'updated'; resource.name")
+            .unwrap();
+        assert_eq!(result, "updated");
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "Object.getPrototypeOf(resource).hasOwnProperty('name') && \
+                 !resource.hasOwnProperty('name')",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn readonly_property_ignores_assignment() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_property_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        let result: f64 = ctx
+            .eval(lock, "resource.increment(); resource.total")
+            .unwrap();
+        assert!((result - 2.0).abs() < f64::EPSILON);
+
+        let result: f64 = ctx
+            .eval(lock, "resource.total = 10; resource.total")
+            .unwrap();
+        assert!((result - 2.0).abs() < f64::EPSILON);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "Object.getOwnPropertyDescriptor(Object.getPrototypeOf(resource), 'total').set \
+                 === undefined",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn instance_property_is_own_property() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_property_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        let result: String = ctx.eval(lock, "resource.label").unwrap();
+        assert_eq!(result, "label:initial");
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "resource.hasOwnProperty('label') && \
+                 !Object.getPrototypeOf(resource).hasOwnProperty('label')",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn property_getter_rejects_foreign_receiver() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let wrapped = wrap_property_resource(lock);
+        ctx.set_global("resource", wrapped);
+
+        let err = ctx
+            .eval::<String>(
+                lock,
+                "Object.getOwnPropertyDescriptor(Object.getPrototypeOf(resource), 'name')\
+                 .get.call({})",
+            )
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, jsg::ExceptionType::TypeError);
+        Ok(())
+    });
+}
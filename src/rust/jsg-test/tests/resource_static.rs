@@ -0,0 +1,134 @@
+//! Tests for `#[jsg_static_method]` and `#[jsg_static_constant]` on Rust resources.
+
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ResourceTemplate;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+use jsg_macros::jsg_static_constant;
+use jsg_macros::jsg_static_method;
+
+#[jsg_resource]
+struct MathUtil {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl MathUtil {
+    #[jsg_static_constant]
+    pub const MAX_VALUE: u32 = 100;
+
+    #[jsg_static_constant(name = "HALF")]
+    pub const HALF_VALUE: f64 = 0.5;
+
+    #[jsg_static_method]
+    pub fn add(a: f64, b: f64) -> f64 {
+        a + b
+    }
+
+    #[jsg_static_method(name = "checkedDivide")]
+    pub fn divide(a: f64, b: f64) -> Result<f64, jsg::Error> {
+        if b == 0.0 {
+            return Err(jsg::Error::new_range_error("Division by zero"));
+        }
+        Ok(a / b)
+    }
+
+    #[jsg_method]
+    pub fn ping(&self) -> String {
+        "pong".to_owned()
+    }
+}
+
+#[test]
+fn static_methods_are_callable_on_constructor() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = MathUtilTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("MathUtil", constructor);
+
+        let result: f64 = ctx.eval(lock, "MathUtil.add(2, 3)").unwrap();
+        assert!((result - 5.0).abs() < f64::EPSILON);
+
+        let result: f64 = ctx.eval(lock, "MathUtil.checkedDivide(1, 4)").unwrap();
+        assert!((result - 0.25).abs() < f64::EPSILON);
+
+        let result: bool = ctx
+            .eval(lock, "MathUtil.prototype.add === undefined")
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn static_methods_propagate_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = MathUtilTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("MathUtil", constructor);
+
+        let err = ctx
+            .eval::<f64>(lock, "MathUtil.checkedDivide(1, 0)")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::RangeError);
+        assert_eq!(err.message, "Division by zero");
+        Ok(())
+    });
+}
+
+#[test]
+fn static_methods_are_not_constructible() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = MathUtilTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("MathUtil", constructor);
+
+        let err = ctx
+            .eval::<f64>(lock, "new MathUtil.add(1, 2)")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        Ok(())
+    });
+}
+
+#[test]
+fn static_constants_are_set_on_constructor_and_prototype() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = MathUtilTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("MathUtil", constructor);
+
+        let result: f64 = ctx.eval(lock, "MathUtil.MAX_VALUE").unwrap();
+        assert!((result - 100.0).abs() < f64::EPSILON);
+
+        let result: f64 = ctx.eval(lock, "MathUtil.prototype.MAX_VALUE").unwrap();
+        assert!((result - 100.0).abs() < f64::EPSILON);
+
+        let result: f64 = ctx.eval(lock, "MathUtil.HALF").unwrap();
+        assert!((result - 0.5).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
+
+#[test]
+fn static_constants_are_read_only() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let template = MathUtilTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("MathUtil", constructor);
+
+        let result: f64 = ctx
+            .eval(lock, "MathUtil.MAX_VALUE = 5; MathUtil.MAX_VALUE")
+            .unwrap();
+        assert!((result - 100.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
@@ -0,0 +1,112 @@
+//! Tests for tracing the JavaScript values held by Rust resources during garbage collection.
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Emitter {
+    _state: ResourceState,
+    drops: Rc<Cell<u32>>,
+    listener: Option<jsg::Value>,
+}
+
+impl Drop for Emitter {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[jsg_resource]
+impl Emitter {
+    #[jsg_method]
+    pub fn on(&mut self, listener: jsg::Value) {
+        self.listener = Some(listener);
+    }
+
+    #[jsg_method]
+    pub fn listener(&self) -> Option<jsg::Value> {
+        self.listener.clone()
+    }
+}
+
+fn new_emitter(drops: &Rc<Cell<u32>>) -> jsg::Ref<Emitter> {
+    jsg::Ref::new(Emitter {
+        _state: ResourceState::default(),
+        drops: drops.clone(),
+        listener: None,
+    })
+}
+
+#[test]
+fn cycles_through_held_values_are_collected() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let emitter = new_emitter(&drops).to_js(lock);
+        ctx.set_global("emitter", emitter);
+
+        // The listener refers back to the emitter's wrapper, which holds the listener.
+        ctx.eval::<bool>(
+            lock,
+            "emitter.on({ emitter }); delete globalThis.emitter; true",
+        )
+        .unwrap();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 1);
+        Ok(())
+    });
+    drop(harness);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn held_values_survive_gc_while_resource_is_reachable() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let emitter = new_emitter(&drops).to_js(lock);
+        ctx.set_global("emitter", emitter);
+
+        ctx.eval::<bool>(lock, "emitter.on({ marker: 42 }); true")
+            .unwrap();
+        // The handle is made strong again after each GC and traced again by the next one.
+        ctx.request_gc();
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+
+        let result: bool = ctx.eval(lock, "emitter.listener().marker === 42").unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn held_values_survive_gc_while_rust_holds_resource() {
+    let drops = Rc::new(Cell::new(0));
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let emitter = new_emitter(&drops);
+        let wrapper = emitter.clone().to_js(lock);
+        ctx.set_global("emitter", wrapper);
+
+        ctx.eval::<bool>(
+            lock,
+            "emitter.on({ marker: 42 }); delete globalThis.emitter; true",
+        )
+        .unwrap();
+        // The wrapper is collected, but the resource and its listener outlive it.
+        ctx.request_gc();
+        assert_eq!(drops.get(), 0);
+
+        let listener = emitter.listener.as_ref().unwrap().as_local(lock);
+        ctx.set_global("listener", listener);
+        let result: bool = ctx.eval(lock, "listener.marker === 42").unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
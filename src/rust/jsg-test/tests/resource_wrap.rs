@@ -0,0 +1,228 @@
+//! Tests for passing Rust resources to and from JavaScript via `jsg::Ref<R>`.
+
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ResourceTemplate;
+use jsg::ToJS;
+use jsg_macros::jsg_constructor;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Counter {
+    _state: ResourceState,
+    count: u32,
+}
+
+#[jsg_resource]
+impl Counter {
+    #[jsg_constructor]
+    pub fn constructor(count: Option<u32>) -> Self {
+        Self {
+            _state: ResourceState::default(),
+            count: count.unwrap_or_default(),
+        }
+    }
+
+    #[jsg_method]
+    pub fn increment(&mut self) {
+        self.count += 1;
+    }
+
+    #[jsg_method]
+    pub fn value(&self) -> u32 {
+        self.count
+    }
+}
+
+#[jsg_resource]
+struct Registry {
+    _state: ResourceState,
+    counter: jsg::Ref<Counter>,
+}
+
+#[jsg_resource]
+impl Registry {
+    #[jsg_method]
+    pub fn counter(&self) -> jsg::Ref<Counter> {
+        self.counter.clone()
+    }
+
+    #[jsg_method]
+    pub fn set_counter(&mut self, counter: jsg::Ref<Counter>) {
+        self.counter = counter;
+    }
+
+    #[jsg_method]
+    pub fn new_counter(&self, count: u32) -> Counter {
+        Counter {
+            _state: ResourceState::default(),
+            count,
+        }
+    }
+
+    #[jsg_method]
+    pub fn read(&self, counter: jsg::Ref<Counter>) -> u32 {
+        counter.count
+    }
+}
+
+fn new_counter(count: u32) -> jsg::Ref<Counter> {
+    jsg::Ref::new(Counter {
+        _state: ResourceState::default(),
+        count,
+    })
+}
+
+fn set_registry(lock: &mut jsg::Lock, ctx: &crate::EvalContext) {
+    let registry = jsg::Ref::new(Registry {
+        _state: ResourceState::default(),
+        counter: new_counter(0),
+    });
+    let registry = registry.to_js(lock);
+    ctx.set_global("registry", registry);
+}
+
+#[test]
+fn returned_resource_reuses_existing_wrapper() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx);
+
+        let result: bool = ctx
+            .eval(lock, "registry.counter() === registry.counter()")
+            .unwrap();
+        assert!(result);
+
+        let result: u32 = ctx
+            .eval(
+                lock,
+                "registry.counter().increment(); registry.counter().increment(); registry.counter().value()",
+            )
+            .unwrap();
+        assert_eq!(result, 2);
+        Ok(())
+    });
+}
+
+#[test]
+fn resource_returned_by_value_is_wrapped() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx);
+
+        let result: u32 = ctx
+            .eval(lock, "const c = registry.newCounter(5); c.increment(); c.value()")
+            .unwrap();
+        assert_eq!(result, 6);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "Object.getPrototypeOf(registry.newCounter(1)) === Object.getPrototypeOf(registry.counter())",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn resource_parameter_unwraps_same_resource() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx);
+
+        let result: u32 = ctx.eval(lock, "registry.read(registry.newCounter(7))").unwrap();
+        assert_eq!(result, 7);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "const c = registry.newCounter(3); registry.setCounter(c); registry.counter() === c",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn resource_parameter_rejects_other_values() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx);
+
+        let err = ctx
+            .eval::<u32>(lock, "registry.read({})")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "Expected Counter but got object");
+
+        let err = ctx
+            .eval::<u32>(lock, "registry.read(registry)")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "Expected Counter but got object");
+
+        let err = ctx
+            .eval::<u32>(lock, "registry.read(1)")
+            .unwrap_err()
+            .unwrap_jsg_err(lock);
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "Expected Counter but got number");
+        Ok(())
+    });
+}
+
+#[test]
+fn get_template_returns_cached_template() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, _ctx| {
+        let lhs = lock
+            .get_template::<Counter>()
+            .as_local(lock)
+            .get_function(lock);
+        let rhs = lock
+            .get_template::<Counter>()
+            .as_local(lock)
+            .get_function(lock);
+        assert_eq!(lhs, rhs);
+
+        let template = CounterTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        assert_eq!(lhs, constructor);
+
+        let other = lock
+            .get_template::<Registry>()
+            .as_local(lock)
+            .get_function(lock);
+        assert_ne!(lhs, other);
+        Ok(())
+    });
+}
+
+#[test]
+fn constructed_and_returned_resources_share_class() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_registry(lock, ctx);
+        let template = CounterTemplate::new(lock);
+        let constructor = template.get_constructor().as_local(lock).get_function(lock);
+        ctx.set_global("Counter", constructor);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "registry.counter() instanceof Counter && registry.newCounter(1) instanceof Counter",
+            )
+            .unwrap();
+        assert!(result);
+
+        let result: u32 = ctx.eval(lock, "registry.read(new Counter(4))").unwrap();
+        assert_eq!(result, 4);
+        Ok(())
+    });
+}
@@ -0,0 +1,213 @@
+//! Tests for `Vec<T>` and `jsg::Sequence<T>` conversions.
+
+use jsg::NonCoercible;
+use jsg::Sequence;
+use jsg::ToJS;
+use jsg_macros::jsg_struct;
+
+use crate::EvalError;
+
+#[jsg_struct]
+struct Record {
+    pub name: String,
+    pub ttl: f64,
+}
+
+#[test]
+fn vec_unwraps_arrays() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let strings: Vec<String> = ctx.eval(lock, "['a', 'b', 'c']").unwrap();
+        assert_eq!(strings, ["a", "b", "c"]);
+
+        let numbers: Vec<f64> = ctx.eval(lock, "[1, 2.5, '3']").unwrap();
+        assert_eq!(numbers, [1.0, 2.5, 3.0]);
+
+        let empty: Vec<bool> = ctx.eval(lock, "[]").unwrap();
+        assert!(empty.is_empty());
+
+        let nested: Vec<Vec<f64>> = ctx.eval(lock, "[[1, 2], [], [3]]").unwrap();
+        assert_eq!(nested, [vec![1.0, 2.0], vec![], vec![3.0]]);
+        Ok(())
+    });
+}
+
+#[test]
+fn vec_wraps_to_array() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let records = vec![
+            Record {
+                name: "a.example".to_owned(),
+                ttl: 300.0,
+            },
+            Record {
+                name: "b.example".to_owned(),
+                ttl: 60.0,
+            },
+        ];
+        ctx.set_global("records", records.to_js(lock));
+        let result: String = ctx
+            .eval(
+                lock,
+                "Array.isArray(records) && records.map(r => r.name + '/' + r.ttl).join(',')",
+            )
+            .unwrap();
+        assert_eq!(result, "a.example/300,b.example/60");
+
+        ctx.set_global("empty", Vec::<String>::new().to_js(lock));
+        let result: bool = ctx
+            .eval(lock, "Array.isArray(empty) && empty.length === 0")
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
+
+#[test]
+fn vec_rejects_non_arrays() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        for (code, type_of) in [
+            ("'abc'", "string"),
+            ("new Set([1, 2])", "object"),
+            ("({ length: 1, 0: 'a' })", "object"),
+        ] {
+            match ctx.eval::<Vec<String>>(lock, code) {
+                Err(EvalError::UncoercibleResult { message, .. }) => {
+                    assert_eq!(
+                        message,
+                        format!("TypeError: Expected an Array but got {type_of}")
+                    );
+                }
+                Err(e) => panic!("Unexpected error for {code}: {e:?}"),
+                Ok(value) => panic!("Expected {code} to be rejected but got {value:?}"),
+            }
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn element_errors_include_index() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        match ctx.eval::<Vec<NonCoercible<String>>>(lock, "['a', 'b', 3]") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Incorrect type for the element at index 2: Expected a string value \
+                 but got number"
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+
+        match ctx.eval::<Vec<Record>>(lock, "[{ name: 'a', ttl: 1 }, { ttl: 2 }]") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Incorrect type for the element at index 1: Required field 'name' is \
+                 missing on 'Record'."
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+
+        match ctx.eval::<Vec<Vec<NonCoercible<f64>>>>(lock, "[[1], [2, 'x']]") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Incorrect type for the element at index 1: Incorrect type for the \
+                 element at index 1: Expected a number value but got string"
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn sequence_accepts_iterables() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let from_array: Sequence<String> = ctx.eval(lock, "['x', 'y']").unwrap();
+        assert_eq!(*from_array, ["x", "y"]);
+
+        let from_set: Sequence<f64> = ctx.eval(lock, "new Set([1, 2, 2, 3])").unwrap();
+        assert_eq!(from_set.into_inner(), [1.0, 2.0, 3.0]);
+
+        let from_generator: Sequence<String> = ctx
+            .eval(lock, "(function* () { yield 'first'; yield 'second'; })()")
+            .unwrap();
+        assert_eq!(*from_generator, ["first", "second"]);
+
+        let from_map_keys: Sequence<String> = ctx
+            .eval(lock, "new Map([['k1', 1], ['k2', 2]]).keys()")
+            .unwrap();
+        assert_eq!(*from_map_keys, ["k1", "k2"]);
+        Ok(())
+    });
+}
+
+#[test]
+fn sequence_rejects_non_iterables() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        match ctx.eval::<Sequence<String>>(lock, "'abc'") {
+            Err(EvalError::UncoercibleResult { message, .. }) => {
+                assert_eq!(message, "TypeError: Expected a Sequence but got string");
+            }
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+
+        match ctx.eval::<Sequence<String>>(lock, "({ length: 1, 0: 'a' })") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Expected a Sequence but got a non-iterable object"
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn sequence_propagates_iterator_exceptions() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let code = "(function* () { yield 'ok'; throw new RangeError('iteration failed'); })()";
+        match ctx.eval::<Sequence<String>>(lock, code) {
+            Err(EvalError::UncoercibleResult { message, .. }) => {
+                assert_eq!(message, "RangeError: iteration failed");
+            }
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+
+        match ctx.eval::<Sequence<NonCoercible<bool>>>(lock, "new Set([true, 'no'])") {
+            Err(EvalError::UncoercibleResult { message, .. }) => assert_eq!(
+                message,
+                "TypeError: Incorrect type for the element at index 1: Expected a boolean value \
+                 but got string"
+            ),
+            Err(e) => panic!("Unexpected error: {e:?}"),
+            Ok(_) => panic!("Expected an error"),
+        }
+        Ok(())
+    });
+}
+
+#[test]
+fn sequence_wraps_to_array() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let sequence = Sequence::new(vec![1.0, 2.0, 3.0]);
+        ctx.set_global("sequence", sequence.to_js(lock));
+        let result: String = ctx
+            .eval(lock, "Array.isArray(sequence) && sequence.join('-')")
+            .unwrap();
+        assert_eq!(result, "1-2-3");
+        Ok(())
+    });
+}
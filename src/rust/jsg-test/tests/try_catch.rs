@@ -0,0 +1,140 @@
+//! Tests for catching JavaScript exceptions with `Lock::try_catch`.
+
+use jsg::ExceptionType;
+use jsg::FromJS;
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg::V8Ref;
+use jsg::v8;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Reader {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Reader {
+    /// Reads `object.value`, returning the message of the exception thrown by its getter.
+    #[jsg_method]
+    pub fn describe(&self, lock: &mut jsg::Lock, object: V8Ref<v8::Object>) -> String {
+        let object = object.as_local(lock);
+        match lock.try_catch(|lock| object.get(lock, "value")) {
+            Ok(_) => "no exception".to_owned(),
+            Err(exception) => exception.message().to_owned(),
+        }
+    }
+
+    /// Reads `object.value`, rethrowing the exception thrown by its getter.
+    #[jsg_method]
+    pub fn rethrow(&self, lock: &mut jsg::Lock, object: V8Ref<v8::Object>) {
+        let object = object.as_local(lock);
+        if let Err(exception) = lock.try_catch(|lock| object.get(lock, "value")) {
+            exception.rethrow(lock);
+        }
+    }
+}
+
+fn set_reader(lock: &mut jsg::Lock, ctx: &crate::EvalContext) {
+    let reader = jsg::Ref::new(Reader {
+        _state: ResourceState::default(),
+    });
+    let reader = reader.to_js(lock);
+    ctx.set_global("reader", reader);
+}
+
+/// Evaluates `code` with a `Reader` installed as `reader`, expecting it to return `expected`.
+fn check_with_reader(code: &str, expected: &str) {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_reader(lock, ctx);
+
+        let result: String = ctx.eval(lock, code).unwrap();
+        assert_eq!(result, expected);
+        Ok(())
+    });
+}
+
+#[test]
+fn try_catch_returns_result_when_nothing_is_thrown() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let object: V8Ref<v8::Object> = ctx.eval(lock, "({ value: 42 })").unwrap();
+        let object = object.as_local(lock);
+
+        let value = lock
+            .try_catch(|lock| object.get(lock, "value"))
+            .unwrap()
+            .unwrap();
+        assert!((f64::from_js(lock, value)? - 42.0).abs() < f64::EPSILON);
+        Ok(())
+    });
+}
+
+#[test]
+fn try_catch_preserves_thrown_value_and_stack() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let object: V8Ref<v8::Object> = ctx
+            .eval(
+                lock,
+                "globalThis.thrown = new RangeError('too far'); \
+                 ({ get value() { return (function failingGetter() { throw thrown; })(); } })",
+            )
+            .unwrap();
+        let object = object.as_local(lock);
+
+        let exception = lock
+            .try_catch(|lock| object.get(lock, "value"))
+            .unwrap_err();
+        assert!(!exception.is_termination());
+        assert_eq!(exception.message(), "Uncaught RangeError: too far");
+        assert!(exception.stack().unwrap().contains("failingGetter"));
+
+        let error = exception.to_error(lock);
+        assert_eq!(error.name, ExceptionType::RangeError);
+        assert_eq!(error.message, "too far");
+
+        ctx.set_global("caught", exception.value(lock));
+        let same: bool = ctx.eval(lock, "caught === thrown").unwrap();
+        assert!(same);
+        Ok(())
+    });
+}
+
+#[test]
+fn try_catch_catches_non_error_values() {
+    check_with_reader(
+        "reader.describe({ get value() { throw 42; } })",
+        "Uncaught 42",
+    );
+}
+
+#[test]
+fn try_catch_swallows_caught_exceptions() {
+    check_with_reader(
+        "try { reader.describe({ get value() { throw new Error('boom'); } }) } \
+         catch { 'propagated' }",
+        "Uncaught Error: boom",
+    );
+}
+
+#[test]
+fn caught_exceptions_can_be_rethrown() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        set_reader(lock, ctx);
+
+        let result: bool = ctx
+            .eval(
+                lock,
+                "const thrown = { reason: 'custom' }; \
+                 try { reader.rethrow({ get value() { throw thrown; } }); false } \
+                 catch (e) { e === thrown }",
+            )
+            .unwrap();
+        assert!(result);
+        Ok(())
+    });
+}
@@ -0,0 +1,282 @@
+//! Tests for the `String`, `Symbol`, `Date` and `RegExp` handles and for casting a
+//! `Local<Value>` to a specific type.
+
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use jsg::ExceptionType;
+use jsg::ResourceState;
+use jsg::ToJS;
+use jsg::v8;
+use jsg::v8::ToLocalValue;
+use jsg::v8::WellKnownSymbol;
+use jsg_macros::jsg_method;
+use jsg_macros::jsg_resource;
+
+#[jsg_resource]
+struct Calendar {
+    _state: ResourceState,
+}
+
+#[jsg_resource]
+impl Calendar {
+    #[jsg_method]
+    pub fn next_day(&self, time: SystemTime) -> SystemTime {
+        time + Duration::from_hours(24)
+    }
+
+    #[jsg_method]
+    pub fn millis(&self, time: Option<SystemTime>) -> f64 {
+        time.map_or(-1.0, |time| {
+            let millis = time.duration_since(UNIX_EPOCH).unwrap().as_millis();
+            f64::from(u32::try_from(millis).unwrap())
+        })
+    }
+}
+
+/// Evaluates `code` with a `Calendar` installed as `calendar`, expecting it to return `expected`.
+fn check_with_calendar(code: &str, expected: &str) {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let calendar = jsg::Ref::new(Calendar {
+            _state: ResourceState::default(),
+        });
+        let calendar = calendar.to_js(lock);
+        ctx.set_global("calendar", calendar);
+
+        let result: String = ctx.eval(lock, code).unwrap();
+        assert_eq!(result, expected);
+        Ok(())
+    });
+}
+
+#[test]
+fn symbols_have_descriptions() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let symbol = v8::Local::<v8::Symbol>::new(lock, "token");
+        assert_eq!(symbol.description(lock).as_deref(), Some("token"));
+        let value: v8::Local<v8::Value> = symbol.into();
+        assert!(value.is_symbol());
+        assert_eq!(value.type_of(), "symbol");
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "Symbol()")
+            .unwrap()
+            .as_local(lock);
+        let symbol = value.try_cast::<v8::Symbol>().unwrap();
+        assert_eq!(symbol.description(lock), None);
+
+        let iterator = v8::Local::<v8::Symbol>::well_known(lock, WellKnownSymbol::Iterator);
+        assert_eq!(
+            iterator.description(lock).as_deref(),
+            Some("Symbol.iterator")
+        );
+        Ok(())
+    });
+}
+
+#[test]
+fn well_known_symbols_can_be_set_on_objects() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let object = ctx.eval::<jsg::Value>(lock, "({})").unwrap().as_local(lock);
+        let mut object = object.try_cast::<v8::Object>().unwrap();
+        let tag = v8::Local::<v8::Symbol>::well_known(lock, WellKnownSymbol::ToStringTag);
+        let value = "Calendar".to_local(lock);
+        object.set(lock, &tag, value);
+
+        let value = object.get(lock, &tag).unwrap();
+        assert_eq!(value.to_string(), "Calendar");
+        let missing = v8::Local::<v8::Symbol>::new(lock, "missing");
+        assert!(object.get(lock, &missing).unwrap().is_undefined());
+
+        ctx.set_global("object", object.into());
+        let result: String = ctx
+            .eval(lock, "Object.prototype.toString.call(object)")
+            .unwrap();
+        assert_eq!(result, "[object Calendar]");
+        Ok(())
+    });
+}
+
+#[test]
+fn dates_convert_to_and_from_system_time() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        let date = v8::Local::<v8::Date>::new(lock, time);
+        assert!((date.value_of() - 1_700_000_000_123.0).abs() < f64::EPSILON);
+        assert_eq!(date.to_system_time(), Some(time));
+
+        // Times before the epoch are supported, and sub-millisecond precision is truncated.
+        let before = UNIX_EPOCH - Duration::from_micros(1_500);
+        let date = v8::Local::<v8::Date>::new(lock, before);
+        assert!((date.value_of() + 1.0).abs() < f64::EPSILON);
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "new Date(NaN)")
+            .unwrap()
+            .as_local(lock);
+        let date = value.try_cast::<v8::Date>().unwrap();
+        assert!(date.value_of().is_nan());
+        assert_eq!(date.to_system_time(), None);
+
+        let time: SystemTime = ctx.eval(lock, "new Date(-86400000)").unwrap();
+        assert_eq!(time, UNIX_EPOCH - Duration::from_hours(24));
+        Ok(())
+    });
+}
+
+#[test]
+fn date_parameters_accept_dates_and_numbers() {
+    check_with_calendar(
+        "calendar.nextDay(new Date(0)).toISOString()",
+        "1970-01-02T00:00:00.000Z",
+    );
+    check_with_calendar("`${calendar.nextDay(0) instanceof Date}`", "true");
+    check_with_calendar(
+        "[calendar.millis(new Date(1234)), calendar.millis(5678), calendar.millis()].join()",
+        "1234,5678,-1",
+    );
+    check_with_calendar(
+        "[new Date(NaN), Infinity, 8.64e15 + 1, '0'].map((value) => { \
+           try { calendar.nextDay(value); } catch (e) { return `${e.name}: ${e.message}`; } \
+         }).join('\\n')",
+        "TypeError: The value cannot be converted because it is not a valid Date.\n\
+         TypeError: The value cannot be converted because it is not a valid Date.\n\
+         TypeError: The value cannot be converted because it is not a valid Date.\n\
+         TypeError: Expected a Date but got string",
+    );
+}
+
+#[test]
+fn reg_exps_can_be_created_and_inspected() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let regexp = v8::Local::<v8::RegExp>::new(lock, "^a+b?$", "ygi").unwrap();
+        assert_eq!(regexp.source(lock), "^a+b?$");
+        assert_eq!(regexp.flags(), "giy");
+
+        ctx.set_global("regexp", regexp.into());
+        let result: String = ctx
+            .eval(lock, "`${regexp.test('AAb')} ${regexp.sticky} ${regexp}`")
+            .unwrap();
+        assert_eq!(result, "true true /^a+b?$/giy");
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "/x/dsu")
+            .unwrap()
+            .as_local(lock);
+        let regexp = value.try_cast::<v8::RegExp>().unwrap();
+        assert_eq!(regexp.source(lock), "x");
+        assert_eq!(regexp.flags(), "dsu");
+        Ok(())
+    });
+}
+
+#[test]
+fn invalid_reg_exps_are_syntax_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, _ctx| {
+        let err = v8::Local::<v8::RegExp>::new(lock, "(", "").unwrap_err();
+        assert_eq!(err.name, ExceptionType::SyntaxError);
+
+        let err = v8::Local::<v8::RegExp>::new(lock, "a", "gg").unwrap_err();
+        assert_eq!(err.name, ExceptionType::SyntaxError);
+        assert_eq!(
+            err.message,
+            "Invalid flags supplied to RegExp constructor 'gg'"
+        );
+        let err = v8::Local::<v8::RegExp>::new(lock, "a", "q").unwrap_err();
+        assert_eq!(err.name, ExceptionType::SyntaxError);
+        Ok(())
+    });
+}
+
+#[test]
+fn try_cast_checks_the_value_type() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let value = ctx
+            .eval::<jsg::Value>(lock, "new Date(0)")
+            .unwrap()
+            .as_local(lock);
+        assert!(value.try_cast::<v8::Object>().is_some());
+        assert!(value.try_cast::<v8::Date>().is_some());
+        assert!(value.try_cast::<v8::RegExp>().is_none());
+        assert!(value.try_cast::<v8::Function>().is_none());
+
+        let value = "Symbol.iterator".to_local(lock);
+        assert!(value.try_cast::<v8::Symbol>().is_none());
+        assert!(value.try_cast::<v8::Object>().is_none());
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "() => {}")
+            .unwrap()
+            .as_local(lock);
+        assert!(value.try_cast::<v8::Function>().is_some());
+        assert!(value.try_cast::<v8::Object>().is_some());
+        assert!(value.try_cast::<v8::Array>().is_none());
+        Ok(())
+    });
+}
+
+#[test]
+fn checked_casts_return_type_errors() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let value = "text".to_local(lock);
+        let err = value.clone().try_into_object().unwrap_err();
+        assert_eq!(err.name, ExceptionType::TypeError);
+        assert_eq!(err.message, "Expected an object but got string");
+        let string = value.try_into_string().unwrap();
+        assert_eq!(string.value(lock), "text");
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "[1, 2]")
+            .unwrap()
+            .as_local(lock);
+        let err = value.clone().try_into_function().unwrap_err();
+        assert_eq!(err.message, "Expected a Function but got object");
+        assert!(value.clone().try_into_array().is_ok());
+        let object = value.try_into_object().unwrap();
+        assert!(object.has(lock, "length"));
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "(value) => value * 2")
+            .unwrap()
+            .as_local(lock);
+        let function = value.try_into_function().unwrap();
+        let receiver = v8::Local::<v8::Value>::undefined(lock);
+        let argument = 21.to_local(lock);
+        let result = function.call(lock, receiver, vec![argument]).unwrap();
+        assert_eq!(result.to_string(), "42");
+
+        let value = ctx
+            .eval::<jsg::Value>(lock, "Promise.resolve()")
+            .unwrap()
+            .as_local(lock);
+        assert!(value.try_into_promise().is_ok());
+        let value = v8::Local::<v8::Value>::null(lock);
+        assert!(value.try_into_promise().is_err());
+        Ok(())
+    });
+}
+
+#[test]
+fn strings_can_be_created() {
+    let harness = crate::Harness::new();
+    harness.run_in_context(|lock, ctx| {
+        let string = v8::Local::<v8::String>::new(lock, "héllo 👋");
+        assert_eq!(string.value(lock), "héllo 👋");
+        let value: v8::Local<v8::Value> = string.into();
+        assert!(value.is_string());
+
+        ctx.set_global("string", value);
+        let result: String = ctx.eval(lock, "`${string.length}`").unwrap();
+        assert_eq!(result, "8");
+        Ok(())
+    });
+}
@@ -0,0 +1,136 @@
+//! Binary data, analogous to `jsg::BufferSource` in C++ JSG.
+//!
+//! `BufferSource` accepts an `ArrayBuffer` or any `ArrayBufferView` (typed arrays and
+//! `DataView`) and exposes its bytes without copying. `Vec<u8>` parameters accept the same
+//! values but copy the bytes, and `Vec<u8>` return values are wrapped as a `Uint8Array`.
+
+use std::ops::Deref;
+
+use crate::Error;
+use crate::FromJS;
+use crate::Lock;
+use crate::MemoryTracker;
+use crate::ToJS;
+use crate::Type;
+use crate::v8;
+
+/// An `ArrayBuffer` or `ArrayBufferView` passed from JavaScript.
+///
+/// Holds a strong reference to the JavaScript object and borrows its backing store directly.
+/// The bytes must only be accessed while the isolate lock is held and no JavaScript runs in
+/// between: JavaScript can write to the buffer, or detach it, in which case the buffer source
+/// appears empty. Copy the bytes with `to_vec()` to keep them across an `.await`.
+///
+/// A `&[u8]` method parameter is unwrapped as a `BufferSource`, so the method borrows the bytes
+/// without copying.
+///
+/// # Example
+///
+/// ```ignore
+/// #[jsg_method]
+/// pub fn checksum(&self, data: &[u8]) -> u32 {
+///     data.iter().map(|&byte| u32::from(byte)).sum()
+/// }
+/// ```
+pub struct BufferSource {
+    handle: v8::Global<v8::Value>,
+    isolate: v8::IsolatePtr,
+}
+
+impl BufferSource {
+    /// Returns the bytes viewed by this buffer source without copying.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe {
+            v8::ffi::global_buffer_source_bytes(self.isolate.as_ffi(), self.handle.as_ffi_ref())
+        }
+    }
+
+    /// Returns the underlying `ArrayBuffer` or `ArrayBufferView`.
+    pub fn as_local<'s>(&self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        self.handle.as_local(lock)
+    }
+
+    /// Wraps `values` as a `Uint8Array`. Used for `Vec<u8>` return values.
+    pub(crate) fn wrap_bytes<'s>(values: &[u8], lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        v8::Local::<v8::Uint8Array>::new(lock, values).into()
+    }
+
+    /// Copies the bytes of a buffer source. Used for `Vec<u8>` parameters.
+    pub(crate) fn unwrap_bytes(
+        lock: &mut Lock,
+        value: v8::Local<v8::Value>,
+    ) -> Result<Vec<u8>, Error> {
+        Ok(Self::from_js(lock, value)?.to_vec())
+    }
+}
+
+impl Deref for BufferSource {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for BufferSource {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Type for BufferSource {
+    fn class_name() -> &'static str {
+        "BufferSource"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_array_buffer() || value.is_array_buffer_view()
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        tracker.track_global("buffer", &self.handle);
+    }
+}
+
+impl ToJS for BufferSource {
+    /// Returns the original `ArrayBuffer` or `ArrayBufferView`.
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        self.as_local(lock)
+    }
+}
+
+impl FromJS for BufferSource {
+    type ResultType = Self;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        if !Self::is_exact(&value) {
+            let msg = format!(
+                "Expected an ArrayBuffer or ArrayBufferView but got {}",
+                value.type_of()
+            );
+            return Err(Error::new_type_error(msg));
+        }
+        Ok(Self {
+            handle: value.into(),
+            isolate: lock.isolate(),
+        })
+    }
+}
+
+impl Type for &[u8] {
+    fn class_name() -> &'static str {
+        BufferSource::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        BufferSource::is_exact(value)
+    }
+}
+
+impl FromJS for &[u8] {
+    type ResultType = BufferSource;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        BufferSource::from_js(lock, value)
+    }
+}
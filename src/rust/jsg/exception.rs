@@ -0,0 +1,137 @@
+//! Catching JavaScript exceptions in Rust, analogous to `jsg::Lock::tryCatch()` in C++ JSG.
+//!
+//! Calls into V8 that run JavaScript, such as property getters, leave a thrown exception pending
+//! on the isolate until control returns to JavaScript. `Lock::try_catch` runs a callback under a
+//! `v8::TryCatch` and returns the exception it caught as a `jsg::Exception`, which keeps the
+//! thrown value so that it can be inspected or rethrown as is.
+
+use std::fmt;
+
+use crate::Error;
+use crate::FromJS;
+use crate::Lock;
+use crate::v8;
+
+/// A JavaScript exception caught by `Lock::try_catch`.
+///
+/// Unlike `jsg::Error`, which only keeps the name and message of an error, an `Exception` holds
+/// on to the thrown value itself, whatever its type, along with its stack trace.
+pub struct Exception {
+    /// The thrown value, or `None` if execution was terminated.
+    value: Option<v8::Global<v8::Value>>,
+    stack: Option<String>,
+    message: String,
+}
+
+impl Exception {
+    /// Returns the thrown value, or `undefined` if execution was terminated.
+    pub fn value<'s>(&self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        match &self.value {
+            Some(value) => value.as_local(lock),
+            None => v8::Local::<v8::Value>::undefined(lock),
+        }
+    }
+
+    /// Returns the stack trace of the exception, if the thrown value has one, e.g. an `Error`.
+    pub fn stack(&self) -> Option<&str> {
+        self.stack.as_deref()
+    }
+
+    /// Returns the message V8 reports for the exception, e.g. `Uncaught TypeError: invalid`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns true if the exception was caused by terminating execution, e.g. because the
+    /// isolate ran out of time. Termination cannot be caught, so it keeps propagating regardless.
+    pub fn is_termination(&self) -> bool {
+        self.value.is_none()
+    }
+
+    /// Converts the thrown value to a `jsg::Error`, see `Error::from_js()`.
+    pub fn to_error(&self, lock: &mut Lock) -> Error {
+        if self.is_termination() {
+            return Error::new_error("JavaScript execution was terminated");
+        }
+        let value = self.value(lock);
+        Error::from_js(lock, value).unwrap_or_else(|err| err)
+    }
+
+    /// Throws the original value again, e.g. after inspecting it. Has no effect if execution was
+    /// terminated, which propagates on its own.
+    pub fn rethrow(self, lock: &mut Lock) {
+        if let Some(value) = self.value {
+            let value = value.as_local(lock);
+            unsafe { v8::ffi::isolate_throw_exception(lock.isolate().as_ffi(), value.into_ffi()) };
+        }
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.stack.as_deref().unwrap_or(&self.message))
+    }
+}
+
+impl fmt::Debug for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Exception")
+            .field("stack", &self.stack)
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Lock<'_> {
+    /// Runs `callback`, catching any JavaScript exception thrown while it runs.
+    ///
+    /// Returns the callback's result, or the caught exception if one was thrown, in which case
+    /// the result is discarded. The exception is no longer pending once caught; use
+    /// `Exception::rethrow()` to propagate it to JavaScript.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let value = match lock.try_catch(|lock| parse_config(lock, &source)) {
+    ///     Ok(value) => value,
+    ///     Err(exception) => return Err(exception.to_error(lock)),
+    /// };
+    /// ```
+    pub fn try_catch<T, F>(&mut self, callback: F) -> Result<T, Exception>
+    where
+        F: FnOnce(&mut Self) -> T,
+    {
+        fn trampoline<'s, T, F>(isolate: *mut v8::ffi::Isolate, data: usize)
+        where
+            F: FnOnce(&mut Lock<'s>) -> T,
+        {
+            let (callback, result) = unsafe { &mut *(data as *mut (Option<F>, Option<T>)) };
+            if let Some(callback) = callback.take() {
+                let mut lock = unsafe { Lock::from_isolate_ptr(isolate) };
+                *result = Some(callback(&mut lock));
+            }
+        }
+
+        let mut state: (Option<F>, Option<T>) = (Some(callback), None);
+        let caught = unsafe {
+            v8::ffi::isolate_try_catch(
+                self.isolate().as_ffi(),
+                &raw mut state as usize,
+                trampoline::<T, F>,
+            )
+        };
+        if !caught.caught {
+            let Some(result) = state.1 else {
+                unreachable!("the callback runs unless it throws");
+            };
+            return Ok(result);
+        }
+
+        let value: Option<v8::ffi::Local> = caught.exception.into();
+        Err(Exception {
+            value: value.map(|value| unsafe { v8::Local::from_ffi(self.isolate(), value) }.into()),
+            stack: (!caught.stack.is_empty()).then_some(caught.stack),
+            message: caught.message,
+        })
+    }
+}
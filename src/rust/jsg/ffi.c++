@@ -1,12 +1,19 @@
 #include "ffi.h"
 
+#include <workerd/jsg/dom-exception.h>
 #include <workerd/jsg/jsg.h>
 #include <workerd/jsg/util.h>
 #include <workerd/jsg/wrappable.h>
 #include <workerd/rust/jsg/ffi-inl.h>
+#include <workerd/rust/jsg/lib.rs.h>
 #include <workerd/rust/jsg/v8.rs.h>
 
+#include <cppgc/allocation.h>
+
 #include <kj/common.h>
+#include <kj/map.h>
+#include <kj/mutex.h>
+#include <kj/vector.h>
 
 using namespace kj_rs;
 
@@ -43,6 +50,16 @@ Local local_new_boolean(Isolate* isolate, bool value) {
   return to_ffi(kj::mv(val));
 }
 
+Local local_new_big_int(Isolate* isolate, int64_t value) {
+  v8::Local<v8::BigInt> val = v8::BigInt::New(isolate, value);
+  return to_ffi(kj::mv(val));
+}
+
+Local local_new_big_int_from_unsigned(Isolate* isolate, uint64_t value) {
+  v8::Local<v8::BigInt> val = v8::BigInt::NewFromUnsigned(isolate, value);
+  return to_ffi(kj::mv(val));
+}
+
 Local local_new_object(Isolate* isolate) {
   v8::Local<v8::Object> object = v8::Object::New(isolate);
   return to_ffi(kj::mv(object));
@@ -78,6 +95,10 @@ bool local_is_number(const Local& val) {
   return local_as_ref_from_ffi<v8::Value>(val)->IsNumber();
 }
 
+bool local_is_big_int(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsBigInt();
+}
+
 bool local_is_null(const Local& val) {
   return local_as_ref_from_ffi<v8::Value>(val)->IsNull();
 }
@@ -98,6 +119,42 @@ bool local_is_native_error(const Local& val) {
   return local_as_ref_from_ffi<v8::Value>(val)->IsNativeError();
 }
 
+bool local_is_promise(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsPromise();
+}
+
+bool local_is_function(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsFunction();
+}
+
+bool local_is_array(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsArray();
+}
+
+bool local_is_array_buffer(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsArrayBuffer();
+}
+
+bool local_is_array_buffer_view(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsArrayBufferView();
+}
+
+bool local_is_uint8_array(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsUint8Array();
+}
+
+bool local_is_symbol(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsSymbol();
+}
+
+bool local_is_date(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsDate();
+}
+
+bool local_is_reg_exp(const Local& val) {
+  return local_as_ref_from_ffi<v8::Value>(val)->IsRegExp();
+}
+
 ::rust::String local_type_of(Isolate* isolate, const Local& val) {
   auto v8Val = local_as_ref_from_ffi<v8::Value>(val);
   v8::Local<v8::String> typeStr = v8Val->TypeOf(isolate);
@@ -106,41 +163,195 @@ bool local_is_native_error(const Local& val) {
 }
 
 // Local<Object>
-void local_object_set_property(Isolate* isolate, Local& object, ::rust::Str key, Local value) {
+Local local_new_internalized_string(Isolate* isolate, ::rust::Str value) {
+  return to_ffi(::workerd::jsg::check(v8::String::NewFromUtf8(
+      isolate, value.cbegin(), v8::NewStringType::kInternalized, value.size())));
+}
+
+void local_object_set(Isolate* isolate, const Local& object, Local key, Local value) {
   auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
   auto context = isolate->GetCurrentContext();
-  auto v8_key = ::workerd::jsg::check(
-      v8::String::NewFromUtf8(isolate, key.cbegin(), v8::NewStringType::kInternalized, key.size()));
-  ::workerd::jsg::check(v8_obj->Set(context, v8_key, local_from_ffi<v8::Value>(kj::mv(value))));
+  ::workerd::jsg::check(v8_obj->Set(
+      context, local_from_ffi<v8::Value>(kj::mv(key)), local_from_ffi<v8::Value>(kj::mv(value))));
 }
 
-bool local_object_has_property(Isolate* isolate, const Local& object, ::rust::Str key) {
+bool local_object_has(Isolate* isolate, const Local& object, Local key) {
   auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
   auto context = isolate->GetCurrentContext();
-  auto v8_key = ::workerd::jsg::check(
-      v8::String::NewFromUtf8(isolate, key.cbegin(), v8::NewStringType::kInternalized, key.size()));
-  return v8_obj->Has(context, v8_key).FromJust();
+  return ::workerd::jsg::check(v8_obj->Has(context, local_from_ffi<v8::Value>(kj::mv(key))));
 }
 
-kj::Maybe<Local> local_object_get_property(Isolate* isolate, const Local& object, ::rust::Str key) {
+kj::Maybe<Local> local_object_get(Isolate* isolate, const Local& object, Local key) {
   auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
   auto context = isolate->GetCurrentContext();
-  auto v8_key = ::workerd::jsg::check(
-      v8::String::NewFromUtf8(isolate, key.cbegin(), v8::NewStringType::kInternalized, key.size()));
+  // Leaves the exception pending if a getter or Proxy trap threw.
   v8::Local<v8::Value> result;
-  if (!v8_obj->Get(context, v8_key).ToLocal(&result)) {
+  if (!v8_obj->Get(context, local_from_ffi<v8::Value>(kj::mv(key))).ToLocal(&result)) {
     return kj::none;
   }
   return to_ffi(kj::mv(result));
 }
 
+bool local_object_delete(Isolate* isolate, const Local& object, Local key) {
+  auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
+  auto context = isolate->GetCurrentContext();
+  return ::workerd::jsg::check(v8_obj->Delete(context, local_from_ffi<v8::Value>(kj::mv(key))));
+}
+
+void local_object_define_property(Isolate* isolate,
+    const Local& object,
+    Local key,
+    Local value,
+    PropertyAttributes attributes) {
+  auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
+  auto context = isolate->GetCurrentContext();
+  auto v8_key = local_from_ffi<v8::Value>(kj::mv(key));
+  // DefineOwnProperty takes a name, so integer indices are converted to strings.
+  v8::Local<v8::Name> name = v8_key->IsName()
+      ? v8_key.As<v8::Name>()
+      : ::workerd::jsg::check(v8_key->ToString(context)).As<v8::Name>();
+  int v8_attributes = v8::None;
+  if (!attributes.writable) {
+    v8_attributes |= v8::ReadOnly;
+  }
+  if (!attributes.enumerable) {
+    v8_attributes |= v8::DontEnum;
+  }
+  if (!attributes.configurable) {
+    v8_attributes |= v8::DontDelete;
+  }
+  ::workerd::jsg::check(v8_obj->DefineOwnProperty(context, name,
+      local_from_ffi<v8::Value>(kj::mv(value)), static_cast<v8::PropertyAttribute>(v8_attributes)));
+}
+
+::rust::Vec<::rust::String> local_object_get_own_property_names(
+    Isolate* isolate, const Local& object) {
+  auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
+  auto context = isolate->GetCurrentContext();
+  auto names = ::workerd::jsg::check(v8_obj->GetOwnPropertyNames(context,
+      static_cast<v8::PropertyFilter>(v8::ONLY_ENUMERABLE | v8::SKIP_SYMBOLS),
+      v8::KeyConversionMode::kConvertToString));
+  ::rust::Vec<::rust::String> result;
+  result.reserve(names->Length());
+  for (uint32_t i = 0; i < names->Length(); i++) {
+    result.push_back(unwrap_string(isolate, to_ffi(::workerd::jsg::check(names->Get(context, i)))));
+  }
+  return result;
+}
+
+::rust::Vec<Local> local_object_get_own_property_keys(Isolate* isolate, const Local& object) {
+  auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
+  auto context = isolate->GetCurrentContext();
+  auto keys = ::workerd::jsg::check(v8_obj->GetOwnPropertyNames(
+      context, v8::ALL_PROPERTIES, v8::KeyConversionMode::kConvertToString));
+  ::rust::Vec<Local> result;
+  result.reserve(keys->Length());
+  for (uint32_t i = 0; i < keys->Length(); i++) {
+    result.push_back(to_ffi(::workerd::jsg::check(keys->Get(context, i))));
+  }
+  return result;
+}
+
+Local local_object_get_prototype(Isolate* isolate, const Local& object) {
+  return to_ffi(local_as_ref_from_ffi<v8::Object>(object)->GetPrototypeV2());
+}
+
+bool local_object_set_prototype(Isolate* isolate, const Local& object, Local prototype) {
+  auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
+  auto context = isolate->GetCurrentContext();
+  // Leaves the exception pending if the prototype chain would contain a cycle.
+  return v8_obj->SetPrototypeV2(context, local_from_ffi<v8::Value>(kj::mv(prototype)))
+      .FromMaybe(false);
+}
+
+// Local<Array>
+Local local_new_array(Isolate* isolate, ::rust::Vec<Local> values) {
+  auto elements = kj::heapArrayBuilder<v8::Local<v8::Value>>(values.size());
+  for (auto& value: values) {
+    elements.add(local_from_ffi<v8::Value>(kj::mv(value)));
+  }
+  return to_ffi(v8::Array::New(isolate, elements.begin(), elements.size()));
+}
+
+kj::Maybe<Local> local_iterate(Isolate* isolate, const Local& value, ::rust::Vec<Local>& values) {
+  auto context = isolate->GetCurrentContext();
+  auto handle = local_as_ref_from_ffi<v8::Value>(value);
+  v8::TryCatch tryCatch(isolate);
+  auto typeError = [&](kj::StringPtr message) -> kj::Maybe<Local> {
+    return to_ffi(v8::Exception::TypeError(::workerd::jsg::v8Str(isolate, message)));
+  };
+
+  // Arrays are read by index, matching the fast path of C++ JSG's sequence unwrapping.
+  if (handle->IsArray()) {
+    auto array = handle.As<v8::Array>();
+    for (uint32_t i = 0; i < array->Length(); i++) {
+      v8::Local<v8::Value> element;
+      if (!array->Get(context, i).ToLocal(&element)) {
+        return to_ffi(tryCatch.Exception());
+      }
+      values.push_back(to_ffi(kj::mv(element)));
+    }
+    return kj::none;
+  }
+
+  if (!handle->IsObject()) {
+    return typeError("Expected a Sequence but got a non-object value");
+  }
+  auto object = handle.As<v8::Object>();
+  v8::Local<v8::Value> method;
+  if (!object->Get(context, v8::Symbol::GetIterator(isolate)).ToLocal(&method)) {
+    return to_ffi(tryCatch.Exception());
+  }
+  if (!method->IsFunction()) {
+    return typeError("Expected a Sequence but got a non-iterable object");
+  }
+  v8::Local<v8::Value> iterator;
+  if (!method.As<v8::Function>()->Call(context, object, 0, nullptr).ToLocal(&iterator)) {
+    return to_ffi(tryCatch.Exception());
+  }
+  if (!iterator->IsObject()) {
+    return typeError("Result of the Symbol.iterator method is not an object");
+  }
+  v8::Local<v8::Value> next;
+  if (!iterator.As<v8::Object>()
+           ->Get(context, ::workerd::jsg::v8StrIntern(isolate, "next"))
+           .ToLocal(&next)) {
+    return to_ffi(tryCatch.Exception());
+  }
+  if (!next->IsFunction()) {
+    return typeError("Iterator does not have a callable next method");
+  }
+
+  auto doneKey = ::workerd::jsg::v8StrIntern(isolate, "done");
+  auto valueKey = ::workerd::jsg::v8StrIntern(isolate, "value");
+  for (;;) {
+    v8::Local<v8::Value> result;
+    if (!next.As<v8::Function>()->Call(context, iterator, 0, nullptr).ToLocal(&result)) {
+      return to_ffi(tryCatch.Exception());
+    }
+    if (!result->IsObject()) {
+      return typeError("Iterator result is not an object");
+    }
+    auto resultObject = result.As<v8::Object>();
+    v8::Local<v8::Value> done;
+    if (!resultObject->Get(context, doneKey).ToLocal(&done)) {
+      return to_ffi(tryCatch.Exception());
+    }
+    if (done->BooleanValue(isolate)) {
+      return kj::none;
+    }
+    v8::Local<v8::Value> element;
+    if (!resultObject->Get(context, valueKey).ToLocal(&element)) {
+      return to_ffi(tryCatch.Exception());
+    }
+    values.push_back(to_ffi(kj::mv(element)));
+  }
+}
+
 // Wrappers
-Local wrap_resource(Isolate* isolate, size_t resource, const Global& tmpl, size_t drop_callback) {
+namespace {
+void set_resource_internal_fields(v8::Local<v8::Object> object, size_t resource) {
   auto self = reinterpret_cast<void*>(resource);
-  auto& global_tmpl = global_as_ref_from_ffi<v8::FunctionTemplate>(tmpl);
-  auto local_tmpl = v8::Local<v8::FunctionTemplate>::New(isolate, global_tmpl);
-  v8::Local<v8::Object> object = ::workerd::jsg::check(
-      local_tmpl->InstanceTemplate()->NewInstance(isolate->GetCurrentContext()));
   auto tagAddress = const_cast<uint16_t*>(&::workerd::jsg::Wrappable::WORKERD_RUST_WRAPPABLE_TAG);
   object->SetAlignedPointerInInternalField(::workerd::jsg::Wrappable::WRAPPABLE_TAG_FIELD_INDEX,
       tagAddress,
@@ -148,9 +359,24 @@ Local wrap_resource(Isolate* isolate, size_t resource, const Global& tmpl, size_
   object->SetAlignedPointerInInternalField(::workerd::jsg::Wrappable::WRAPPED_OBJECT_FIELD_INDEX,
       self,
       static_cast<v8::EmbedderDataTypeTag>(::workerd::jsg::Wrappable::WRAPPED_OBJECT_FIELD_INDEX));
+}
+}  // namespace
+
+Local wrap_resource(Isolate* isolate, size_t resource, const Global& tmpl, size_t drop_callback) {
+  auto& global_tmpl = global_as_ref_from_ffi<v8::FunctionTemplate>(tmpl);
+  auto local_tmpl = v8::Local<v8::FunctionTemplate>::New(isolate, global_tmpl);
+  v8::Local<v8::Object> object = ::workerd::jsg::check(
+      local_tmpl->InstanceTemplate()->NewInstance(isolate->GetCurrentContext()));
+  set_resource_internal_fields(object, resource);
   return to_ffi(kj::mv(object));
 }
 
+void attach_resource(Isolate* isolate, size_t resource, const Local& object) {
+  auto v8_obj = local_as_ref_from_ffi<v8::Object>(object);
+  KJ_ASSERT(v8_obj->InternalFieldCount() == ::workerd::jsg::Wrappable::INTERNAL_FIELD_COUNT);
+  set_resource_internal_fields(v8_obj, resource);
+}
+
 // Unwrappers
 ::rust::String unwrap_string(Isolate* isolate, Local value) {
   v8::Local<v8::String> v8Str = ::workerd::jsg::check(
@@ -172,6 +398,27 @@ double unwrap_number(Isolate* isolate, Local value) {
       ->Value();
 }
 
+int64_t unwrap_big_int_i64(const Local& value, bool& lossless) {
+  return local_as_ref_from_ffi<v8::BigInt>(value)->Int64Value(&lossless);
+}
+
+uint64_t unwrap_big_int_u64(const Local& value, bool& lossless) {
+  return local_as_ref_from_ffi<v8::BigInt>(value)->Uint64Value(&lossless);
+}
+
+size_t unwrap_big_int_usize(const Local& value, bool& lossless) {
+  static_assert(sizeof(size_t) == sizeof(uint64_t));
+  return unwrap_big_int_u64(value, lossless);
+}
+
+bool unwrap_big_int_is_negative(const Local& value) {
+  // With a word count of zero, ToWordsArray only reports the sign and the required word count.
+  int signBit = 0;
+  int wordCount = 0;
+  local_as_ref_from_ffi<v8::BigInt>(value)->ToWordsArray(&signBit, &wordCount, nullptr);
+  return signBit != 0;
+}
+
 size_t unwrap_resource(Isolate* isolate, Local value) {
   auto v8_obj = local_from_ffi<v8::Object>(kj::mv(value));
   KJ_ASSERT(v8_obj->GetAlignedPointerFromInternalField(
@@ -184,6 +431,157 @@ size_t unwrap_resource(Isolate* isolate, Local value) {
       static_cast<v8::EmbedderDataTypeTag>(::workerd::jsg::Wrappable::WRAPPED_OBJECT_FIELD_INDEX)));
 }
 
+// Local<Function>
+FunctionCallResult local_function_call(
+    Isolate* isolate, const Local& function, Local receiver, ::rust::Vec<Local> args) {
+  auto context = isolate->GetCurrentContext();
+  auto argv = kj::heapArrayBuilder<v8::Local<v8::Value>>(args.size());
+  for (auto& arg: args) {
+    argv.add(local_from_ffi<v8::Value>(kj::mv(arg)));
+  }
+
+  v8::TryCatch tryCatch(isolate);
+  FunctionCallResult result;
+  v8::Local<v8::Value> value;
+  auto recv = local_from_ffi<v8::Value>(kj::mv(receiver));
+  if (local_as_ref_from_ffi<v8::Function>(function)
+          ->Call(context, recv, static_cast<int>(argv.size()), argv.begin())
+          .ToLocal(&value)) {
+    result.value = to_ffi(kj::mv(value));
+  } else if (tryCatch.HasTerminated()) {
+    // Termination cannot be caught, let it propagate past the caller.
+    tryCatch.ReThrow();
+  } else if (tryCatch.HasCaught()) {
+    result.exception = to_ffi(tryCatch.Exception());
+  }
+  return result;
+}
+
+// Local<Uint8Array>
+
+Local local_new_uint8_array(Isolate* isolate, ::rust::Slice<const uint8_t> data) {
+  auto buffer = v8::ArrayBuffer::New(isolate, data.size());
+  if (data.size() > 0) {
+    memcpy(buffer->Data(), data.data(), data.size());
+  }
+  return to_ffi(v8::Uint8Array::New(buffer, 0, data.size()));
+}
+
+// Local<Symbol>
+Local local_new_symbol(Isolate* isolate, ::rust::Str description) {
+  auto v8_description = ::workerd::jsg::check(v8::String::NewFromUtf8(
+      isolate, description.cbegin(), v8::NewStringType::kNormal, description.size()));
+  return to_ffi(v8::Symbol::New(isolate, v8_description));
+}
+
+Local local_symbol_well_known(Isolate* isolate, WellKnownSymbol symbol) {
+  switch (symbol) {
+    case WellKnownSymbol::AsyncIterator:
+      return to_ffi(v8::Symbol::GetAsyncIterator(isolate));
+    case WellKnownSymbol::HasInstance:
+      return to_ffi(v8::Symbol::GetHasInstance(isolate));
+    case WellKnownSymbol::IsConcatSpreadable:
+      return to_ffi(v8::Symbol::GetIsConcatSpreadable(isolate));
+    case WellKnownSymbol::Iterator:
+      return to_ffi(v8::Symbol::GetIterator(isolate));
+    case WellKnownSymbol::Match:
+      return to_ffi(v8::Symbol::GetMatch(isolate));
+    case WellKnownSymbol::Replace:
+      return to_ffi(v8::Symbol::GetReplace(isolate));
+    case WellKnownSymbol::Search:
+      return to_ffi(v8::Symbol::GetSearch(isolate));
+    case WellKnownSymbol::Split:
+      return to_ffi(v8::Symbol::GetSplit(isolate));
+    case WellKnownSymbol::ToPrimitive:
+      return to_ffi(v8::Symbol::GetToPrimitive(isolate));
+    case WellKnownSymbol::ToStringTag:
+      return to_ffi(v8::Symbol::GetToStringTag(isolate));
+    case WellKnownSymbol::Unscopables:
+      return to_ffi(v8::Symbol::GetUnscopables(isolate));
+  }
+  KJ_UNREACHABLE;
+}
+
+Local local_symbol_description(Isolate* isolate, const Local& symbol) {
+  return to_ffi(local_as_ref_from_ffi<v8::Symbol>(symbol)->Description(isolate));
+}
+
+// Local<Date>
+Local local_new_date(Isolate* isolate, double time) {
+  return to_ffi(::workerd::jsg::check(v8::Date::New(isolate->GetCurrentContext(), time)));
+}
+
+double local_date_value_of(const Local& date) {
+  return local_as_ref_from_ffi<v8::Date>(date)->ValueOf();
+}
+
+// Local<RegExp>
+namespace {
+struct RegExpFlag {
+  char name;
+  v8::RegExp::Flags value;
+};
+
+// The flags of a RegExp in the order `RegExp.prototype.flags` lists them.
+constexpr RegExpFlag REG_EXP_FLAGS[] = {
+  {'d', v8::RegExp::kHasIndices},
+  {'g', v8::RegExp::kGlobal},
+  {'i', v8::RegExp::kIgnoreCase},
+  {'m', v8::RegExp::kMultiline},
+  {'s', v8::RegExp::kDotAll},
+  {'u', v8::RegExp::kUnicode},
+  {'v', v8::RegExp::kUnicodeSets},
+  {'y', v8::RegExp::kSticky},
+};
+}  // namespace
+
+Local local_new_reg_exp(Isolate* isolate, ::rust::Str pattern, ::rust::Str flags) {
+  // Parse the flags like the RegExp constructor does, which rejects unknown and repeated flags.
+  int v8_flags = v8::RegExp::kNone;
+  for (char c: flags) {
+    bool valid = false;
+    for (auto flag: REG_EXP_FLAGS) {
+      if (c == flag.name && (v8_flags & flag.value) == 0) {
+        v8_flags |= flag.value;
+        valid = true;
+        break;
+      }
+    }
+    if (!valid) {
+      isolate->ThrowException(v8::Exception::SyntaxError(::workerd::jsg::v8Str(isolate,
+          kj::str("Invalid flags supplied to RegExp constructor '",
+              kj::heapString(flags.data(), flags.size()), "'"))));
+      return to_ffi(v8::Local<v8::Value>());
+    }
+  }
+
+  auto context = isolate->GetCurrentContext();
+  auto v8_pattern = ::workerd::jsg::check(
+      v8::String::NewFromUtf8(isolate, pattern.cbegin(), v8::NewStringType::kNormal, pattern.size()));
+  v8::Local<v8::RegExp> result;
+  if (!v8::RegExp::New(context, v8_pattern, static_cast<v8::RegExp::Flags>(v8_flags))
+           .ToLocal(&result)) {
+    // The SyntaxError for an invalid pattern is left pending for the caller's TryCatch.
+    return to_ffi(v8::Local<v8::Value>());
+  }
+  return to_ffi(kj::mv(result));
+}
+
+::rust::String local_reg_exp_source(Isolate* isolate, const Local& regexp) {
+  return unwrap_string(isolate, to_ffi(local_as_ref_from_ffi<v8::RegExp>(regexp)->GetSource()));
+}
+
+::rust::String local_reg_exp_flags(const Local& regexp) {
+  auto v8_flags = local_as_ref_from_ffi<v8::RegExp>(regexp)->GetFlags();
+  kj::Vector<char> result;
+  for (auto flag: REG_EXP_FLAGS) {
+    if ((v8_flags & flag.value) != 0) {
+      result.add(flag.name);
+    }
+  }
+  return ::rust::String(result.begin(), result.size());
+}
+
 // Global<T>
 
 void global_drop(Global value) {
@@ -191,7 +589,8 @@ void global_drop(Global value) {
 }
 
 Global global_clone(const Global& value) {
-  return Global{.ptr = value.ptr};
+  auto& glbl = global_as_ref_from_ffi<v8::Value>(value);
+  return to_ffi(v8::Global<v8::Value>(v8::Isolate::GetCurrent(), glbl));
 }
 
 Local global_to_local(Isolate* isolate, const Global& value) {
@@ -200,15 +599,55 @@ Local global_to_local(Isolate* isolate, const Global& value) {
   return to_ffi(kj::mv(local));
 }
 
+::rust::Slice<const uint8_t> global_buffer_source_bytes(Isolate* isolate, const Global& value) {
+  auto handle = global_as_ref_from_ffi<v8::Value>(value).Get(isolate);
+  if (handle->IsArrayBuffer()) {
+    auto buffer = handle.As<v8::ArrayBuffer>();
+    return {static_cast<const uint8_t*>(buffer->Data()), buffer->ByteLength()};
+  }
+  // A detached buffer reports a null data pointer and a zero length.
+  auto view = handle.As<v8::ArrayBufferView>();
+  auto data = static_cast<const uint8_t*>(view->Buffer()->Data());
+  if (data == nullptr) {
+    return {};
+  }
+  return {data + view->ByteOffset(), view->ByteLength()};
+}
+
+namespace {
+// The parameter of a handle made weak by `global_make_weak()`.
+struct WeakCallbackParameter {
+  size_t data;
+  WeakCallback callback;
+};
+
+// Whether a weak callback is running. Like `HeapTracer::isInCppgcDestructor()` in C++ JSG, traced
+// references destroyed during GC may point to slots that were already collected, so they must
+// not be reset.
+thread_local bool inWeakCallback = false;
+}  // namespace
+
 void global_make_weak(Isolate* isolate, Global* value, size_t data, WeakCallback callback) {
-  // callback is unused; GC-based cleanup not yet implemented.
-  // Cleanup happens in Realm::drop() during context disposal.
   auto glbl = global_as_ref_from_ffi<v8::Object>(*value);
-  glbl->SetWeak(reinterpret_cast<void*>(data), [](const v8::WeakCallbackInfo<void>& info) {
-    KJ_UNIMPLEMENTED("global_make_weak");
+  glbl->SetWeak(new WeakCallbackParameter{.data = data, .callback = callback},
+      [](const v8::WeakCallbackInfo<WeakCallbackParameter>& info) {
+    auto parameter = info.GetParameter();
+    auto data = parameter->data;
+    auto callback = parameter->callback;
+    delete parameter;
+    KJ_ASSERT(!inWeakCallback);
+    inWeakCallback = true;
+    KJ_DEFER(inWeakCallback = false);
+    // As this is a first-pass callback, `callback` must reset the handle.
+    callback(info.GetIsolate(), data);
   }, v8::WeakCallbackType::kParameter);
 }
 
+void global_clear_weak(Global* value) {
+  auto glbl = global_as_ref_from_ffi<v8::Object>(*value);
+  delete glbl->ClearWeak<WeakCallbackParameter>();
+}
+
 // FunctionCallbackInfo
 Isolate* fci_get_isolate(FunctionCallbackInfo* args) {
   return args->GetIsolate();
@@ -230,6 +669,100 @@ void fci_set_return_value(FunctionCallbackInfo* args, Local value) {
   args->GetReturnValue().Set(local_from_ffi<v8::Value>(kj::mv(value)));
 }
 
+bool fci_is_construct_call(FunctionCallbackInfo* args) {
+  return !args->NewTarget()->IsUndefined();
+}
+
+// Local<FunctionTemplate>
+Local function_template_get_function(Isolate* isolate, const Local& tmpl) {
+  auto v8_tmpl = local_as_ref_from_ffi<v8::FunctionTemplate>(tmpl);
+  v8::Local<v8::Function> function =
+      ::workerd::jsg::check(v8_tmpl->GetFunction(isolate->GetCurrentContext()));
+  return to_ffi(kj::mv(function));
+}
+
+bool function_template_has_instance(const Local& tmpl, const Local& value) {
+  auto v8_tmpl = local_as_ref_from_ffi<v8::FunctionTemplate>(tmpl);
+  return v8_tmpl->HasInstance(local_as_ref_from_ffi<v8::Value>(value));
+}
+
+// Local<PromiseResolver>
+Local promise_resolver_new(Isolate* isolate) {
+  v8::Local<v8::Promise::Resolver> resolver =
+      ::workerd::jsg::check(v8::Promise::Resolver::New(isolate->GetCurrentContext()));
+  return to_ffi(kj::mv(resolver));
+}
+
+Local promise_resolver_get_promise(Isolate* isolate, const Local& resolver) {
+  v8::Local<v8::Promise> promise =
+      local_as_ref_from_ffi<v8::Promise::Resolver>(resolver)->GetPromise();
+  return to_ffi(kj::mv(promise));
+}
+
+void promise_resolver_resolve(Isolate* isolate, const Local& resolver, Local value) {
+  ::workerd::jsg::check(local_as_ref_from_ffi<v8::Promise::Resolver>(resolver)->Resolve(
+      isolate->GetCurrentContext(), local_from_ffi<v8::Value>(kj::mv(value))));
+}
+
+void promise_resolver_reject(Isolate* isolate, const Local& resolver, Local value) {
+  ::workerd::jsg::check(local_as_ref_from_ffi<v8::Promise::Resolver>(resolver)->Reject(
+      isolate->GetCurrentContext(), local_from_ffi<v8::Value>(kj::mv(value))));
+}
+
+// Local<Promise>
+namespace {
+// Owns a Rust promise reaction until it runs. V8 settles a promise at most once, so exactly one of
+// the two reaction functions consumes it. If neither ever runs, the reaction is dropped once both
+// functions have been garbage collected, or by the Realm when its context is disposed first.
+struct PromiseReaction {
+  size_t data;
+  PromiseReactionCallback callback;
+  PromiseReactionDrop dropData;
+  v8::Global<v8::External> handle;
+
+  ~PromiseReaction() {
+    if (data != 0) {
+      dropData(data);
+    }
+  }
+};
+
+template <bool rejected>
+void runPromiseReaction(const v8::FunctionCallbackInfo<v8::Value>& args) {
+  auto* reaction = static_cast<PromiseReaction*>(args.Data().As<v8::External>()->Value());
+  auto data = reaction->data;
+  KJ_ASSERT(data != 0, "promise reaction ran twice");
+  reaction->data = 0;
+  reaction->callback(args.GetIsolate(), data, to_ffi(args[0]), rejected,
+      const_cast<FunctionCallbackInfo*>(&args));
+}
+}  // namespace
+
+Local promise_then(Isolate* isolate,
+    const Local& promise,
+    size_t data,
+    PromiseReactionCallback callback,
+    PromiseReactionDrop drop_data) {
+  auto context = isolate->GetCurrentContext();
+  auto* reaction = new PromiseReaction{data, callback, drop_data, {}};
+  auto external = v8::External::New(isolate, reaction);
+  reaction->handle.Reset(isolate, external);
+  reaction->handle.SetWeak(reaction, [](const v8::WeakCallbackInfo<PromiseReaction>& info) {
+    // The reaction may hold V8 handles, so release it in the second pass.
+    info.GetParameter()->handle.Reset();
+    info.SetSecondPassCallback(
+        [](const v8::WeakCallbackInfo<PromiseReaction>& info) { delete info.GetParameter(); });
+  }, v8::WeakCallbackType::kParameter);
+
+  auto onFulfilled = ::workerd::jsg::check(v8::Function::New(
+      context, &runPromiseReaction<false>, external, 1, v8::ConstructorBehavior::kThrow));
+  auto onRejected = ::workerd::jsg::check(v8::Function::New(
+      context, &runPromiseReaction<true>, external, 1, v8::ConstructorBehavior::kThrow));
+  v8::Local<v8::Promise> derived = ::workerd::jsg::check(
+      local_as_ref_from_ffi<v8::Promise>(promise)->Then(context, onFulfilled, onRejected));
+  return to_ffi(kj::mv(derived));
+}
+
 Global create_resource_template(Isolate* isolate, const ResourceDescriptor& descriptor) {
   // Construct lazily.
   v8::EscapableHandleScope scope(isolate);
@@ -294,6 +827,16 @@ Global create_resource_template(Isolate* isolate, const ResourceDescriptor& desc
     constructor->Set(name, functionTemplate);
   }
 
+  // Static constants are plain values rather than accessors, set on both the constructor and
+  // the prototype like C++ JSG's registerStaticConstant().
+  for (const auto& constant: descriptor.static_constants) {
+    auto name = ::workerd::jsg::check(v8::String::NewFromUtf8(
+        isolate, constant.name.data(), v8::NewStringType::kInternalized, constant.name.size()));
+    auto value = v8::Number::New(isolate, constant.value);
+    constructor->Set(name, value, v8::PropertyAttribute::ReadOnly);
+    prototype->Set(name, value, v8::PropertyAttribute::ReadOnly);
+  }
+
   for (const auto& method: descriptor.methods) {
     auto functionTemplate = v8::FunctionTemplate::New(isolate,
         reinterpret_cast<v8::FunctionCallback>(reinterpret_cast<void*>(method.callback)),
@@ -303,18 +846,401 @@ Global create_resource_template(Isolate* isolate, const ResourceDescriptor& desc
     prototype->Set(name, functionTemplate);
   }
 
+  // Properties are installed as accessor pairs backed by function templates, the same way C++ JSG
+  // does for prototype properties. A property without a setter is read-only.
+  auto installProperty = [&](v8::Local<v8::ObjectTemplate> target,
+                             const PropertyDescriptor& property) {
+    auto name = ::workerd::jsg::check(v8::String::NewFromUtf8(
+        isolate, property.name.data(), v8::NewStringType::kInternalized, property.name.size()));
+    auto getterFn = v8::FunctionTemplate::New(isolate,
+        reinterpret_cast<v8::FunctionCallback>(reinterpret_cast<void*>(property.getter_callback)),
+        v8::Local<v8::Value>(), signature, 0, v8::ConstructorBehavior::kThrow);
+    v8::Local<v8::FunctionTemplate> setterFn;
+    auto attributes = v8::PropertyAttribute::ReadOnly;
+    if (property.setter_callback != 0) {
+      setterFn = v8::FunctionTemplate::New(isolate,
+          reinterpret_cast<v8::FunctionCallback>(reinterpret_cast<void*>(property.setter_callback)),
+          v8::Local<v8::Value>(), signature, 0, v8::ConstructorBehavior::kThrow);
+      attributes = v8::PropertyAttribute::None;
+    }
+    target->SetAccessorProperty(name, getterFn, setterFn, attributes);
+  };
+
+  for (const auto& property: descriptor.prototype_properties) {
+    installProperty(prototype, property);
+  }
+
+  for (const auto& property: descriptor.instance_properties) {
+    installProperty(instance, property);
+  }
+
   auto result = scope.Escape(constructor);
   return to_ffi(v8::Global<v8::FunctionTemplate>(isolate, result));
 }
 
-Realm* realm_from_isolate(Isolate* isolate) {
-  auto* realm =
-      static_cast<Realm*>(isolate->GetData(::workerd::jsg::SetDataIndex::SET_DATA_RUST_REALM));
-  KJ_ASSERT(realm != nullptr, "Rust Realm not set on isolate");
-  return realm;
+namespace {
+// Owned by the embedder data of a context with a Realm. The scheduler is declared last so that it
+// is destroyed first, cancelling pending I/O before the Realm drops the resources it tracks.
+struct ContextRealm {
+  ::rust::Box<Realm> realm;
+  // Weak handle to the context, set for Realms created on first use, see `realm_from_context()`.
+  v8::Global<v8::Context> context;
+  kj::Maybe<kj::Own<IoScheduler>> ioScheduler;
+};
+
+kj::Maybe<ContextRealm&> tryGetContextRealm(v8::Local<v8::Context> context) {
+  // Contexts that were not set up by the embedder may not have the slot at all.
+  if (context->GetNumberOfEmbedderDataFields() <=
+      static_cast<uint32_t>(::workerd::jsg::ContextPointerSlot::RUST_REALM)) {
+    return kj::none;
+  }
+  return ::workerd::jsg::getAlignedPointerFromEmbedderData<ContextRealm>(
+      context, ::workerd::jsg::ContextPointerSlot::RUST_REALM);
+}
+}  // namespace
+
+void realm_create_for_context(
+    Isolate* isolate, v8::Local<v8::Context> context, kj::Own<IoScheduler> ioScheduler) {
+  KJ_REQUIRE(tryGetContextRealm(context) == kj::none, "Rust Realm already set on context");
+  auto contextRealm = new ContextRealm{realm_create(isolate, false), {}, kj::mv(ioScheduler)};
+  ::workerd::jsg::setAlignedPointerInEmbedderData(
+      context, ::workerd::jsg::ContextPointerSlot::RUST_REALM, contextRealm);
+}
+
+void realm_dispose(v8::Local<v8::Context> context) {
+  KJ_IF_SOME(contextRealm, tryGetContextRealm(context)) {
+    ::workerd::jsg::setAlignedPointerInEmbedderData(
+        context, ::workerd::jsg::ContextPointerSlot::RUST_REALM, nullptr);
+    delete &contextRealm;
+  }
+}
+
+Realm* realm_from_context(const Local& context) {
+  auto& v8Context = local_as_ref_from_ffi<v8::Context>(context);
+  KJ_IF_SOME(contextRealm, tryGetContextRealm(v8Context)) {
+    return &*contextRealm.realm;
+  }
+
+  // Rust code is running in a context the embedder did not set up, e.g. one created by other C++
+  // code, so its Realm is created on first use and dropped once the context is collected. The
+  // Realm holds no strong handles that could keep the context alive. There is no scheduler to
+  // resume I/O in such a context.
+  auto isolate = v8::Isolate::GetCurrent();
+  auto contextRealm = new ContextRealm{realm_create(isolate, true), {isolate, v8Context}, kj::none};
+  contextRealm->context.SetWeak(contextRealm, [](const v8::WeakCallbackInfo<ContextRealm>& info) {
+    // The Realm drops resources that may hold V8 handles, so it is dropped in the second pass.
+    info.GetParameter()->context.Reset();
+    info.SetSecondPassCallback(
+        [](const v8::WeakCallbackInfo<ContextRealm>& info) { delete info.GetParameter(); });
+  }, v8::WeakCallbackType::kParameter);
+  ::workerd::jsg::setAlignedPointerInEmbedderData(
+      v8Context, ::workerd::jsg::ContextPointerSlot::RUST_REALM, contextRealm);
+  return &*contextRealm->realm;
+}
+
+namespace {
+void buildRealmEmbedderGraph(v8::Isolate* isolate, v8::EmbedderGraph* graph, void* data) {
+  auto& realm = *static_cast<Realm*>(data);
+  MemoryTracker::buildGraph(
+      isolate, graph, [&](MemoryTracker& tracker) { realm_get_memory_info(realm, tracker); });
+}
+}  // namespace
+
+void realm_register_memory_info(Isolate* isolate, Realm* realm) {
+  isolate->GetHeapProfiler()->AddBuildEmbedderGraphCallback(buildRealmEmbedderGraph, realm);
+}
+
+void realm_unregister_memory_info(Isolate* isolate, Realm* realm) {
+  isolate->GetHeapProfiler()->RemoveBuildEmbedderGraphCallback(buildRealmEmbedderGraph, realm);
+}
+
+// MemoryTracker
+namespace {
+// The tracker keeps node and edge names as pointers that are only read once the heap snapshot is
+// built, so names passed from Rust are interned for the lifetime of the process.
+kj::Maybe<kj::StringPtr> internName(::rust::Str name) {
+  if (name.empty()) return kj::none;
+  static kj::MutexGuarded<kj::HashSet<kj::String>> names;
+  auto key = kj::heapString(name.data(), name.size());
+  auto lock = names.lockExclusive();
+  KJ_IF_SOME(existing, lock->find(key)) {
+    return existing.asPtr();
+  }
+  return lock->insert(kj::mv(key)).asPtr();
+}
+}  // namespace
+
+void memory_tracker_track_field_with_size(
+    MemoryTracker& tracker, ::rust::Str edge_name, size_t size) {
+  tracker.trackFieldWithSize(internName(edge_name).orDefault(nullptr), size);
+}
+
+void memory_tracker_track_inline_field_with_size(
+    MemoryTracker& tracker, ::rust::Str edge_name, size_t size) {
+  tracker.trackInlineFieldWithSize(internName(edge_name).orDefault(nullptr), size);
+}
+
+void memory_tracker_track_global(MemoryTracker& tracker, ::rust::Str edge_name, const Global& value) {
+  v8::HandleScope scope(tracker.isolate());
+  tracker.trackField(
+      internName(edge_name).orDefault(nullptr), global_as_ref_from_ffi<v8::Value>(value));
+}
+
+void memory_tracker_track_foreign(MemoryTracker& tracker,
+    ::rust::Str edge_name,
+    size_t retainer,
+    ::rust::Str name,
+    size_t size,
+    const Global* wrapper,
+    size_t data,
+    MemoryInfoCallback callback) {
+  v8::HandleScope scope(tracker.isolate());
+  v8::Local<v8::Object> object;
+  if (wrapper != nullptr) {
+    object = global_as_ref_from_ffi<v8::Object>(*wrapper).Get(tracker.isolate());
+  }
+  tracker.trackForeign(internName(edge_name), reinterpret_cast<const void*>(retainer),
+      KJ_ASSERT_NONNULL(internName(name)), size, object,
+      [&](MemoryTracker& tracker) { callback(tracker, data); });
+}
+
+void memory_tracker_track_traced_data(
+    MemoryTracker& tracker, ::rust::Str edge_name, const TracedData& value) {
+  v8::HandleScope scope(tracker.isolate());
+  tracker.trackField(internName(edge_name).orDefault(nullptr), value.handle);
+}
+
+// GC
+TracedData::~TracedData() noexcept(false) {
+  KJ_IF_SOME(t, tracer) {
+    t.restore(*this);
+  }
+}
+
+GcTracer::GcTracer(Isolate* isolate, Realm& realm): isolate(isolate), realm(realm) {
+  isolate->AddGCPrologueCallback(gcPrologue, this,
+      static_cast<v8::GCType>(v8::kGCTypeMarkSweepCompact | v8::kGCTypeIncrementalMarking));
+  isolate->AddGCEpilogueCallback(gcEpilogue, this, v8::kGCTypeMarkSweepCompact);
+}
+
+GcTracer::~GcTracer() noexcept(false) {
+  isolate->RemoveGCPrologueCallback(gcPrologue, this);
+  isolate->RemoveGCEpilogueCallback(gcEpilogue, this);
+  while (!weakened.empty()) {
+    restore(weakened.front());
+  }
+}
+
+void GcTracer::visit(TracedData& data, size_t owner) {
+  if (data.handle.IsEmpty()) return;
+
+  if (confirming) {
+    KJ_IF_SOME(t, data.tracer) {
+      // A value moved to another resource since marking started would not be traced by the
+      // wrapper it is weak for.
+      if (&t == this && data.owner == owner) {
+        data.confirmed = true;
+      }
+    }
+    return;
+  }
+
+  if (data.tracer != kj::none) return;
+
+  // Same as `GcVisitor::visit(Data&)` for a value that is only reachable via traced objects. The
+  // traced reference also keeps the value alive through minor GCs until the wrapper is traced.
+  v8::HandleScope scope(isolate);
+  data.traced = v8::TracedReference<v8::Value>(isolate, data.handle.Get(isolate));
+  data.handle.SetWeak();
+  data.tracer = *this;
+  data.owner = owner;
+  data.confirmed = false;
+  weakened.add(data);
+}
+
+void GcTracer::restore(TracedData& data) {
+  KJ_DASSERT(&KJ_ASSERT_NONNULL(data.tracer) == this);
+  weakened.remove(data);
+  data.tracer = kj::none;
+  data.owner = 0;
+  data.confirmed = false;
+
+  // A handle that was traced during the last GC cannot have been collected by it, but its value
+  // is gone if the handle is destroyed from a weak callback during that same GC.
+  if (!data.handle.IsEmpty()) {
+    data.handle.ClearWeak();
+  }
+  KJ_IF_SOME(t, data.traced) {
+    if (!inWeakCallback) {
+      t.Reset();
+    }
+  }
+  data.traced = kj::none;
+}
+
+void GcTracer::gcPrologue(Isolate*, v8::GCType type, v8::GCCallbackFlags, void* data) {
+  auto& tracer = *static_cast<GcTracer*>(data);
+  // Resources released without the lock since Rust code last held it can be collected by this GC.
+  realm_release_deferred_wrappers(tracer.realm);
+  if (!tracer.marking) {
+    tracer.marking = true;
+    realm_visit_for_gc(tracer.realm, tracer);
+  } else if (type == v8::kGCTypeMarkSweepCompact) {
+    // Marking started incrementally, and JavaScript may have moved values between resources, or
+    // taken a reference to a resource from Rust, since then.
+    tracer.confirming = true;
+    KJ_DEFER(tracer.confirming = false);
+    realm_visit_for_gc(tracer.realm, tracer);
+
+    for (auto iter = tracer.weakened.begin(); iter != tracer.weakened.end();) {
+      auto& value = *iter;
+      ++iter;
+      if (value.confirmed) {
+        value.confirmed = false;
+      } else {
+        tracer.restore(value);
+      }
+    }
+  }
+}
+
+void GcTracer::gcEpilogue(Isolate*, v8::GCType, v8::GCCallbackFlags, void* data) {
+  auto& tracer = *static_cast<GcTracer*>(data);
+  tracer.marking = false;
+  while (!tracer.weakened.empty()) {
+    tracer.restore(tracer.weakened.front());
+  }
+}
+
+ResourceShim::~ResourceShim() {
+  if (state != 0) {
+    resource_state_clear_shim(state);
+  }
+}
+
+void ResourceShim::Trace(cppgc::Visitor* visitor) const {
+  if (state != 0) {
+    resource_state_trace(state, *visitor);
+  }
+}
+
+kj::Own<TracedData> traced_data_create(Isolate* isolate, Local value) {
+  return kj::heap<TracedData>(
+      v8::Global<v8::Value>(isolate, local_from_ffi<v8::Value>(kj::mv(value))));
+}
+
+kj::Own<TracedData> traced_data_clone(const TracedData& value) {
+  // The clone is not traced yet, so it starts out strong.
+  return kj::heap<TracedData>(v8::Global<v8::Value>(v8::Isolate::GetCurrent(), value.handle));
+}
+
+Local traced_data_to_local(Isolate* isolate, const TracedData& value) {
+  return to_ffi(v8::Local<v8::Value>::New(isolate, value.handle));
+}
+
+kj::Own<GcTracer> gc_tracer_create(Isolate* isolate, Realm* realm) {
+  return kj::heap<GcTracer>(isolate, *realm);
+}
+
+void gc_tracer_visit(GcTracer& tracer, const TracedData& value, size_t owner) {
+  // Rust only has shared references to the values held by resources while walking them.
+  tracer.visit(const_cast<TracedData&>(value), owner);
+}
+
+void cppgc_visitor_trace(CppgcVisitor& visitor, const TracedData& value) {
+  // Only values made weak when marking started are traced, the others are still strong.
+  KJ_IF_SOME(traced, value.traced) {
+    visitor.Trace(traced);
+  }
+}
+
+ResourceShim* resource_shim_create(Isolate* isolate, const Local& object, size_t state) {
+  auto& allocHandle = isolate->GetCppHeap()->GetAllocationHandle();
+  auto shim = cppgc::MakeGarbageCollected<ResourceShim>(allocHandle, state);
+  v8::Object::Wrap<::workerd::jsg::Wrappable::WRAPPABLE_TAG>(
+      isolate, local_as_ref_from_ffi<v8::Object>(object), shim);
+  return shim;
+}
+
+void resource_shim_detach(ResourceShim* shim) {
+  shim->state = 0;
+}
+
+// HandleScope
+kj::Own<HandleScope> handle_scope_create(Isolate* isolate) {
+  return kj::heap<HandleScope>(isolate);
+}
+
+kj::Own<EscapableHandleScope> escapable_handle_scope_create(Isolate* isolate) {
+  return kj::heap<EscapableHandleScope>(isolate);
+}
+
+Local escapable_handle_scope_escape(EscapableHandleScope& scope, Local value) {
+  return to_ffi(scope.scope.Escape(local_from_ffi<v8::Value>(kj::mv(value))));
+}
+
+// I/O
+bool await_io(Isolate* isolate, ::rust::Box<PendingIo> io) {
+  // Contexts the embedder did not set up have no scheduler to resume the I/O.
+  auto maybeContextRealm = tryGetContextRealm(isolate->GetCurrentContext());
+  KJ_IF_SOME(contextRealm, maybeContextRealm) {
+    KJ_IF_SOME(ioScheduler, contextRealm.ioScheduler) {
+      auto promise = io->poll();
+      // `promise` borrows `io`, which the continuation owning it keeps alive until `promise` is
+      // done.
+      return ioScheduler->awaitIo(promise.then(
+          [io = kj::mv(io)]() mutable -> kj::Function<void(::workerd::jsg::Lock&)> {
+        return [io = kj::mv(io)](::workerd::jsg::Lock& js) mutable {
+          pending_io_resume(js.v8Isolate, kj::mv(io));
+        };
+      }));
+    }
+  }
+  return false;
 }
 
 // Errors
+namespace {
+kj::StringPtr domExceptionName(ExceptionType exception_type) {
+  switch (exception_type) {
+    case ExceptionType::OperationError:
+      return "OperationError"_kj;
+    case ExceptionType::DataError:
+      return "DataError"_kj;
+    case ExceptionType::DataCloneError:
+      return "DataCloneError"_kj;
+    case ExceptionType::InvalidAccessError:
+      return "InvalidAccessError"_kj;
+    case ExceptionType::InvalidStateError:
+      return "InvalidStateError"_kj;
+    case ExceptionType::InvalidCharacterError:
+      return "InvalidCharacterError"_kj;
+    case ExceptionType::NotSupportedError:
+      return "NotSupportedError"_kj;
+    case ExceptionType::TimeoutError:
+      return "TimeoutError"_kj;
+    case ExceptionType::TypeMismatchError:
+      return "TypeMismatchError"_kj;
+    case ExceptionType::AbortError:
+      return "AbortError"_kj;
+    case ExceptionType::NotFoundError:
+      return "NotFoundError"_kj;
+    default:
+      KJ_UNREACHABLE;
+  }
+}
+
+// DOMExceptions are created and recognized through the embedder's jsg::Lock, which Rust code can
+// run without, e.g. from a GC callback or in a context that JSG did not set up.
+kj::Maybe<::workerd::jsg::Lock&> tryGetJsgLock(Isolate* isolate) {
+  auto lock = isolate->GetData(::workerd::jsg::SET_DATA_LOCK);
+  if (lock == nullptr) {
+    return kj::none;
+  }
+  return *static_cast<::workerd::jsg::Lock*>(lock);
+}
+}  // namespace
+
 Local exception_create(Isolate* isolate, ExceptionType exception_type, ::rust::Str description) {
   auto message = ::workerd::jsg::check(v8::String::NewFromUtf8(
       isolate, description.data(), v8::NewStringType::kInternalized, description.size()));
@@ -324,17 +1250,77 @@ Local exception_create(Isolate* isolate, ExceptionType exception_type, ::rust::S
     case ExceptionType::ReferenceError:
       return to_ffi(v8::Exception::ReferenceError(message));
     case ExceptionType::SyntaxError:
+      // SyntaxError is also a DOMException name, but it is thrown as the JavaScript error, which
+      // is what parse failures are expected to throw.
       return to_ffi(v8::Exception::SyntaxError(message));
     case ExceptionType::TypeError:
       return to_ffi(v8::Exception::TypeError(message));
-    default:
-      // DOM-style exceptions (OperationError, DataError, etc.) and Error fall back to Error.
-      // TODO(soon): Use js.domException() to create proper DOMException objects.
+    case ExceptionType::Error:
       return to_ffi(v8::Exception::Error(message));
+    default: {
+      // DOM-style exceptions (OperationError, DataError, etc.) are created as DOMException
+      // objects, which derive their legacy `code` from the name.
+      auto name = domExceptionName(exception_type);
+      KJ_IF_SOME(js, tryGetJsgLock(isolate)) {
+        auto exception =
+            js.domException(kj::str(name), kj::heapString(description.data(), description.size()));
+        return to_ffi(v8::Local<v8::Value>(KJ_ASSERT_NONNULL(exception.tryGetHandle(js))));
+      }
+      // Without a jsg::Lock, fall back to a plain Error carrying the DOM name, which
+      // exception_is_dom_exception() does not match but Error::from_js() still reads back.
+      auto exception = v8::Exception::Error(message);
+      ::workerd::jsg::check(exception.As<v8::Object>()->DefineOwnProperty(
+          isolate->GetCurrentContext(), ::workerd::jsg::v8StrIntern(isolate, "name"),
+          ::workerd::jsg::v8StrIntern(isolate, name), v8::DontEnum));
+      return to_ffi(kj::mv(exception));
+    }
+  }
+}
+
+void exception_set_cause(Isolate* isolate, const Local& exception, Local cause) {
+  auto object = local_as_ref_from_ffi<v8::Object>(exception);
+  auto context = isolate->GetCurrentContext();
+  // Like the `cause` option of the Error constructor, `cause` is a non-enumerable own property.
+  ::workerd::jsg::check(object->DefineOwnProperty(context,
+      ::workerd::jsg::v8StrIntern(isolate, "cause"), local_from_ffi<v8::Value>(kj::mv(cause)),
+      v8::DontEnum));
+}
+
+bool exception_is_dom_exception(Isolate* isolate, const Local& value) {
+  auto handle = local_as_ref_from_ffi<v8::Value>(value);
+  if (!handle->IsObject()) {
+    return false;
+  }
+  KJ_IF_SOME(js, tryGetJsgLock(isolate)) {
+    return ::workerd::jsg::JsObject(handle.As<v8::Object>())
+        .isInstanceOf<::workerd::jsg::DOMException>(js);
+  }
+  // Without a jsg::Lock, check against the `DOMException` global of the current context, if any.
+  // Neither the lookup nor `instanceof` may leave an exception behind for the caller.
+  auto context = isolate->GetCurrentContext();
+  v8::TryCatch tryCatch(isolate);
+  v8::Local<v8::Value> constructor;
+  if (!context->Global()
+           ->Get(context, ::workerd::jsg::v8StrIntern(isolate, "DOMException"))
+           .ToLocal(&constructor) ||
+      !constructor->IsFunction()) {
+    return false;
   }
+  return handle->InstanceOf(context, constructor.As<v8::Object>()).FromMaybe(false);
+}
+
+// Local<Context>
+Local local_context_global(const Local& context) {
+  return to_ffi(local_as_ref_from_ffi<v8::Context>(context)->Global());
 }
 
 // Isolate
+Local isolate_get_current_context(Isolate* isolate) {
+  auto context = isolate->GetCurrentContext();
+  KJ_REQUIRE(!context.IsEmpty(), "no JavaScript context is entered");
+  return to_ffi(kj::mv(context));
+}
+
 void isolate_throw_exception(Isolate* isolate, Local exception) {
   isolate->ThrowException(local_from_ffi<v8::Value>(kj::mv(exception)));
 }
@@ -345,6 +1331,35 @@ void isolate_throw_error(Isolate* isolate, ::rust::Str description) {
   isolate->ThrowError(message);
 }
 
+TryCatchResult isolate_try_catch(Isolate* isolate, size_t data, TryCatchCallback callback) {
+  v8::TryCatch tryCatch(isolate);
+  callback(isolate, data);
+
+  TryCatchResult result;
+  result.caught = tryCatch.HasCaught();
+  if (!result.caught) {
+    return result;
+  }
+  if (tryCatch.HasTerminated()) {
+    // Termination cannot be caught, let it propagate past the caller.
+    tryCatch.ReThrow();
+    return result;
+  }
+
+  result.exception = to_ffi(tryCatch.Exception());
+  v8::Local<v8::Value> stack;
+  if (tryCatch.StackTrace(isolate->GetCurrentContext()).ToLocal(&stack) && stack->IsString()) {
+    v8::String::Utf8Value utf8(isolate, stack);
+    result.stack = ::rust::String(*utf8, utf8.length());
+  }
+  auto message = tryCatch.Message();
+  if (!message.IsEmpty()) {
+    v8::String::Utf8Value utf8(isolate, message->Get());
+    result.message = ::rust::String(*utf8, utf8.length());
+  }
+  return result;
+}
+
 bool isolate_is_locked(Isolate* isolate) {
   return v8::Locker::IsLocked(isolate);
 }
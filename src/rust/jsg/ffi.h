@@ -1,14 +1,22 @@
 #pragma once
 
+#include <workerd/jsg/memory.h>
 #include <workerd/jsg/modules.capnp.h>
 
 #include <kj-rs/kj-rs.h>
 #include <rust/cxx.h>
+#include <v8-cppgc.h>
 #include <v8.h>
 
+#include <kj/async.h>
 #include <kj/function.h>
+#include <kj/list.h>
 #include <kj/memory.h>
 
+namespace workerd::jsg {
+class Lock;
+}  // namespace workerd::jsg
+
 // Forward declarations needed by v8.rs.h
 namespace workerd::rust::jsg {
 using Isolate = v8::Isolate;
@@ -18,11 +26,25 @@ using WeakCallbackInfo = v8::WeakCallbackInfo<void>;
 struct ModuleRegistry;
 struct Local;
 struct Global;
+struct FunctionCallResult;
+struct TryCatchResult;
+struct PropertyAttributes;
+struct TracedData;
 struct Realm;
+struct PendingIo;
+class IoScheduler;
 enum class ExceptionType : ::std::uint8_t;
+enum class WellKnownSymbol : ::std::uint8_t;
 using ModuleType = ::workerd::jsg::ModuleType;
 using ModuleCallback = ::rust::Fn<Local(Isolate*)>;
 using WeakCallback = ::rust::Fn<void(Isolate*, size_t)>;
+using PromiseReactionCallback =
+    ::rust::Fn<void(Isolate*, size_t, Local, bool, FunctionCallbackInfo*)>;
+using PromiseReactionDrop = ::rust::Fn<void(size_t)>;
+using TryCatchCallback = ::rust::Fn<void(Isolate*, size_t)>;
+using MemoryTracker = ::workerd::jsg::MemoryTracker;
+using MemoryInfoCallback = ::rust::Fn<void(MemoryTracker&, size_t)>;
+using CppgcVisitor = ::cppgc::Visitor;
 
 struct ResourceDescriptor;
 
@@ -33,6 +55,8 @@ Global local_to_global(Isolate* isolate, Local value);
 Local local_new_number(Isolate* isolate, double value);
 Local local_new_string(Isolate* isolate, ::rust::Str value);
 Local local_new_boolean(Isolate* isolate, bool value);
+Local local_new_big_int(Isolate* isolate, int64_t value);
+Local local_new_big_int_from_unsigned(Isolate* isolate, uint64_t value);
 Local local_new_object(Isolate* isolate);
 Local local_new_null(Isolate* isolate);
 Local local_new_undefined(Isolate* isolate);
@@ -41,32 +65,86 @@ bool local_has_value(const Local& val);
 bool local_is_string(const Local& val);
 bool local_is_boolean(const Local& val);
 bool local_is_number(const Local& val);
+bool local_is_big_int(const Local& val);
 bool local_is_null(const Local& val);
 bool local_is_undefined(const Local& val);
 bool local_is_null_or_undefined(const Local& val);
 bool local_is_object(const Local& val);
 bool local_is_native_error(const Local& val);
+bool local_is_promise(const Local& val);
+bool local_is_function(const Local& val);
+bool local_is_array(const Local& val);
+bool local_is_array_buffer(const Local& val);
+bool local_is_array_buffer_view(const Local& val);
+bool local_is_uint8_array(const Local& val);
+bool local_is_symbol(const Local& val);
+bool local_is_date(const Local& val);
+bool local_is_reg_exp(const Local& val);
 ::rust::String local_type_of(Isolate* isolate, const Local& val);
 
 // Local<Object>
-void local_object_set_property(Isolate* isolate, Local& object, ::rust::Str key, Local value);
-bool local_object_has_property(Isolate* isolate, const Local& object, ::rust::Str key);
-kj::Maybe<Local> local_object_get_property(Isolate* isolate, const Local& object, ::rust::Str key);
+Local local_new_internalized_string(Isolate* isolate, ::rust::Str value);
+void local_object_set(Isolate* isolate, const Local& object, Local key, Local value);
+bool local_object_has(Isolate* isolate, const Local& object, Local key);
+kj::Maybe<Local> local_object_get(Isolate* isolate, const Local& object, Local key);
+bool local_object_delete(Isolate* isolate, const Local& object, Local key);
+void local_object_define_property(Isolate* isolate,
+    const Local& object,
+    Local key,
+    Local value,
+    PropertyAttributes attributes);
+::rust::Vec<::rust::String> local_object_get_own_property_names(
+    Isolate* isolate, const Local& object);
+::rust::Vec<Local> local_object_get_own_property_keys(Isolate* isolate, const Local& object);
+Local local_object_get_prototype(Isolate* isolate, const Local& object);
+bool local_object_set_prototype(Isolate* isolate, const Local& object, Local prototype);
+
+// Local<Array>
+Local local_new_array(Isolate* isolate, ::rust::Vec<Local> values);
+kj::Maybe<Local> local_iterate(Isolate* isolate, const Local& value, ::rust::Vec<Local>& values);
+
+// Local<Function>
+FunctionCallResult local_function_call(
+    Isolate* isolate, const Local& function, Local receiver, ::rust::Vec<Local> args);
+
+// Local<Uint8Array>
+Local local_new_uint8_array(Isolate* isolate, ::rust::Slice<const uint8_t> data);
+
+// Local<Symbol>
+Local local_new_symbol(Isolate* isolate, ::rust::Str description);
+Local local_symbol_well_known(Isolate* isolate, WellKnownSymbol symbol);
+Local local_symbol_description(Isolate* isolate, const Local& symbol);
+
+// Local<Date>
+Local local_new_date(Isolate* isolate, double time);
+double local_date_value_of(const Local& date);
+
+// Local<RegExp>
+Local local_new_reg_exp(Isolate* isolate, ::rust::Str pattern, ::rust::Str flags);
+::rust::String local_reg_exp_source(Isolate* isolate, const Local& regexp);
+::rust::String local_reg_exp_flags(const Local& regexp);
 
 // Global<T>
 void global_drop(Global value);
 Global global_clone(const Global& value);
 Local global_to_local(Isolate* isolate, const Global& value);
+::rust::Slice<const uint8_t> global_buffer_source_bytes(Isolate* isolate, const Global& value);
 void global_make_weak(
     Isolate* isolate, Global* value, size_t /* void* */ data, WeakCallback callback);
+void global_clear_weak(Global* value);
 
 // Wrappers
 Local wrap_resource(Isolate* isolate, size_t resource, const Global& tmpl, size_t drop_callback);
+void attach_resource(Isolate* isolate, size_t resource, const Local& object);
 
 // Unwrappers
 ::rust::String unwrap_string(Isolate* isolate, Local value);
 bool unwrap_boolean(Isolate* isolate, Local value);
 double unwrap_number(Isolate* isolate, Local value);
+int64_t unwrap_big_int_i64(const Local& value, bool& lossless);
+uint64_t unwrap_big_int_u64(const Local& value, bool& lossless);
+size_t unwrap_big_int_usize(const Local& value, bool& lossless);
+bool unwrap_big_int_is_negative(const Local& value);
 size_t unwrap_resource(Isolate* isolate, Local value);
 
 // FunctionCallbackInfo
@@ -75,6 +153,24 @@ Local fci_get_this(FunctionCallbackInfo* args);
 size_t fci_get_length(FunctionCallbackInfo* args);
 Local fci_get_arg(FunctionCallbackInfo* args, size_t index);
 void fci_set_return_value(FunctionCallbackInfo* args, Local value);
+bool fci_is_construct_call(FunctionCallbackInfo* args);
+
+// Local<FunctionTemplate>
+Local function_template_get_function(Isolate* isolate, const Local& tmpl);
+bool function_template_has_instance(const Local& tmpl, const Local& value);
+
+// Local<Promise>
+Local promise_then(Isolate* isolate,
+    const Local& promise,
+    size_t data,
+    PromiseReactionCallback callback,
+    PromiseReactionDrop drop_data);
+
+// Local<PromiseResolver>
+Local promise_resolver_new(Isolate* isolate);
+Local promise_resolver_get_promise(Isolate* isolate, const Local& resolver);
+void promise_resolver_resolve(Isolate* isolate, const Local& resolver, Local value);
+void promise_resolver_reject(Isolate* isolate, const Local& resolver, Local value);
 
 struct ModuleRegistry {
   virtual ~ModuleRegistry() = default;
@@ -92,14 +188,193 @@ inline void register_add_builtin_module(ModuleRegistry& registry,
 Global create_resource_template(Isolate* isolate, const ResourceDescriptor& descriptor);
 
 // Realm
-Realm* realm_from_isolate(Isolate* isolate);
+// Creates the Realm tracking the Rust resources of `context`, owned by the context's embedder
+// data along with the `IoScheduler` that resumes I/O started in the context. Embedders call this
+// for every context that may run Rust code, and `realm_dispose()` under the isolate lock when the
+// context is disposed, which cancels pending I/O and drops the resources the Realm still tracks.
+//
+// Rust code running in any other context gets a Realm created on first use by
+// `realm_from_context()`, which is dropped once the context is collected. So that it does not keep
+// the context alive, the wrappers of its resources are always weak and it stores no context data.
+// I/O cannot be awaited in such a context.
+void realm_create_for_context(
+    Isolate* isolate, v8::Local<v8::Context> context, kj::Own<IoScheduler> ioScheduler);
+void realm_dispose(v8::Local<v8::Context> context);
+Realm* realm_from_context(const Local& context);
+void realm_register_memory_info(Isolate* isolate, Realm* realm);
+void realm_unregister_memory_info(Isolate* isolate, Realm* realm);
+
+// MemoryTracker
+void memory_tracker_track_field_with_size(
+    MemoryTracker& tracker, ::rust::Str edge_name, size_t size);
+void memory_tracker_track_inline_field_with_size(
+    MemoryTracker& tracker, ::rust::Str edge_name, size_t size);
+void memory_tracker_track_global(MemoryTracker& tracker, ::rust::Str edge_name, const Global& value);
+void memory_tracker_track_foreign(MemoryTracker& tracker,
+    ::rust::Str edge_name,
+    size_t retainer,
+    ::rust::Str name,
+    size_t size,
+    const Global* wrapper,
+    size_t data,
+    MemoryInfoCallback callback);
+void memory_tracker_track_traced_data(
+    MemoryTracker& tracker, ::rust::Str edge_name, const TracedData& value);
+
+// GC
+class GcTracer;
+
+// The storage of a `jsg::V8Ref<T>`, analogous to `jsg::Data` in C++ JSG. The handle is strong,
+// except during a full GC, see `GcTracer`. It lives on the heap because V8 resets weak handles in
+// place, while Rust may move the `V8Ref` owning it.
+struct TracedData {
+  explicit TracedData(v8::Global<v8::Value> handle): handle(kj::mv(handle)) {}
+  ~TracedData() noexcept(false);
+  KJ_DISALLOW_COPY_AND_MOVE(TracedData);
+
+  v8::Global<v8::Value> handle;
+
+  // While `handle` is weak, a traced reference to the same value that V8 marks through.
+  kj::Maybe<v8::TracedReference<v8::Value>> traced;
+
+  // The tracer that made `handle` weak, which makes it strong again once the GC is over.
+  kj::Maybe<GcTracer&> tracer;
+  kj::ListLink<TracedData> link;
+
+  // The `ResourceState` of the wrapped resource that held the value when it was made weak.
+  size_t owner = 0;
+
+  // Whether the value is still held by `owner` when marking finishes, see `GcTracer`.
+  bool confirmed = false;
+};
+
+// Makes the `jsg::V8Ref<T>` values held by the resources of a Realm weak during full GCs.
+//
+// Like `GcVisitor::visit(Data&)` in C++ JSG, a value held by a resource that is only owned by its
+// wrapper has its handle made weak and is traced through the wrapper's `ResourceShim` instead, so
+// that cycles through Rust resources can be collected. Unlike C++, Rust cannot intercept moves, so
+// the handles are only weak while a full GC is in progress:
+//
+// - When marking starts, the Realm's resources are walked to make the values they hold weak.
+// - Marking may be incremental, with JavaScript running in between steps. Before V8 traces the
+//   wrappers, which cppgc does atomically, the resources are walked again, and the values no
+//   longer held by the same resource are made strong again.
+// - Once the GC is over, every value is made strong again.
+class GcTracer {
+ public:
+  GcTracer(Isolate* isolate, Realm& realm);
+  ~GcTracer() noexcept(false);
+  KJ_DISALLOW_COPY_AND_MOVE(GcTracer);
+
+  // Called for each value held by the resource whose `ResourceState` is at `owner`, while the
+  // Realm's resources are walked.
+  void visit(TracedData& data, size_t owner);
+
+  // Makes `data` strong again and stops tracking it.
+  void restore(TracedData& data);
+
+ private:
+  Isolate* isolate;
+  Realm& realm;
+
+  // Whether values were made weak for the GC in progress.
+  bool marking = false;
+
+  // Whether the Realm's resources are walked to confirm the values made weak.
+  bool confirming = false;
+
+  kj::List<TracedData, &TracedData::link> weakened;
+
+  static void gcPrologue(
+      Isolate* isolate, v8::GCType type, v8::GCCallbackFlags flags, void* data);
+  static void gcEpilogue(
+      Isolate* isolate, v8::GCType type, v8::GCCallbackFlags flags, void* data);
+};
+
+// Traces the values held by a Rust resource while V8 traces its wrapper, analogous to
+// `Wrappable::CppgcShim` in C++ JSG. Rust resources are not allocated on the cppgc heap, so each
+// wrapper is given a shim pointing back at the resource's `ResourceState`. The shim and the
+// resource clear their pointers to each other when either goes away first.
+class ResourceShim final: public v8::Object::Wrappable {
+ public:
+  explicit ResourceShim(size_t state): state(state) {}
+  ~ResourceShim();
+
+  void Trace(cppgc::Visitor* visitor) const override;
+  const char* GetHumanReadableName() const override {
+    return "ResourceShim";
+  }
+
+  // The `ResourceState` of the resource, or 0 once the resource no longer uses this wrapper.
+  size_t state;
+};
+
+kj::Own<TracedData> traced_data_create(Isolate* isolate, Local value);
+kj::Own<TracedData> traced_data_clone(const TracedData& value);
+Local traced_data_to_local(Isolate* isolate, const TracedData& value);
+kj::Own<GcTracer> gc_tracer_create(Isolate* isolate, Realm* realm);
+void gc_tracer_visit(GcTracer& tracer, const TracedData& value, size_t owner);
+void cppgc_visitor_trace(CppgcVisitor& visitor, const TracedData& value);
+ResourceShim* resource_shim_create(Isolate* isolate, const Local& object, size_t state);
+void resource_shim_detach(ResourceShim* shim);
+
+// HandleScope
+// The scopes opened by `jsg::HandleScope` and `jsg::EscapableHandleScope`. V8 only allows handle
+// scopes on the stack, but Rust owns these through a `kj::Own`, so they are wrapped to be allocated
+// on the heap. The `Lock` borrowed by the Rust scope ensures they are still closed in order.
+struct HandleScope {
+  explicit HandleScope(Isolate* isolate): scope(isolate) {}
+  KJ_DISALLOW_COPY_AND_MOVE(HandleScope);
+
+  v8::HandleScope scope;
+};
+
+struct EscapableHandleScope {
+  explicit EscapableHandleScope(Isolate* isolate): scope(isolate) {}
+  KJ_DISALLOW_COPY_AND_MOVE(EscapableHandleScope);
+
+  v8::EscapableHandleScope scope;
+};
+
+kj::Own<HandleScope> handle_scope_create(Isolate* isolate);
+kj::Own<EscapableHandleScope> escapable_handle_scope_create(Isolate* isolate);
+Local escapable_handle_scope_escape(EscapableHandleScope& scope, Local value);
+
+// I/O
+// Resumes the futures passed to `Lock::await_io()` in a context, provided by the embedder when it
+// creates the context's Realm. Rust code must only run under the embedder's `jsg::Lock`, so once
+// a future completes, the scheduler takes the lock the same way it does for any other event, e.g.
+// through `IoContext::run()` in workerd, so that the continuation is subject to the same limits
+// and serialized with other events.
+class IoScheduler {
+ public:
+  virtual ~IoScheduler() noexcept(false) = default;
+
+  // Called under the isolate lock when Rust code starts awaiting I/O in the context. `promise`
+  // resolves to the continuation once the I/O completes, which must then be called under the
+  // embedder's lock with the context entered, followed by a microtask checkpoint. Dropping
+  // `promise` cancels the I/O.
+  //
+  // Returns false if I/O cannot be awaited at this point, e.g. outside of a request, in which
+  // case the promise returned to JavaScript is rejected.
+  virtual bool awaitIo(kj::Promise<kj::Function<void(::workerd::jsg::Lock&)>> promise) = 0;
+};
+
+bool await_io(Isolate* isolate, ::rust::Box<PendingIo> io);
 
 // Errors
 Local exception_create(Isolate* isolate, ExceptionType exception_type, ::rust::Str message);
+void exception_set_cause(Isolate* isolate, const Local& exception, Local cause);
+bool exception_is_dom_exception(Isolate* isolate, const Local& value);
+
+// Local<Context>
+Local local_context_global(const Local& context);
 
 // Isolate
+Local isolate_get_current_context(Isolate* isolate);
 void isolate_throw_exception(Isolate* isolate, Local exception);
 void isolate_throw_error(Isolate* isolate, ::rust::Str message);
+TryCatchResult isolate_try_catch(Isolate* isolate, size_t data, TryCatchCallback callback);
 bool isolate_is_locked(Isolate* isolate);
 
 }  // namespace workerd::rust::jsg
@@ -0,0 +1,131 @@
+//! JavaScript functions callable from Rust, analogous to `jsg::Function<T>` in C++ JSG.
+//!
+//! A `Function<fn(A, B) -> R>` holds a JavaScript function that Rust code calls with arguments
+//! converted through `ToJS`, converting its result through `FromJS`. Like `V8Ref<T>`, a function
+//! stored in a resource is traced through the resource's wrapper, so callbacks that refer back
+//! to the resource do not keep it alive.
+
+use std::marker::PhantomData;
+
+use crate::Error;
+use crate::FromJS;
+use crate::GcVisitor;
+use crate::Lock;
+use crate::MemoryTracker;
+use crate::Result;
+use crate::ToJS;
+use crate::Type;
+use crate::V8Ref;
+use crate::v8;
+
+/// A JavaScript function with the signature `F`, a function pointer type such as
+/// `fn(String, u32) -> bool`.
+///
+/// Arguments are converted with `ToJS` and the result with `FromJS`. If the function throws, the
+/// exception is returned as a `jsg::Error`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[jsg_method]
+/// pub fn for_each(
+///     &self,
+///     lock: &mut jsg::Lock,
+///     callback: jsg::Function<fn(String, u32)>,
+/// ) -> jsg::Result<()> {
+///     for (index, entry) in (0..).zip(&self.entries) {
+///         callback.call(lock, entry.clone(), index)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Function<F> {
+    function: V8Ref<v8::Function>,
+    _marker: PhantomData<F>,
+}
+
+impl<F> Function<F> {
+    /// Returns the underlying `v8::Function`.
+    pub fn as_local<'s>(&self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Function> {
+        self.function.as_local(lock)
+    }
+
+    /// Calls the function with `undefined` as `this` and unwraps its result as `R`.
+    fn invoke<R: FromJS<ResultType = R>>(
+        &self,
+        lock: &mut Lock,
+        args: Vec<v8::Local<'_, v8::Value>>,
+    ) -> Result<R> {
+        let function = self.as_local(lock);
+        let receiver = v8::Local::<v8::Value>::undefined(lock);
+        match function.call(lock, receiver, args) {
+            Ok(value) => R::from_js(lock, value),
+            Err(Some(exception)) => Err(Error::from_js(lock, exception).unwrap_or_else(|err| err)),
+            Err(None) => Err(Error::new_error("JavaScript execution was terminated")),
+        }
+    }
+}
+
+/// Implements `call` for `Function<fn(..) -> R>` with the given parameters.
+macro_rules! impl_function_call {
+    ($($arg:ident: $type:ident),*) => {
+        impl<$($type: ToJS,)* R: FromJS<ResultType = R>> Function<fn($($type),*) -> R> {
+            /// Calls the function under `lock`, returning its result or the exception it threw.
+            pub fn call(&self, lock: &mut Lock, $($arg: $type),*) -> Result<R> {
+                let args = vec![$($arg.to_js(lock)),*];
+                self.invoke(lock, args)
+            }
+        }
+    };
+}
+
+impl_function_call!();
+impl_function_call!(a: A);
+impl_function_call!(a: A, b: B);
+impl_function_call!(a: A, b: B, c: C);
+impl_function_call!(a: A, b: B, c: C, d: D);
+impl_function_call!(a: A, b: B, c: C, d: D, e: E);
+
+impl<F> Clone for Function<F> {
+    fn clone(&self) -> Self {
+        Self {
+            function: self.function.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F> Type for Function<F> {
+    fn class_name() -> &'static str {
+        "Function"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_function()
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        tracker.track_field("function", &self.function);
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        visitor.visit(&self.function);
+    }
+}
+
+impl<F> ToJS for Function<F> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        self.as_local(lock).into()
+    }
+}
+
+impl<F> FromJS for Function<F> {
+    type ResultType = Self;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        Ok(Self {
+            function: V8Ref::<v8::Function>::from_js(lock, value)?,
+            _marker: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,114 @@
+//! GC tracing support, analogous to `jsg::GcVisitor` in C++ JSG.
+//!
+//! When a full GC starts, the Realm visits its wrapped resources through `Type::visit_for_gc()`,
+//! and the handles of the `V8Ref<T>` values they hold are made weak for the duration of the GC.
+//! When V8 then traces the wrapper of a resource, the wrapper's cppgc shim calls back into Rust to
+//! visit the resource again, this time tracing its values through the wrapper. This way,
+//! JavaScript values that are only reachable through resources can be collected along with them.
+
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
+
+use crate::Ref;
+use crate::Resource;
+use crate::ResourceState;
+use crate::Type;
+use crate::V8Ref;
+use crate::ffi;
+
+/// Visits the JavaScript values held by a Rust object while V8 traces it.
+///
+/// A `GcVisitor` is passed to `Type::visit_for_gc()`, which `#[jsg_struct]` and
+/// `#[jsg_resource]` generate to visit every field. Types holding values in other ways implement
+/// it by hand.
+///
+/// # Example
+///
+/// ```ignore
+/// impl jsg::Type for Listeners {
+///     // ...
+///     fn visit_for_gc(&self, visitor: &mut jsg::GcVisitor) {
+///         for listener in self.listeners.values() {
+///             visitor.visit(listener);
+///         }
+///     }
+/// }
+/// ```
+pub struct GcVisitor<'a> {
+    inner: Inner<'a>,
+}
+
+enum Inner<'a> {
+    /// Walking the resources of a Realm to make the values held by the resource at `owner` weak.
+    Tracer {
+        tracer: Pin<&'a mut ffi::GcTracer>,
+        owner: usize,
+    },
+    /// Tracing the values held by a resource through its wrapper.
+    Cppgc(Pin<&'a mut ffi::CppgcVisitor>),
+}
+
+impl<'a> GcVisitor<'a> {
+    pub(crate) fn from_tracer(tracer: Pin<&'a mut ffi::GcTracer>, owner: usize) -> Self {
+        Self {
+            inner: Inner::Tracer { tracer, owner },
+        }
+    }
+
+    pub(crate) fn from_cppgc(visitor: Pin<&'a mut ffi::CppgcVisitor>) -> Self {
+        Self {
+            inner: Inner::Cppgc(visitor),
+        }
+    }
+
+    /// Visits the JavaScript values held by `value`.
+    pub fn visit<T: Type>(&mut self, value: &T) {
+        value.visit_for_gc(self);
+    }
+
+    /// Traces the value held by `value` through the resource being visited, whose wrapper keeps
+    /// it alive until the GC is over.
+    pub fn visit_v8_ref<T>(&mut self, value: &V8Ref<T>) {
+        match &mut self.inner {
+            Inner::Tracer { tracer, owner } => {
+                ffi::gc_tracer_visit(tracer.as_mut(), value.data(), *owner);
+            }
+            Inner::Cppgc(visitor) => ffi::cppgc_visitor_trace(visitor.as_mut(), value.data()),
+        }
+    }
+}
+
+/// Visits the resource owning `state`, which must have been wrapped as an `R`.
+///
+/// The values held by a resource that Rust code also refers to stay strong, as the resource may
+/// outlive its wrapper. Only the wrapper's own `Ref` counts as owned by JavaScript.
+pub fn visit_resource_state<R: Resource>(visitor: &mut GcVisitor, state: &ResourceState) {
+    // The wrapper holds the `Ref` leaked in `ResourceState.this`; borrow it without releasing it.
+    let resource = ManuallyDrop::new(unsafe { Ref::<R>::from_raw(state.this.cast()) });
+    if resource.is_unique() {
+        resource.visit_for_gc(visitor);
+    }
+}
+
+/// Wraps a field for `#[jsg_struct]` and `#[jsg_resource]`, which visit the fields implementing
+/// `Type` and skip the others, like `MemoryField` does for memory tracking.
+#[doc(hidden)]
+pub struct GcField<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait VisitGcField {
+    fn visit_for_gc(&self, visitor: &mut GcVisitor);
+}
+
+impl<T: Type> VisitGcField for GcField<'_, T> {
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        visitor.visit(self.0);
+    }
+}
+
+#[doc(hidden)]
+pub trait SkipGcField {
+    fn visit_for_gc(&self, _visitor: &mut GcVisitor) {}
+}
+
+impl<T> SkipGcField for &GcField<'_, T> {}
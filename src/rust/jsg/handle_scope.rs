@@ -0,0 +1,88 @@
+//! Scopes for the local handles created by Rust code, analogous to `v8::HandleScope` and
+//! `v8::EscapableHandleScope` in C++.
+//!
+//! Every `v8::Local<T>` lives in the innermost `HandleScope` of the isolate, and stays alive until
+//! that scope is closed. Callbacks from JavaScript run in a scope opened by C++, so a loop in Rust
+//! that creates handles, e.g. while converting a large `Vec`, grows that scope until the callback
+//! returns. Running each iteration in a scope of its own releases the handles created by the
+//! iteration as soon as it is over.
+//!
+//! Like `jsg::Lock::withinHandleScope()` in C++, a scope is open while a callback runs. The
+//! callback is given a `Lock<'s>` for a lifetime `'s` that is unique to the scope, so the
+//! `v8::Local<'s, T>` handles created through it cannot be stored anywhere that outlives the
+//! callback.
+
+use crate::Lock;
+use crate::ffi;
+use crate::v8;
+
+impl<'p> Lock<'p> {
+    /// Runs `callback` in a new `HandleScope`, releasing the handles it created once it returns.
+    ///
+    /// The callback's result cannot hold a handle created in the scope; use
+    /// `within_escapable_handle_scope()` to return one.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for entry in &entries {
+    ///     lock.within_handle_scope(|lock| {
+    ///         let value = entry.to_local(lock);
+    ///         callback.call(lock, receiver.clone(), vec![value])
+    ///     })?;
+    /// }
+    /// ```
+    ///
+    /// A handle created in the scope cannot be used once the scope is closed:
+    ///
+    /// ```compile_fail
+    /// fn new_objects(lock: &mut jsg::Lock<'_>) -> bool {
+    ///     let mut objects = Vec::new();
+    ///     lock.within_handle_scope(|lock| objects.push(lock.new_object()));
+    ///     objects[0].is_object()
+    /// }
+    /// ```
+    ///
+    /// ```compile_fail
+    /// fn new_entry<'p>(lock: &mut jsg::Lock<'p>) -> jsg::v8::Local<'p, jsg::v8::Object> {
+    ///     lock.within_handle_scope(|lock| lock.new_object())
+    /// }
+    /// ```
+    pub fn within_handle_scope<R>(
+        &mut self,
+        callback: impl for<'s> FnOnce(&mut Lock<'s>) -> R,
+    ) -> R {
+        let _scope = unsafe { ffi::handle_scope_create(self.isolate().as_ffi()) };
+        let mut lock = unsafe { Lock::from_isolate_ptr(self.isolate().as_ffi()) };
+        callback(&mut lock)
+    }
+
+    /// Runs `callback` in a new `EscapableHandleScope`, passing the handle it returns to the
+    /// enclosing scope and releasing every other handle it created.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// fn to_entry<'p>(lock: &mut jsg::Lock<'p>, key: &str, value: f64) -> v8::Local<'p, v8::Object> {
+    ///     lock.within_escapable_handle_scope(|lock| {
+    ///         let mut entry = lock.new_object();
+    ///         let value = value.to_local(lock);
+    ///         entry.set(lock, key, value);
+    ///         entry
+    ///     })
+    /// }
+    /// ```
+    pub fn within_escapable_handle_scope<T>(
+        &mut self,
+        callback: impl for<'s> FnOnce(&mut Lock<'s>) -> v8::Local<'s, T>,
+    ) -> v8::Local<'p, T> {
+        let isolate = self.isolate();
+        let mut scope = unsafe { ffi::escapable_handle_scope_create(isolate.as_ffi()) };
+        let mut lock = unsafe { Lock::from_isolate_ptr(isolate.as_ffi()) };
+        let value = callback(&mut lock);
+        unsafe {
+            let value = ffi::escapable_handle_scope_escape(scope.as_mut(), value.into_ffi());
+            v8::Local::from_ffi(isolate, value)
+        }
+    }
+}
@@ -1,19 +1,58 @@
+use std::any::Any;
+use std::any::TypeId;
 use std::cell::UnsafeCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::future::Future;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::num::ParseIntError;
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::os::raw::c_void;
+use std::pin::Pin;
 use std::ptr::NonNull;
 use std::rc::Rc;
 
 use kj_rs::KjMaybe;
-
+use kj_rs::KjOwn;
+
+mod buffer;
+mod exception;
+mod function;
+mod gc;
+mod handle_scope;
+mod memory;
 pub mod modules;
+pub mod node;
+mod promise;
 pub mod v8;
+mod v8_ref;
 mod wrappable;
 
+pub use buffer::BufferSource;
+pub use exception::Exception;
+pub use function::Function;
+#[doc(hidden)]
+pub use gc::GcField;
+pub use gc::GcVisitor;
+#[doc(hidden)]
+pub use gc::SkipGcField;
+#[doc(hidden)]
+pub use gc::VisitGcField;
+#[doc(hidden)]
+pub use memory::MemoryField;
+pub use memory::MemoryTracker;
+#[doc(hidden)]
+pub use memory::SkipMemoryField;
+#[doc(hidden)]
+pub use memory::TrackMemoryField;
+pub use promise::Promise;
+pub use promise::PromiseFuture;
+pub use promise::PromiseResolver;
 pub use v8::ffi::ExceptionType;
+pub use v8_ref::V8Ref;
+pub use v8_ref::Value;
 pub use wrappable::FromJS;
 pub use wrappable::ToJS;
 
@@ -23,16 +62,105 @@ mod ffi {
         type Realm;
 
         #[expect(clippy::unnecessary_box_returns)]
-        unsafe fn realm_create(isolate: *mut Isolate) -> Box<Realm>;
+        unsafe fn realm_create(isolate: *mut Isolate, collected_with_context: bool) -> Box<Realm>;
+        unsafe fn realm_get_memory_info(realm: &Realm, tracker: Pin<&mut MemoryTracker>);
+        unsafe fn realm_visit_for_gc(realm: &Realm, tracer: Pin<&mut GcTracer>);
+        unsafe fn realm_release_deferred_wrappers(realm: &mut Realm);
+    }
+
+    // ResourceState
+    extern "Rust" {
+        unsafe fn resource_state_trace(state: usize, visitor: Pin<&mut CppgcVisitor>);
+        unsafe fn resource_state_clear_shim(state: usize);
+    }
+
+    // PendingIo
+    extern "Rust" {
+        type PendingIo;
+
+        async unsafe fn poll<'a>(self: &'a mut PendingIo);
+        unsafe fn pending_io_resume(isolate: *mut Isolate, io: Box<PendingIo>);
     }
 
     unsafe extern "C++" {
         include!("workerd/rust/jsg/ffi.h");
 
         type Isolate = crate::v8::ffi::Isolate;
+        type Local = crate::v8::ffi::Local;
+        type Global = crate::v8::ffi::Global;
+        #[namespace = "workerd::jsg"]
+        type MemoryTracker;
+        type CppgcVisitor;
+        type TracedData;
+        type GcTracer;
+        type ResourceShim;
+        type HandleScope;
+        type EscapableHandleScope;
 
         // Realm
-        pub unsafe fn realm_from_isolate(isolate: *mut Isolate) -> *mut Realm;
+        pub unsafe fn realm_from_context(context: &Local) -> *mut Realm;
+        pub unsafe fn realm_register_memory_info(isolate: *mut Isolate, realm: *mut Realm);
+        pub unsafe fn realm_unregister_memory_info(isolate: *mut Isolate, realm: *mut Realm);
+
+        // MemoryTracker
+        pub fn memory_tracker_track_field_with_size(
+            tracker: Pin<&mut MemoryTracker>,
+            edge_name: &str,
+            size: usize,
+        );
+        pub fn memory_tracker_track_inline_field_with_size(
+            tracker: Pin<&mut MemoryTracker>,
+            edge_name: &str,
+            size: usize,
+        );
+        pub fn memory_tracker_track_global(
+            tracker: Pin<&mut MemoryTracker>,
+            edge_name: &str,
+            value: &Global,
+        );
+        pub unsafe fn memory_tracker_track_foreign(
+            tracker: Pin<&mut MemoryTracker>,
+            edge_name: &str,
+            retainer: usize, /* const void* */
+            name: &str,
+            size: usize,
+            wrapper: *const Global,
+            data: usize, /* void* */
+            callback: unsafe fn(tracker: Pin<&mut MemoryTracker>, data: usize) -> (),
+        );
+        pub fn memory_tracker_track_traced_data(
+            tracker: Pin<&mut MemoryTracker>,
+            edge_name: &str,
+            value: &TracedData,
+        );
+
+        // GC
+        pub unsafe fn traced_data_create(isolate: *mut Isolate, value: Local) -> KjOwn<TracedData>;
+        pub unsafe fn traced_data_clone(value: &TracedData) -> KjOwn<TracedData>;
+        pub unsafe fn traced_data_to_local(isolate: *mut Isolate, value: &TracedData) -> Local;
+        pub unsafe fn gc_tracer_create(isolate: *mut Isolate, realm: *mut Realm)
+        -> KjOwn<GcTracer>;
+        pub fn gc_tracer_visit(tracer: Pin<&mut GcTracer>, value: &TracedData, owner: usize);
+        pub fn cppgc_visitor_trace(visitor: Pin<&mut CppgcVisitor>, value: &TracedData);
+        pub unsafe fn resource_shim_create(
+            isolate: *mut Isolate,
+            object: &Local,
+            state: usize, /* ResourceState* */
+        ) -> *mut ResourceShim;
+        pub unsafe fn resource_shim_detach(shim: *mut ResourceShim);
+
+        // HandleScope
+        pub unsafe fn handle_scope_create(isolate: *mut Isolate) -> KjOwn<HandleScope>;
+        pub unsafe fn escapable_handle_scope_create(
+            isolate: *mut Isolate,
+        ) -> KjOwn<EscapableHandleScope>;
+        pub unsafe fn escapable_handle_scope_escape(
+            scope: Pin<&mut EscapableHandleScope>,
+            value: Local,
+        ) -> Local;
+
+        // I/O
+        pub unsafe fn await_io(isolate: *mut Isolate, io: Box<PendingIo>) -> bool;
     }
 }
 
@@ -44,6 +172,9 @@ fn get_resource_descriptor<R: Resource>() -> v8::ffi::ResourceDescriptor {
         constructor: KjMaybe::None,
         methods: Vec::new(),
         static_methods: Vec::new(),
+        static_constants: Vec::new(),
+        prototype_properties: Vec::new(),
+        instance_properties: Vec::new(),
     };
 
     for m in R::members() {
@@ -60,10 +191,31 @@ fn get_resource_descriptor<R: Resource>() -> v8::ffi::ResourceDescriptor {
                 });
             }
             Member::Property {
-                name: _,
-                getter_callback: _,
-                setter_callback: _,
-            } => todo!(),
+                name,
+                getter_callback,
+                setter_callback,
+            } => {
+                descriptor
+                    .prototype_properties
+                    .push(v8::ffi::PropertyDescriptor {
+                        name,
+                        getter_callback: getter_callback as usize,
+                        setter_callback: setter_callback.map_or(0, |cb| cb as usize),
+                    });
+            }
+            Member::InstanceProperty {
+                name,
+                getter_callback,
+                setter_callback,
+            } => {
+                descriptor
+                    .instance_properties
+                    .push(v8::ffi::PropertyDescriptor {
+                        name,
+                        getter_callback: getter_callback as usize,
+                        setter_callback: setter_callback.map_or(0, |cb| cb as usize),
+                    });
+            }
             Member::StaticMethod { name, callback } => {
                 descriptor
                     .static_methods
@@ -72,6 +224,11 @@ fn get_resource_descriptor<R: Resource>() -> v8::ffi::ResourceDescriptor {
                         callback: callback as usize,
                     });
             }
+            Member::StaticConstant { name, value } => {
+                descriptor
+                    .static_constants
+                    .push(v8::ffi::StaticConstantDescriptor { name, value });
+            }
         }
     }
 
@@ -92,11 +249,21 @@ pub fn create_resource_constructor<R: Resource>(
 /// # Safety
 /// The caller must ensure V8 operations are performed within the correct isolate/context and
 /// that the resource's lifetime is properly managed via the Realm.
-pub unsafe fn wrap_resource<'a, R: Resource + 'a, RT: ResourceTemplate>(
-    lock: &mut Lock,
-    mut resource: Ref<R>,
+pub unsafe fn wrap_resource<'s, R: Resource + 's, RT: ResourceTemplate>(
+    lock: &mut Lock<'s>,
+    resource: Ref<R>,
     resource_template: &mut RT,
-) -> v8::Local<'a, v8::Value> {
+) -> v8::Local<'s, v8::Value> {
+    unsafe { wrap_resource_with(lock, resource, resource_template.get_constructor()) }
+}
+
+/// Returns the wrapper of `resource`, creating it from `constructor` if the resource has not been
+/// wrapped yet.
+pub(crate) unsafe fn wrap_resource_with<'s, R: Resource + 's>(
+    lock: &mut Lock<'s>,
+    mut resource: Ref<R>,
+    constructor: &v8::Global<v8::FunctionTemplate>,
+) -> v8::Local<'s, v8::Value> {
     match resource
         .get_state()
         .strong_wrapper
@@ -105,14 +272,14 @@ pub unsafe fn wrap_resource<'a, R: Resource + 'a, RT: ResourceTemplate>(
     {
         Some(value) if value.has_value() => value.into(),
         _ => {
-            let constructor = resource_template.get_constructor();
-
             // Store the leaked Ref in ResourceState.this and the drop function
             let drop_fn = (*resource).get_drop_fn();
             resource.get_state().this = Ref::into_raw(resource.clone()).cast();
             resource.get_state().drop_fn = Some(drop_fn);
+            resource.get_state().memory_info_fn = Some(memory::track_resource_state::<R>);
+            resource.get_state().visit_for_gc_fn = Some(gc::visit_resource_state::<R>);
 
-            let instance: v8::Local<'a, v8::Value> = unsafe {
+            let instance: v8::Local<'s, v8::Value> = unsafe {
                 v8::Local::from_ffi(
                     lock.isolate(),
                     v8::ffi::wrap_resource(
@@ -126,13 +293,41 @@ pub unsafe fn wrap_resource<'a, R: Resource + 'a, RT: ResourceTemplate>(
             unsafe {
                 resource
                     .get_state()
-                    .attach_wrapper(lock.realm(), instance.clone().into());
+                    .attach_wrapper(lock.realm(), instance.clone().cast_unchecked());
             }
             instance
         }
     }
 }
 
+/// Attaches a Rust resource to an object created from the resource's template, e.g. `this`
+/// inside a constructor callback.
+///
+/// # Safety
+/// The caller must ensure `object` was instantiated from the `FunctionTemplate` of `R` and does
+/// not already wrap a resource.
+pub unsafe fn attach_resource<R: Resource>(
+    lock: &mut Lock,
+    mut resource: Ref<R>,
+    object: v8::Local<v8::Object>,
+) {
+    // Store the leaked Ref in ResourceState.this and the drop function
+    let drop_fn = (*resource).get_drop_fn();
+    resource.get_state().this = Ref::into_raw(resource.clone()).cast();
+    resource.get_state().drop_fn = Some(drop_fn);
+    resource.get_state().memory_info_fn = Some(memory::track_resource_state::<R>);
+    resource.get_state().visit_for_gc_fn = Some(gc::visit_resource_state::<R>);
+
+    unsafe {
+        v8::ffi::attach_resource(
+            lock.isolate().as_ffi(),
+            resource.get_state().this as usize,
+            object.as_ffi(),
+        );
+        resource.get_state().attach_wrapper(lock.realm(), object);
+    }
+}
+
 pub fn unwrap_resource<'a, R: Resource>(
     lock: &'a mut Lock,
     value: v8::Local<v8::Value>,
@@ -142,6 +337,22 @@ pub fn unwrap_resource<'a, R: Resource>(
     unsafe { &mut *ptr }
 }
 
+/// Unwraps a JavaScript object into a new strong reference to its Rust resource.
+///
+/// Unlike `unwrap_resource`, the returned `Ref` keeps the resource alive independently of the
+/// lock, e.g. across an `await` in an async method.
+pub fn unwrap_resource_ref<R: Resource>(lock: &mut Lock, value: v8::Local<v8::Value>) -> Ref<R> {
+    let ptr =
+        unsafe { v8::ffi::unwrap_resource(lock.isolate().as_ffi(), value.into_ffi()) as *mut R };
+    // The wrapper holds the `Ref` leaked in `ResourceState.this`; clone it rather than taking it.
+    let mut resource = unsafe {
+        Rc::increment_strong_count(ptr.cast::<UnsafeCell<R>>());
+        Ref::from_raw(ptr)
+    };
+    resource.get_state().retain_wrapper();
+    resource
+}
+
 impl From<&str> for ExceptionType {
     fn from(value: &str) -> Self {
         match value {
@@ -165,10 +376,74 @@ impl From<&str> for ExceptionType {
     }
 }
 
+/// A JavaScript error, thrown by returning it from a method or passing it to
+/// `Lock::throw_exception()`.
+///
+/// DOM-style names (`AbortError`, `NotFoundError`, etc.) are thrown as `DOMException` objects
+/// with the matching legacy `code`; the others are thrown as native JavaScript errors. Note that
+/// `SyntaxError` is always thrown as the JavaScript `SyntaxError`.
 #[derive(Debug, Clone)]
 pub struct Error {
     pub name: ExceptionType,
     pub message: String,
+    /// The error that caused this one, exposed to JavaScript as the `cause` property.
+    pub cause: Option<Box<Self>>,
+    /// Additional properties set on the thrown object, e.g. Node.js's `code`.
+    pub properties: Vec<(String, ErrorProperty)>,
+}
+
+/// The value of a custom property set on an error with `Error::with_property()`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorProperty {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+}
+
+impl From<&str> for ErrorProperty {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for ErrorProperty {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<f64> for ErrorProperty {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i32> for ErrorProperty {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<u32> for ErrorProperty {
+    fn from(value: u32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<bool> for ErrorProperty {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl ToJS for ErrorProperty {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        match self {
+            Self::String(value) => value.to_js(lock),
+            Self::Number(value) => value.to_js(lock),
+            Self::Boolean(value) => value.to_js(lock),
+        }
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -184,10 +459,7 @@ macro_rules! impl_error_constructors {
         impl Error {
             $(
                 pub fn $fn_name(message: impl Into<String>) -> Self {
-                    Self {
-                        name: ExceptionType::$variant,
-                        message: message.into(),
-                    }
+                    Self::with_name(ExceptionType::$variant, message)
                 }
             )*
         }
@@ -213,52 +485,162 @@ impl_error_constructors! {
     ReferenceError => new_reference_error,
 }
 
+/// How many nested `cause` errors `Error::from_js` follows, which also guards against cycles.
+const MAX_CAUSE_DEPTH: usize = 16;
+
 impl FromJS for Error {
     type ResultType = Self;
 
     /// Creates an Error from a V8 value (typically an exception).
     ///
-    /// If the value is a native error, extracts the name and message properties.
-    /// Otherwise, converts the value to a string for the message.
+    /// If the value is a native error or a `DOMException`, extracts the name and message
+    /// properties, along with the `cause` and a Node.js-style string `code` if present.
+    /// Otherwise, converts the value to a string for the message. `DOMException` objects are
+    /// recognized through the embedder's `jsg::Lock`, or without it, e.g. in a GC callback,
+    /// through the `DOMException` global of the current context.
     fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
-        if value.is_native_error() {
-            let obj: v8::Local<v8::Object> = value.into();
-
-            let name = obj
-                .get(lock, "name")
-                .and_then(|v| String::from_js(lock, v).ok());
-
-            let message = obj
-                .get(lock, "message")
-                .and_then(|v| String::from_js(lock, v).ok())
-                .unwrap_or_else(|| "Unknown error".to_owned());
-
-            Ok(Self {
-                name: name.map_or(ExceptionType::Error, |n| ExceptionType::from(n.as_str())),
-                message,
-            })
-        } else {
-            Err(Self::new_type_error("Unknown error"))
-        }
+        Self::from_js_with_depth(lock, value, 0)
     }
 }
 
 impl Error {
     pub fn new(name: &str, message: &str) -> Self {
+        Self::with_name(ExceptionType::from(name), message)
+    }
+
+    fn with_name(name: ExceptionType, message: impl Into<String>) -> Self {
         Self {
-            name: ExceptionType::from(name),
-            message: message.to_owned(),
+            name,
+            message: message.into(),
+            cause: None,
+            properties: Vec::new(),
+        }
+    }
+
+    /// Sets the error that caused this one.
+    #[must_use]
+    pub fn with_cause(mut self, cause: Self) -> Self {
+        self.cause = Some(Box::new(cause));
+        self
+    }
+
+    /// Sets a custom property on the thrown object, replacing an earlier value for `name`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// Error::new_type_error("The \"name\" argument must be of type string")
+    ///     .with_property("code", "ERR_INVALID_ARG_TYPE")
+    /// ```
+    #[must_use]
+    pub fn with_property(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<ErrorProperty>,
+    ) -> Self {
+        let name = name.into();
+        let value = value.into();
+        match self.properties.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.properties.push((name, value)),
+        }
+        self
+    }
+
+    /// Returns the custom property `name`, if set.
+    pub fn property(&self, name: &str) -> Option<&ErrorProperty> {
+        self.properties
+            .iter()
+            .find_map(|(key, value)| (key == name).then_some(value))
+    }
+
+    fn from_js_with_depth(
+        lock: &mut Lock,
+        value: v8::Local<v8::Value>,
+        depth: usize,
+    ) -> Result<Self, Self> {
+        let is_dom_exception =
+            unsafe { v8::ffi::exception_is_dom_exception(lock.isolate().as_ffi(), value.as_ffi()) };
+        if !value.is_native_error() && !is_dom_exception {
+            return Err(Self::new_type_error("Unknown error"));
+        }
+        let obj = value.try_into_object()?;
+
+        let name = obj
+            .get(lock, "name")
+            .ok()
+            .filter(|v| !v.is_undefined())
+            .and_then(|v| String::from_js(lock, v).ok());
+
+        let message = obj
+            .get(lock, "message")
+            .ok()
+            .filter(|v| !v.is_undefined())
+            .and_then(|v| String::from_js(lock, v).ok())
+            .unwrap_or_else(|| "Unknown error".to_owned());
+
+        let mut error = Self::with_name(
+            name.map_or(ExceptionType::Error, |n| ExceptionType::from(n.as_str())),
+            message,
+        );
+
+        if depth < MAX_CAUSE_DEPTH {
+            error.cause = obj
+                .get(lock, "cause")
+                .ok()
+                .and_then(|v| Self::from_js_with_depth(lock, v, depth + 1).ok())
+                .map(Box::new);
+        }
+
+        // A DOMException's `code` is derived from its name.
+        if !is_dom_exception {
+            let code = obj
+                .get(lock, "code")
+                .ok()
+                .filter(v8::Local::is_string)
+                .and_then(|v| String::from_js(lock, v).ok());
+            if let Some(code) = code {
+                error.properties.push(("code".to_owned(), code.into()));
+            }
         }
+        Ok(error)
     }
 
     /// Creates a V8 exception from this error.
-    pub fn to_local<'a>(&self, isolate: v8::IsolatePtr) -> v8::Local<'a, v8::Value> {
-        unsafe {
+    ///
+    /// `DOMException` objects are created through the embedder's `jsg::Lock` for the isolate,
+    /// which is held in methods called from JavaScript and in the callbacks of
+    /// `Lock::await_io()`. Without it, e.g. in a GC callback, a DOM error is created as a plain
+    /// `Error` whose `name` is set to the DOM name, which `Error::from_js()` still reads back.
+    pub fn to_local<'s>(&self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        let isolate = lock.isolate();
+        let exception = unsafe {
             v8::Local::from_ffi(
                 isolate,
                 v8::ffi::exception_create(isolate.as_ffi(), self.name, &self.message),
             )
+        };
+        if self.cause.is_none() && self.properties.is_empty() {
+            return exception;
+        }
+
+        if let Some(cause) = &self.cause {
+            let cause = cause.to_local(lock);
+            unsafe {
+                v8::ffi::exception_set_cause(
+                    isolate.as_ffi(),
+                    exception.as_ffi(),
+                    cause.into_ffi(),
+                );
+            }
+        }
+        if let Some(mut object) = exception.try_cast::<v8::Object>() {
+            for (name, value) in &self.properties {
+                let value = value.clone().to_js(lock);
+                object.set(lock, name, value);
+            }
         }
+        exception
     }
 }
 
@@ -345,6 +727,103 @@ impl<T> Deref for NonCoercible<T> {
     }
 }
 
+/// Applies the Web IDL `[EnforceRange]` extended attribute to an integer parameter.
+///
+/// By default, integers are converted like Web IDL integer types without extended attributes:
+/// the value is truncated and wrapped into range, so `300` passed as a `u8` becomes `44`.
+/// `EnforceRange<T>` instead throws a `TypeError` if the value is not finite or, once
+/// truncated, lies outside the range of `T`. For `i64`, `u64` and `usize`, numbers are limited
+/// to the safe integer range, while a `BigInt` passed as `i64` or `u64` must fit exactly.
+///
+/// # Example
+///
+/// ```ignore
+/// use jsg::EnforceRange;
+///
+/// #[jsg_method]
+/// pub fn set_port(&mut self, port: EnforceRange<u16>) {
+///     self.port = port.into_inner();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnforceRange<T> {
+    value: T,
+}
+
+impl<T> EnforceRange<T> {
+    /// Creates a new `EnforceRange` wrapper around the given value.
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Consumes the wrapper and returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> From<T> for EnforceRange<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Deref for EnforceRange<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Applies the Web IDL `[Clamp]` extended attribute to an integer parameter.
+///
+/// Instead of wrapping out-of-range values, `Clamp<T>` clamps the value to the range of `T`
+/// and rounds it to the nearest integer, with ties to even. `NaN` becomes `0`. For `i64`,
+/// `u64` and `usize`, numbers are clamped to the safe integer range, while a `BigInt` passed
+/// as `i64` or `u64` is clamped to the full range of the type.
+///
+/// # Example
+///
+/// ```ignore
+/// use jsg::Clamp;
+///
+/// #[jsg_method]
+/// pub fn set_opacity(&mut self, opacity: Clamp<u8>) {
+///     self.opacity = opacity.into_inner();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Clamp<T> {
+    value: T,
+}
+
+impl<T> Clamp<T> {
+    /// Creates a new `Clamp` wrapper around the given value.
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Consumes the wrapper and returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> From<T> for Clamp<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> Deref for Clamp<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
 /// A wrapper type that accepts `null`, `undefined`, or a value of type `T`.
 ///
 /// `Nullable<T>` is similar to `Option<T>` but also accepts `undefined` as a null-ish value.
@@ -432,20 +911,155 @@ impl<T> From<Nullable<T>> for Option<T> {
     }
 }
 
+/// A Web IDL `sequence<T>`: accepts any iterable JavaScript object and collects its values.
+///
+/// `Vec<T>` only accepts JavaScript arrays, analogous to `kj::Array<T>` in C++ JSG.
+/// `Sequence<T>` is analogous to `jsg::Sequence<T>` and additionally accepts iterables such as
+/// `Set`s or generators, which are consumed through their `Symbol.iterator` method. Each value
+/// is unwrapped as `T`; errors name the index of the offending element. Converting a
+/// `Sequence<T>` back to JavaScript produces an array.
+///
+/// # Example
+///
+/// ```ignore
+/// use jsg::Sequence;
+///
+/// #[jsg_method]
+/// pub fn join(&self, parts: Sequence<String>) -> String {
+///     parts.join(",")
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence<T> {
+    values: Vec<T>,
+}
+
+impl<T> Sequence<T> {
+    /// Creates a new `Sequence` holding the given values.
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+
+    /// Consumes the sequence and returns the collected values.
+    pub fn into_inner(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T> From<Vec<T>> for Sequence<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self::new(values)
+    }
+}
+
+impl<T> Deref for Sequence<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+/// The remaining arguments of a method call, analogous to `jsg::Arguments<T>` in C++ JSG.
+///
+/// Declared as the last parameter of a `#[jsg_method]`, `jsg::Arguments<T>` collects every
+/// argument from its position onwards, each unwrapped as `T`, like a JavaScript rest parameter. It
+/// is empty if the caller passed no further arguments. The parameter's type must be written as
+/// `jsg::Arguments<T>` for the macro to recognize it.
+///
+/// # Example
+///
+/// ```ignore
+/// #[jsg_method]
+/// pub fn log(&self, level: String, values: jsg::Arguments<jsg::Value>) {
+///     self.write(&level, values.len());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arguments<T> {
+    values: Vec<T>,
+}
+
+impl<T> Arguments<T> {
+    /// Creates new `Arguments` holding the given values.
+    pub fn new(values: Vec<T>) -> Self {
+        Self { values }
+    }
+
+    /// Consumes the arguments and returns the unwrapped values.
+    pub fn into_inner(self) -> Vec<T> {
+        self.values
+    }
+}
+
+impl<T: FromJS<ResultType = T>> Arguments<T> {
+    /// Unwraps the arguments of `args` from index `start` onwards. Called by the code
+    /// `#[jsg_method]` generates.
+    ///
+    /// An error names the position of the argument that failed to unwrap, counting from 1.
+    #[doc(hidden)]
+    pub fn from_args(
+        lock: &mut Lock,
+        args: &v8::FunctionCallbackInfo,
+        start: usize,
+    ) -> Result<Self, Error> {
+        (start..args.len())
+            .map(|index| {
+                T::from_js(lock, args.get(index)).map_err(|err| Error {
+                    message: format!("Incorrect type for argument {}: {}", index + 1, err.message),
+                    ..err
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+}
+
+impl<T> From<Vec<T>> for Arguments<T> {
+    fn from(values: Vec<T>) -> Self {
+        Self::new(values)
+    }
+}
+
+impl<T> Deref for Arguments<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl<T> IntoIterator for Arguments<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
 /// Provides access to V8 operations within an isolate lock.
 ///
 /// A Lock wraps a V8 isolate pointer and is passed to resource methods and callbacks to
 /// perform V8 operations like creating objects, wrapping values, and accessing the Realm.
 /// This is analogous to `jsg::Lock` in C++ JSG.
-pub struct Lock {
+///
+/// `'s` brands the `HandleScope` the lock creates handles in: every `v8::Local<'s, T>` created
+/// through a `Lock<'s>` lives no longer than that scope. `within_handle_scope()` runs a callback
+/// with a `Lock` of its own, whose handles cannot outlive the callback.
+pub struct Lock<'s> {
     isolate: v8::IsolatePtr,
+    _scope: PhantomData<&'s ()>,
 }
 
-impl Lock {
+impl<'s> Lock<'s> {
     /// # Safety
     /// The caller must ensure that `args` is a valid pointer to `FunctionCallbackInfo`.
     pub unsafe fn from_args(args: *mut v8::ffi::FunctionCallbackInfo) -> Self {
-        unsafe { Self::from_isolate_ptr(v8::ffi::fci_get_isolate(args)) }
+        let mut lock = unsafe { Self::from_isolate_ptr(v8::ffi::fci_get_isolate(args)) };
+        // Makes weak the wrappers of resources released without the lock since it was last held.
+        lock.realm();
+        lock
     }
 
     /// Creates a Lock from a raw isolate pointer.
@@ -455,6 +1069,7 @@ impl Lock {
     pub unsafe fn from_isolate_ptr(isolate: *mut v8::ffi::Isolate) -> Self {
         Self {
             isolate: unsafe { v8::IsolatePtr::from_ffi(isolate) },
+            _scope: PhantomData,
         }
     }
 
@@ -463,7 +1078,71 @@ impl Lock {
         self.isolate
     }
 
-    pub fn new_object<'a>(&mut self) -> v8::Local<'a, v8::Object> {
+    /// Returns the context of the JavaScript code running on the isolate, like
+    /// `jsg::Lock::v8Context()` in C++ JSG. A context must be entered, as it is while JavaScript
+    /// calls into Rust.
+    pub fn current_context(&mut self) -> v8::Local<'s, v8::Context> {
+        unsafe {
+            v8::Local::from_ffi(
+                self.isolate(),
+                v8::ffi::isolate_get_current_context(self.isolate().as_ffi()),
+            )
+        }
+    }
+
+    /// Stores `value` in the current context, replacing the value of the same type stored
+    /// previously, if any, which is returned.
+    ///
+    /// The value is dropped when the context is disposed, along with the Rust resources it
+    /// wrapped. Each context has its own values, so the state of a module can be kept per context
+    /// instead of in a global.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// struct Counters(HashMap<String, u64>);
+    ///
+    /// lock.set_context_data(Counters(HashMap::new()));
+    /// if let Some(counters) = lock.context_data::<Counters>() {
+    ///     *counters.0.entry(label).or_default() += 1;
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedder did not set up the current context with
+    /// `realm_create_for_context()`, since the value could keep the context alive.
+    pub fn set_context_data<T: 'static>(&mut self, value: T) -> Option<T> {
+        let realm = self.realm();
+        assert!(
+            !realm.collected_with_context,
+            "context data cannot be stored in a context the embedder did not set up"
+        );
+        realm
+            .context_data
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|previous| previous.downcast::<T>().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Returns the value of type `T` stored in the current context by `set_context_data()`.
+    pub fn context_data<T: 'static>(&mut self) -> Option<&mut T> {
+        self.realm()
+            .context_data
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut::<T>())
+    }
+
+    /// Removes the value of type `T` stored in the current context, returning it.
+    pub fn take_context_data<T: 'static>(&mut self) -> Option<T> {
+        self.realm()
+            .context_data
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast::<T>().ok())
+            .map(|value| *value)
+    }
+
+    pub fn new_object(&mut self) -> v8::Local<'s, v8::Object> {
         unsafe {
             v8::Local::from_ffi(
                 self.isolate(),
@@ -472,25 +1151,86 @@ impl Lock {
         }
     }
 
-    pub fn await_io<F, C, I, R>(self, _fut: F, _callback: C) -> Result<R>
+    /// Awaits `future` on the KJ event loop, then re-enters the isolate lock and passes its output
+    /// to `callback`.
+    ///
+    /// Returns a promise that is resolved with the callback's result, or rejected with its error.
+    /// This is analogous to `IoContext::awaitIo()` in C++. The embedder's `IoScheduler` for the
+    /// current context takes its own lock to run `callback`, like for any other event, and
+    /// cancels the future if the context is disposed before it completes. The promise is rejected
+    /// if I/O cannot be awaited in the current context, e.g. outside of a request or in a context
+    /// the embedder did not set up.
+    pub fn await_io<F, C, I, R>(&mut self, future: F, callback: C) -> v8::Local<'s, v8::Promise>
     where
-        F: Future<Output = I>,
-        C: FnOnce(Self, I) -> Result<R>,
+        F: Future<Output = I> + 'static,
+        I: 'static,
+        C: FnOnce(&mut Lock, I) -> Result<R> + 'static,
+        R: ToJS,
     {
-        todo!()
+        let resolver = v8::Local::<v8::PromiseResolver>::new(self);
+        let promise = resolver.get_promise(self);
+        let global_resolver = v8::Global::from(resolver.clone());
+
+        let future = async move {
+            let output = future.await;
+            let continuation: IoContinuation = Box::new(move |lock: &mut Lock| {
+                let resolver = global_resolver.as_local(lock);
+                match callback(lock, output) {
+                    Ok(value) => {
+                        let value = value.to_js(lock);
+                        resolver.resolve(lock, value);
+                    }
+                    Err(err) => {
+                        let exception = err.to_local(lock);
+                        resolver.reject(lock, exception);
+                    }
+                }
+            });
+            continuation
+        };
+
+        let io = Box::new(PendingIo {
+            future: Box::pin(future),
+            continuation: None,
+        });
+        if !unsafe { ffi::await_io(self.isolate().as_ffi(), io) } {
+            let exception =
+                Error::new_error("I/O cannot be awaited in this context").to_local(self);
+            resolver.reject(self, exception);
+        }
+        promise
+    }
+
+    /// Returns the function template for resources of type `R`, creating it on first use.
+    ///
+    /// The template is cached in the Realm of the current context, so every call returns the same
+    /// template for as long as the context lives. Constructors obtained from it, and wrappers created from it by
+    /// `ToJS`, all share one JavaScript class, so `instanceof` checks and resource type checks
+    /// behave consistently.
+    pub fn get_template<R: Resource + 'static>(&mut self) -> v8::Global<v8::FunctionTemplate> {
+        let key = TypeId::of::<R>();
+        if let Some(template) = self.realm().templates.get(&key) {
+            return template.clone();
+        }
+        let template = create_resource_constructor::<R>(self);
+        self.realm().templates.insert(key, template.clone());
+        template
     }
 
+    /// Returns the Realm of the current context, first making weak the wrappers of the resources
+    /// it tracks that were released without the isolate lock.
     fn realm(&mut self) -> &mut Realm {
-        unsafe { &mut *crate::ffi::realm_from_isolate(self.isolate().as_ffi()) }
+        let context = self.current_context();
+        let realm = unsafe { &mut *crate::ffi::realm_from_context(context.as_ffi()) };
+        realm.release_deferred_wrappers();
+        realm
     }
 
-    /// Throws an error as a V8 exception.
+    /// Throws an error as a V8 exception, created by `Error::to_local()`.
     pub fn throw_exception(&mut self, err: &Error) {
+        let exception = err.to_local(self);
         unsafe {
-            v8::ffi::isolate_throw_exception(
-                self.isolate().as_ffi(),
-                err.to_local(self.isolate()).into_ffi(),
-            );
+            v8::ffi::isolate_throw_exception(self.isolate().as_ffi(), exception.into_ffi());
         }
     }
 }
@@ -531,7 +1271,9 @@ impl<T: Resource> Ref<T> {
     }
 
     pub fn into_raw(r: Self) -> *mut T {
-        UnsafeCell::raw_get(Rc::into_raw(r.val))
+        // The strong count is kept for the returned pointer, so `r` must not be dropped.
+        let r = ManuallyDrop::new(r);
+        UnsafeCell::raw_get(Rc::as_ptr(&r.val))
     }
 
     /// Reconstructs a `Ref<T>` from a raw pointer.
@@ -546,6 +1288,18 @@ impl<T: Resource> Ref<T> {
             val: unsafe { Rc::from_raw(this.cast::<UnsafeCell<T>>()) },
         }
     }
+
+    /// Returns true if no other `Ref` refers to the resource, including the one held by its
+    /// wrapper.
+    pub(crate) fn is_unique(&self) -> bool {
+        Rc::strong_count(&self.val) == 1
+    }
+
+    /// Returns the JavaScript wrapper of the resource, if it is currently wrapped.
+    pub(crate) fn wrapper(&self) -> Option<&v8::Global<v8::Object>> {
+        let resource = unsafe { &mut *self.val.get() };
+        resource.get_state().strong_wrapper.as_ref()
+    }
 }
 
 impl<T: Resource> Clone for Ref<T> {
@@ -556,13 +1310,21 @@ impl<T: Resource> Clone for Ref<T> {
     }
 }
 
+impl<T: Resource> Drop for Ref<T> {
+    fn drop(&mut self) {
+        // Once this `Ref` is dropped, only the one owned by the wrapper may remain.
+        if Rc::strong_count(&self.val) == 2 {
+            let resource = unsafe { &mut *self.val.get() };
+            resource.get_state().release_wrapper();
+        }
+    }
+}
+
 /// Provides metadata about Rust types exposed to JavaScript.
 ///
 /// This trait provides type information used for error messages, memory tracking,
 /// and type validation (for `NonCoercible<T>`). The actual conversion logic is in
 /// `ToJS` (Rust → JS) and `FromJS` (JS → Rust).
-///
-/// TODO: Implement `memory_info(jsg::MemoryTracker)`
 pub trait Type: Sized {
     /// The JavaScript class name for this type (used in error messages).
     fn class_name() -> &'static str;
@@ -577,6 +1339,22 @@ pub trait Type: Sized {
         std::mem::size_of::<Self>()
     }
 
+    /// Same as jsgGetMemoryInfo. Tracks the memory this value owns in addition to
+    /// `memory_self_size()`, such as heap allocations and referenced resources.
+    fn memory_info(&self, _tracker: &mut MemoryTracker) {}
+
+    /// Tracks this value as the field `edge_name` of the object being tracked. Used by
+    /// `MemoryTracker::track_field()` so that `Ref<R>` and wrappers such as `Option<T>` can
+    /// track the values they refer to instead of themselves.
+    #[doc(hidden)]
+    fn track_memory(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+        tracker.track_inline(edge_name, self);
+    }
+
+    /// Same as jsgVisitForGc. Visits the JavaScript values this value holds, such as `V8Ref<T>`
+    /// fields, so that V8 can trace through them while tracing the resource holding this value.
+    fn visit_for_gc(&self, _visitor: &mut GcVisitor) {}
+
     /// Returns true if the V8 value is exactly this type (no coercion).
     /// Used by `NonCoercible<T>` to reject values that would require coercion.
     fn is_exact(value: &v8::Local<v8::Value>) -> bool;
@@ -590,29 +1368,57 @@ pub enum Member {
         name: String,
         callback: unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo),
     },
+    /// An accessor property installed on the prototype, analogous to `JSG_PROTOTYPE_PROPERTY`.
+    /// The property is read-only if `setter_callback` is `None`.
     Property {
         name: String,
         getter_callback: unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo),
-        setter_callback: unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo),
+        setter_callback: Option<unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo)>,
+    },
+    /// An accessor property installed on each instance, analogous to `JSG_INSTANCE_PROPERTY`.
+    /// The property is read-only if `setter_callback` is `None`.
+    InstanceProperty {
+        name: String,
+        getter_callback: unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo),
+        setter_callback: Option<unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo)>,
     },
+    /// A function installed on the constructor, analogous to `JSG_STATIC_METHOD`.
     StaticMethod {
         name: String,
         callback: unsafe extern "C" fn(*mut v8::ffi::FunctionCallbackInfo),
     },
+    /// A read-only numeric value installed on both the constructor and the prototype, analogous
+    /// to `JSG_STATIC_CONSTANT`.
+    StaticConstant { name: String, value: f64 },
 }
 
 /// Tracks the V8 wrapper object for a Rust resource.
 ///
 /// Each Resource embeds a `ResourceState` to maintain the connection between the Rust object and
-/// its JavaScript wrapper. When a resource is wrapped, the `ResourceState` stores a weak V8 handle
-/// to the wrapper object along with pointers needed for cleanup: the leaked `Ref<R>` pointer
-/// and the drop function to reconstruct it. The Realm uses this state to perform deterministic
-/// cleanup when the context is disposed.
+/// its JavaScript wrapper. When a resource is wrapped, the `ResourceState` stores a V8 handle to
+/// the wrapper object along with pointers needed for cleanup: the leaked `Ref<R>` pointer and the
+/// drop function to reconstruct it. Like the strong refcount of `Wrappable` in C++ JSG, the handle
+/// is strong while Rust code holds any other `Ref<R>` to the resource, so that JavaScript keeps
+/// seeing the same object, and weak otherwise, unless the Realm is dropped with its context. The leaked `Ref<R>` is released when V8 garbage
+/// collects the wrapper, or when the context is disposed, whichever comes first. While wrapped,
+/// the Realm reports the resource in heap snapshots through `memory_info_fn`, and V8 traces the
+/// values held by the resource through `visit_for_gc_fn` when it traces the wrapper.
 pub struct ResourceState {
     pub this: *mut c_void,
     pub drop_fn: Option<unsafe extern "C" fn(*mut v8::ffi::Isolate, *mut c_void)>,
+    pub memory_info_fn: Option<fn(&mut MemoryTracker, &Self)>,
+    pub visit_for_gc_fn: Option<fn(&mut GcVisitor, &Self)>,
     pub strong_wrapper: Option<v8::Global<v8::Object>>,
     pub isolate: Option<v8::IsolatePtr>,
+    /// The Realm of the context the wrapper was created in, which tracks the resource until the
+    /// wrapper is collected.
+    realm: *mut Realm,
+    /// The cppgc object tracing the resource on behalf of its wrapper. Cleared by the shim if it
+    /// is collected first.
+    shim: *mut ffi::ResourceShim,
+    /// Whether the wrapper is to be made weak the next time the isolate is locked, because the
+    /// last `Ref<R>` besides the wrapper's was dropped without the lock.
+    release_deferred: bool,
 }
 
 impl Default for ResourceState {
@@ -620,8 +1426,13 @@ impl Default for ResourceState {
         Self {
             this: std::ptr::null_mut(),
             drop_fn: None,
+            memory_info_fn: None,
+            visit_for_gc_fn: None,
             strong_wrapper: None,
             isolate: None,
+            realm: std::ptr::null_mut(),
+            shim: std::ptr::null_mut(),
+            release_deferred: false,
         }
     }
 }
@@ -635,29 +1446,117 @@ impl ResourceState {
     /// - This method is called from the correct V8 isolate/context
     /// - `self.this` pointer remains valid until either the weak callback fires or `Realm::drop()` is called
     ///
+    /// The wrapper is strong until the caller drops its own `Ref<R>` to the resource, or the last
+    /// one besides the wrapper's.
+    ///
     /// # Panics
     /// Panics if a wrapper has already been attached (i.e., `strong_wrapper` is not `None`).
     pub unsafe fn attach_wrapper(&mut self, realm: &mut Realm, object: v8::Local<v8::Object>) {
         assert!(self.strong_wrapper.is_none());
 
+        self.shim = unsafe {
+            ffi::resource_shim_create(
+                realm.isolate().as_ffi(),
+                object.as_ffi(),
+                std::ptr::from_mut(self) as usize,
+            )
+        };
         self.strong_wrapper = Some(object.into());
         self.isolate = Some(realm.isolate());
+        self.realm = realm;
 
         realm.add_resource(NonNull::from(&mut *self));
+        if realm.collected_with_context {
+            self.make_wrapper_weak();
+        }
+    }
+
+    /// Returns true if the wrapper stays weak while Rust code holds a `Ref<R>`, because its Realm
+    /// must not keep the context alive.
+    fn keeps_wrapper_weak(&self) -> bool {
+        !self.realm.is_null() && unsafe { (*self.realm).collected_with_context }
+    }
+
+    /// Makes the wrapper strong once Rust code holds a `Ref<R>` besides the wrapper's.
+    fn retain_wrapper(&mut self) {
+        if self.keeps_wrapper_weak() {
+            return;
+        }
+        if self.release_deferred {
+            // The wrapper was never made weak.
+            self.release_deferred = false;
+            let this = NonNull::from(&mut *self);
+            let realm = unsafe { &mut *self.realm };
+            realm.deferred_releases.retain(|resource| *resource != this);
+        } else if let Some(wrapper) = self.strong_wrapper.as_mut() {
+            wrapper.clear_weak();
+        }
+    }
 
-        let Some(wrapper) = self.strong_wrapper.as_mut() else {
-            unreachable!("This should not happen")
+    /// Makes the wrapper weak once only the wrapper holds a `Ref<R>`, so that V8 can collect it.
+    ///
+    /// V8 can only be called under the isolate lock, so if the `Ref<R>` is dropped without it,
+    /// e.g. by a future on the KJ event loop, the Realm makes the wrapper weak later.
+    fn release_wrapper(&mut self) {
+        let Some(isolate) = self.isolate else {
+            return;
         };
-        let isolate = self.isolate.expect("isolate should be set");
+        if self.strong_wrapper.is_none() || self.keeps_wrapper_weak() {
+            return;
+        }
+        if unsafe { isolate.is_locked() } {
+            self.make_wrapper_weak();
+        } else if !self.release_deferred {
+            self.release_deferred = true;
+            // The Realm outlives the wrapper, which the leaked `Ref<R>` keeps alive until then.
+            let realm = unsafe { &mut *self.realm };
+            realm.deferred_releases.push(NonNull::from(&mut *self));
+        }
+    }
+
+    fn make_wrapper_weak(&mut self) {
+        let data = (&raw mut *self).cast();
+        if let (Some(wrapper), Some(isolate)) = (self.strong_wrapper.as_mut(), self.isolate) {
+            unsafe { wrapper.make_weak(isolate, data, Self::weak_callback) };
+        }
+    }
+
+    /// Called by V8 when the wrapper is garbage collected. `data` points to the `ResourceState`.
+    ///
+    /// The state is unregistered from the Realm and the leaked `Ref<R>` is dropped. The wrapper is
+    /// only weak while no other `Ref<R>` remains, so this drops the resource.
+    fn weak_callback(_isolate: *mut v8::ffi::Isolate, data: usize) {
+        let state = data as *mut Self;
         unsafe {
-            wrapper.make_weak(isolate, self.this, Self::weak_callback);
+            // The callback no longer fires once the Realm is dropped, so the Realm is still alive.
+            let realm = &mut *(*state).realm;
+            realm.remove_resource(NonNull::new_unchecked(state));
+            (*state).detach();
         }
     }
 
-    fn weak_callback(_isolate: *mut v8::ffi::Isolate, _data: usize) {
-        // TODO: Implement GC-based cleanup for resources that outlive their context.
-        // Current resources like DnsUtil don't require GC cleanup.
-        // All cleanup happens deterministically in Realm::drop() during context disposal.
+    /// Releases the wrapper and drops the leaked `Ref<R>`.
+    ///
+    /// # Safety
+    /// The wrapper must no longer be weak, or its weak callback must be running. If no other
+    /// `Ref<R>` remains, this frees the resource, so `self` must not be accessed afterwards.
+    unsafe fn detach(&mut self) {
+        // The wrapper may be traced again if it is still alive, e.g. when the Realm is dropped.
+        if !self.shim.is_null() {
+            let shim = std::mem::replace(&mut self.shim, std::ptr::null_mut());
+            unsafe { ffi::resource_shim_detach(shim) };
+        }
+        // Dropping the handle resets it, as V8 requires of weak callbacks.
+        self.strong_wrapper = None;
+        self.realm = std::ptr::null_mut();
+        self.release_deferred = false;
+        let this = std::mem::replace(&mut self.this, std::ptr::null_mut());
+        if let Some(drop_fn) = self.drop_fn.take()
+            && let Some(isolate) = self.isolate.take()
+            && !this.is_null()
+        {
+            unsafe { drop_fn(isolate.as_ffi(), this) };
+        }
     }
 }
 
@@ -680,13 +1579,12 @@ pub trait Resource: Type {
     fn get_state(&mut self) -> &mut ResourceState;
 }
 
-/// Caches the V8 `FunctionTemplate` for a resource type.
+/// Holds the V8 `FunctionTemplate` for a resource type.
 ///
-/// A `ResourceTemplate` is created per Lock and caches the V8 function template used to
-/// instantiate JavaScript wrappers for a resource type. This avoids recreating the template
-/// on every wrap operation.
+/// The generated implementations obtain the template from `Lock::get_template()`, so creating a
+/// `ResourceTemplate` is cheap and every instance refers to the same template.
 pub trait ResourceTemplate {
-    /// Creates a new template for the given lock, initializing the V8 function template.
+    /// Returns the template for the given lock, creating the V8 function template on first use.
     fn new(lock: &mut Lock) -> Self;
 
     /// Returns the cached V8 function template used to create wrappers.
@@ -719,26 +1617,90 @@ pub unsafe fn drop_resource<R: Resource>(_isolate: *mut ffi::Isolate, this: *mut
 /// create stable pointers that outlive V8's GC. The Realm tracks all such leaked resources
 /// and ensures deterministic cleanup when the V8 context is disposed.
 ///
-/// A Realm is created per V8 context and stored in the context's embedder data. Resources
-/// register themselves via `add_resource()` during wrapping. When the context is torn down,
-/// `Drop` iterates all tracked resources and calls their drop functions to reconstruct and
-/// free any leaked `Ref<R>` values for wrappers not yet collected by V8's GC.
+/// A Realm is created per V8 context and stored in the context's embedder data, see
+/// `realm_create_for_context()` in `ffi.h`, so that an isolate running several contexts keeps
+/// the resources of each apart. Resources register themselves with the Realm of the current
+/// context via `add_resource()` during wrapping, and are unregistered when V8's GC collects their
+/// wrapper. When the context is disposed, `Drop` iterates all tracked resources and calls their
+/// drop functions to reconstruct and free any leaked `Ref<R>` values for wrappers not yet
+/// collected by V8's GC.
+///
+/// The Realm also caches the `FunctionTemplate` of each resource type, see `Lock::get_template()`,
+/// holds the values stored by `Lock::set_context_data()`, adds the resources it tracks to heap
+/// snapshots of the isolate, and lets V8 trace the values they hold during full GCs.
+///
+/// A Realm created on first use in a context the embedder did not set up is dropped once V8
+/// collects the context instead, so it must not keep the context alive: the wrappers of its
+/// resources stay weak even while Rust code holds a `Ref<R>`, and it stores no context data.
 pub struct Realm {
     isolate: v8::IsolatePtr,
-    resources: Vec<*mut ResourceState>,
+    /// Whether the Realm is dropped once its context is collected rather than disposed by the
+    /// embedder.
+    collected_with_context: bool,
+    resources: HashSet<*mut ResourceState>,
+    templates: HashMap<TypeId, v8::Global<v8::FunctionTemplate>>,
+    context_data: HashMap<TypeId, Box<dyn Any>>,
+    /// Resources whose wrappers are made weak the next time Rust code or the GC takes the isolate
+    /// lock, because the last `Ref<R>` besides the wrapper's was dropped without it.
+    deferred_releases: Vec<NonNull<ResourceState>>,
+    pending_reactions: promise::PendingReactions,
+    gc_tracer: Option<KjOwn<ffi::GcTracer>>,
 }
 
 impl Realm {
-    /// Creates a new Realm from a V8 isolate.
-    pub fn from_isolate(isolate: v8::IsolatePtr) -> Self {
+    /// Creates a new Realm for a context of `isolate`.
+    pub fn new(isolate: v8::IsolatePtr) -> Self {
         Self {
             isolate,
-            resources: Vec::new(),
+            collected_with_context: false,
+            resources: HashSet::new(),
+            templates: HashMap::new(),
+            context_data: HashMap::new(),
+            deferred_releases: Vec::new(),
+            pending_reactions: promise::PendingReactions::default(),
+            gc_tracer: None,
         }
     }
 
     pub fn add_resource(&mut self, resource: NonNull<ResourceState>) {
-        self.resources.push(resource.as_ptr());
+        self.resources.insert(resource.as_ptr());
+    }
+
+    fn remove_resource(&mut self, resource: NonNull<ResourceState>) {
+        self.resources.remove(&resource.as_ptr());
+    }
+
+    /// Makes weak the wrappers of resources released without the isolate lock. Resources that Rust
+    /// code has retained since are no longer in the list.
+    fn release_deferred_wrappers(&mut self) {
+        for resource_ptr in std::mem::take(&mut self.deferred_releases) {
+            let resource_state = unsafe { &mut *resource_ptr.as_ptr() };
+            resource_state.release_deferred = false;
+            resource_state.make_wrapper_weak();
+        }
+    }
+
+    /// Tracks each wrapped resource as a root of the heap snapshot's embedder graph, with edges
+    /// to and from its wrapper.
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        for resource_ptr in &self.resources {
+            let resource_state = unsafe { &**resource_ptr };
+            if let Some(memory_info_fn) = resource_state.memory_info_fn {
+                memory_info_fn(tracker, resource_state);
+            }
+        }
+    }
+
+    /// Visits the values held by each wrapped resource, for the `GcTracer` to make them weak
+    /// while V8 traces the wrappers.
+    fn visit_for_gc(&self, mut tracer: Pin<&mut ffi::GcTracer>) {
+        for resource_ptr in &self.resources {
+            let resource_state = unsafe { &**resource_ptr };
+            if let Some(visit_for_gc_fn) = resource_state.visit_for_gc_fn {
+                let mut visitor = GcVisitor::from_tracer(tracer.as_mut(), *resource_ptr as usize);
+                visit_for_gc_fn(&mut visitor, resource_state);
+            }
+        }
     }
 
     pub fn isolate(&self) -> v8::IsolatePtr {
@@ -753,31 +1715,98 @@ impl Drop for Realm {
             "Realm must be dropped while holding the isolate lock"
         );
 
+        unsafe { ffi::realm_unregister_memory_info(self.isolate.as_ffi(), self) };
+
+        // Make the values held by resources strong again, in case a GC is in progress, and stop
+        // walking the resources before they are detached.
+        self.gc_tracer = None;
+
+        // Values stored in the context and the reactions to promises still pending in it may hold
+        // `Ref`s to resources, which are released before the resources are detached.
+        self.context_data.clear();
+        self.pending_reactions.clear();
+        self.deferred_releases.clear();
+
         // Clean up all leaked Refs during deterministic context disposal.
-        // Each resource_ptr points to a ResourceState embedded in a Resource.
-        // When wrapping a resource, we leak a Ref<R> and store the raw pointer
-        // in ResourceState.this. If strong_wrapper is still Some, the V8 object
-        // wasn't GC'd, so we must manually drop the leaked Ref by calling drop_fn.
-        for resource_ptr in &self.resources {
+        // Each resource_ptr points to a ResourceState embedded in a Resource whose wrapper
+        // hasn't been collected by V8's GC yet, since the weak callback unregisters it. When
+        // wrapping a resource, we leak a Ref<R> and store the raw pointer in ResourceState.this,
+        // so we must manually drop the leaked Ref by calling drop_fn.
+        for resource_ptr in std::mem::take(&mut self.resources) {
             unsafe {
-                let resource_state = &**resource_ptr;
-                // Only drop if the wrapper hasn't been collected by V8's GC
-                if resource_state.strong_wrapper.is_some()
-                    && let Some(drop_fn) = resource_state.drop_fn
-                    && !resource_state.this.is_null()
-                {
-                    // Note: Do not access resource_state after drop_fn returns, as
-                    // ResourceState is embedded inside the Resource which is now freed.
-                    let isolate = resource_state.isolate.expect("isolate should be set");
-                    drop_fn(isolate.as_ffi(), resource_state.this);
+                let resource_state = &mut *resource_ptr;
+                // The wrapper may outlive the Realm, so its weak callback must not fire later.
+                if let Some(wrapper) = resource_state.strong_wrapper.as_mut() {
+                    wrapper.clear_weak();
                 }
+                // Note: Do not access resource_state after detach returns, as ResourceState is
+                // embedded inside the Resource which may now be freed.
+                resource_state.detach();
             }
         }
-        self.resources.clear();
     }
 }
 
-#[expect(clippy::unnecessary_box_returns)]
-unsafe fn realm_create(isolate: *mut v8::ffi::Isolate) -> Box<Realm> {
-    unsafe { Box::new(Realm::from_isolate(v8::IsolatePtr::from_ffi(isolate))) }
+/// The work left to do under the isolate lock once a future passed to `Lock::await_io` completes.
+type IoContinuation = Box<dyn FnOnce(&mut Lock)>;
+
+/// A future awaited on the KJ event loop on behalf of `Lock::await_io`.
+///
+/// C++ polls the future via `poll()` and, once it completes, re-enters the isolate lock and hands
+/// the `PendingIo` back to `pending_io_resume()` to run the continuation.
+pub struct PendingIo {
+    future: Pin<Box<dyn Future<Output = IoContinuation>>>,
+    continuation: Option<IoContinuation>,
+}
+
+impl PendingIo {
+    async fn poll(&mut self) {
+        self.continuation = Some(self.future.as_mut().await);
+    }
+}
+
+#[expect(clippy::boxed_local)]
+unsafe fn pending_io_resume(isolate: *mut v8::ffi::Isolate, io: Box<PendingIo>) {
+    let mut lock = unsafe { Lock::from_isolate_ptr(isolate) };
+    // The future may have dropped `Ref`s without the lock when it completed.
+    lock.realm();
+    let continuation = io
+        .continuation
+        .expect("pending_io_resume() called before the future completed");
+    continuation(&mut lock);
+}
+
+unsafe fn realm_create(isolate: *mut v8::ffi::Isolate, collected_with_context: bool) -> Box<Realm> {
+    let mut realm = unsafe { Box::new(Realm::new(v8::IsolatePtr::from_ffi(isolate))) };
+    realm.collected_with_context = collected_with_context;
+    // The Realm is boxed, so its address stays valid until `Realm::drop` unregisters it.
+    unsafe { ffi::realm_register_memory_info(isolate, &raw mut *realm) };
+    realm.gc_tracer = Some(unsafe { ffi::gc_tracer_create(isolate, &raw mut *realm) });
+    realm
+}
+
+unsafe fn realm_get_memory_info(realm: &Realm, tracker: Pin<&mut ffi::MemoryTracker>) {
+    realm.memory_info(&mut MemoryTracker::from_ffi(tracker));
+}
+
+unsafe fn realm_visit_for_gc(realm: &Realm, tracer: Pin<&mut ffi::GcTracer>) {
+    realm.visit_for_gc(tracer);
+}
+
+unsafe fn realm_release_deferred_wrappers(realm: &mut Realm) {
+    realm.release_deferred_wrappers();
+}
+
+/// Called by the cppgc shim of a wrapper when V8 traces it.
+unsafe fn resource_state_trace(state: usize, visitor: Pin<&mut ffi::CppgcVisitor>) {
+    let state = unsafe { &*(state as *const ResourceState) };
+    if let Some(visit_for_gc_fn) = state.visit_for_gc_fn {
+        visit_for_gc_fn(&mut GcVisitor::from_cppgc(visitor), state);
+    }
+}
+
+/// Called when the cppgc shim of a wrapper is collected before the resource lets go of it.
+unsafe fn resource_state_clear_shim(state: usize) {
+    let state = unsafe { &mut *(state as *mut ResourceState) };
+    state.shim = std::ptr::null_mut();
 }
@@ -0,0 +1,150 @@
+//! Heap snapshot support, analogous to `jsg::MemoryTracker` in C++ JSG.
+//!
+//! When a heap snapshot is taken, V8 asks the embedder for a graph of the native objects
+//! retaining memory. Each Realm adds the Rust resources it tracks to that graph, and each
+//! resource describes its fields through `Type::memory_info()`.
+
+use std::pin::Pin;
+
+use crate::Resource;
+use crate::ResourceState;
+use crate::Type;
+use crate::V8Ref;
+use crate::ffi;
+use crate::v8;
+
+/// Builds the embedder graph of a V8 heap snapshot from Rust objects.
+///
+/// A `MemoryTracker` is passed to `Type::memory_info()` while a heap snapshot is taken, so that
+/// Rust-backed objects show their retained sizes and the edges to the values they reference.
+/// Rust objects are added to the same graph as C++ ones.
+///
+/// # Example
+///
+/// ```ignore
+/// impl jsg::Type for Cache {
+///     // ...
+///     fn memory_info(&self, tracker: &mut jsg::MemoryTracker) {
+///         tracker.track_field("entries", &self.entries);
+///         tracker.track_field_with_size("buffer", self.buffer.capacity());
+///     }
+/// }
+/// ```
+pub struct MemoryTracker<'a> {
+    tracker: Pin<&'a mut ffi::MemoryTracker>,
+}
+
+impl<'a> MemoryTracker<'a> {
+    pub(crate) fn from_ffi(tracker: Pin<&'a mut ffi::MemoryTracker>) -> Self {
+        Self { tracker }
+    }
+
+    /// Tracks `value` as a field named `edge_name` of the object currently being tracked.
+    ///
+    /// Values that own no memory beyond their own size, such as numbers, are already included in
+    /// the size of the object holding them and are skipped. A `Ref<R>` is tracked as an edge to
+    /// the referenced resource, which is only added to the graph once however many objects refer
+    /// to it.
+    pub fn track_field<T: Type>(&mut self, edge_name: &str, value: &T) {
+        value.track_memory(self, edge_name);
+    }
+
+    /// Tracks a heap allocation of `size` bytes as a separate node named `edge_name`.
+    pub fn track_field_with_size(&mut self, edge_name: &str, size: usize) {
+        ffi::memory_tracker_track_field_with_size(self.tracker.as_mut(), edge_name, size);
+    }
+
+    /// Tracks `size` bytes stored inline in the object currently being tracked.
+    pub fn track_inline_field_with_size(&mut self, edge_name: &str, size: usize) {
+        ffi::memory_tracker_track_inline_field_with_size(self.tracker.as_mut(), edge_name, size);
+    }
+
+    /// Tracks an edge to the JavaScript value held by `value`. Weak handles are skipped.
+    pub fn track_global<T>(&mut self, edge_name: &str, value: &v8::Global<T>) {
+        let value = unsafe { value.as_ffi_ref() };
+        ffi::memory_tracker_track_global(self.tracker.as_mut(), edge_name, value);
+    }
+
+    /// Tracks an edge to the JavaScript value held by `value`.
+    pub fn track_v8_ref<T>(&mut self, edge_name: &str, value: &V8Ref<T>) {
+        ffi::memory_tracker_track_traced_data(self.tracker.as_mut(), edge_name, value.data());
+    }
+
+    /// Tracks a value stored inline in its owner as a node named `T::memory_name()`.
+    pub(crate) fn track_inline<T: Type>(&mut self, edge_name: &str, value: &T) {
+        if std::mem::needs_drop::<T>() {
+            self.track_node(edge_name, 0, value, None);
+        }
+    }
+
+    /// Tracks a resource and the edges to and from its JavaScript wrapper, if it has one.
+    pub(crate) fn track_resource<R: Resource>(
+        &mut self,
+        edge_name: &str,
+        resource: &R,
+        wrapper: Option<&v8::Global<v8::Object>>,
+    ) {
+        let retainer = std::ptr::from_ref(resource) as usize;
+        self.track_node(edge_name, retainer, resource, wrapper);
+    }
+
+    /// Adds a node for `value` and calls its `memory_info()` to track its fields. Nodes with a
+    /// non-zero `retainer` are only added once; later calls add an edge to the existing node.
+    fn track_node<T: Type>(
+        &mut self,
+        edge_name: &str,
+        retainer: usize,
+        value: &T,
+        wrapper: Option<&v8::Global<v8::Object>>,
+    ) {
+        unsafe {
+            ffi::memory_tracker_track_foreign(
+                self.tracker.as_mut(),
+                edge_name,
+                retainer,
+                T::memory_name(),
+                T::memory_self_size(),
+                wrapper.map_or(std::ptr::null(), |wrapper| wrapper.as_ffi_ref()),
+                std::ptr::from_ref(value) as usize,
+                memory_info_callback::<T>,
+            );
+        }
+    }
+}
+
+/// Called by C++ with the node of the value at `data` on top of the tracker's stack.
+fn memory_info_callback<T: Type>(tracker: Pin<&mut ffi::MemoryTracker>, data: usize) {
+    let value = unsafe { &*(data as *const T) };
+    value.memory_info(&mut MemoryTracker::from_ffi(tracker));
+}
+
+/// Tracks the resource owning `state`, which must have been wrapped as an `R`.
+pub fn track_resource_state<R: Resource>(tracker: &mut MemoryTracker, state: &ResourceState) {
+    let resource = unsafe { &*state.this.cast::<R>() };
+    tracker.track_resource("", resource, state.strong_wrapper.as_ref());
+}
+
+/// Wraps a field for `#[jsg_struct]` and `#[jsg_resource]`, which track the fields implementing
+/// `Type` and skip the others. Calling `(&MemoryField(&field)).track_field(..)` resolves to
+/// `TrackMemoryField` if the field implements `Type`, and otherwise to `SkipMemoryField`, whose
+/// receiver needs one more auto-reference.
+#[doc(hidden)]
+pub struct MemoryField<'a, T>(pub &'a T);
+
+#[doc(hidden)]
+pub trait TrackMemoryField {
+    fn track_field(&self, tracker: &mut MemoryTracker, edge_name: &str);
+}
+
+impl<T: Type> TrackMemoryField for MemoryField<'_, T> {
+    fn track_field(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+        tracker.track_field(edge_name, self.0);
+    }
+}
+
+#[doc(hidden)]
+pub trait SkipMemoryField {
+    fn track_field(&self, _tracker: &mut MemoryTracker, _edge_name: &str) {}
+}
+
+impl<T> SkipMemoryField for &MemoryField<'_, T> {}
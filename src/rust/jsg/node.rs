@@ -0,0 +1,528 @@
+//! Node.js-style errors for Rust-backed `node-internal:*` modules.
+//!
+//! Node.js errors carry a `code` such as `ERR_INVALID_ARG_TYPE` and have messages in a fixed
+//! format that user code and tests match against. `jsg::node::Error` formats its messages like
+//! `lib/internal/errors.js` in Node.js (and `internal_errors.ts` in workerd) and converts into a
+//! `jsg::Error` with the `code` property set, so it can be returned from a `#[jsg_method]`:
+//!
+//! ```ignore
+//! #[jsg_method]
+//! pub fn set_port(&mut self, port: f64) -> Result<(), jsg::node::Error> {
+//!     if !(0.0..65536.0).contains(&port) {
+//!         return Err(jsg::node::Error::out_of_range("port", ">= 0 and < 65536", port));
+//!     }
+//!     self.port = port;
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt;
+
+use crate::ExceptionType;
+use crate::FromJS;
+use crate::Lock;
+use crate::v8;
+
+/// A Node.js error with its `code`, e.g. `ERR_INVALID_ARG_TYPE`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// `ERR_INVALID_ARG_TYPE`, a `TypeError`.
+    InvalidArgType {
+        name: String,
+        expected: Vec<String>,
+        actual: Received,
+    },
+    /// `ERR_INVALID_ARG_VALUE`, a `TypeError`.
+    InvalidArgValue {
+        name: String,
+        value: Received,
+        reason: String,
+    },
+    /// `ERR_OUT_OF_RANGE`, a `RangeError`.
+    OutOfRange {
+        name: String,
+        range: String,
+        received: Received,
+    },
+    /// `ERR_MISSING_ARGS`, a `TypeError`.
+    MissingArgs { names: Vec<String> },
+    /// `ERR_INVALID_THIS`, a `TypeError`.
+    InvalidThis { expected: String },
+}
+
+impl Error {
+    /// The argument `name` is not of any of the `expected` types, e.g. `string` or `Buffer`.
+    pub fn invalid_arg_type(
+        name: impl Into<String>,
+        expected: &[&str],
+        actual: impl Into<Received>,
+    ) -> Self {
+        Self::InvalidArgType {
+            name: name.into(),
+            expected: expected
+                .iter()
+                .map(|&expected| expected.to_owned())
+                .collect(),
+            actual: actual.into(),
+        }
+    }
+
+    /// The argument `name` has an unsupported value. Node.js uses `"is invalid"` as the default
+    /// `reason`.
+    pub fn invalid_arg_value(
+        name: impl Into<String>,
+        value: impl Into<Received>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self::InvalidArgValue {
+            name: name.into(),
+            value: value.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// The value of `name` is outside of `range`, e.g. `">= 0 and <= 65535"`.
+    pub fn out_of_range(
+        name: impl Into<String>,
+        range: impl Into<String>,
+        received: impl Into<Received>,
+    ) -> Self {
+        Self::OutOfRange {
+            name: name.into(),
+            range: range.into(),
+            received: received.into(),
+        }
+    }
+
+    /// The arguments `names` are required but were not passed.
+    pub fn missing_args(names: &[&str]) -> Self {
+        Self::MissingArgs {
+            names: names.iter().map(|&name| name.to_owned()).collect(),
+        }
+    }
+
+    /// A method was called on a `this` that is not an `expected`.
+    pub fn invalid_this(expected: impl Into<String>) -> Self {
+        Self::InvalidThis {
+            expected: expected.into(),
+        }
+    }
+
+    /// Returns the Node.js error code, e.g. `ERR_INVALID_ARG_TYPE`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidArgType { .. } => "ERR_INVALID_ARG_TYPE",
+            Self::InvalidArgValue { .. } => "ERR_INVALID_ARG_VALUE",
+            Self::OutOfRange { .. } => "ERR_OUT_OF_RANGE",
+            Self::MissingArgs { .. } => "ERR_MISSING_ARGS",
+            Self::InvalidThis { .. } => "ERR_INVALID_THIS",
+        }
+    }
+
+    /// Returns the type of the JavaScript error that is thrown.
+    pub fn exception_type(&self) -> ExceptionType {
+        match self {
+            Self::OutOfRange { .. } => ExceptionType::RangeError,
+            _ => ExceptionType::TypeError,
+        }
+    }
+}
+
+/// Formats the message of the error exactly as Node.js does.
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidArgType {
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}. {}",
+                invalid_arg_type_message(name, expected),
+                actual.describe_type()
+            ),
+            Self::InvalidArgValue {
+                name,
+                value,
+                reason,
+            } => write!(
+                f,
+                "The {} '{name}' {reason}. Received {}",
+                argument_kind(name),
+                value.inspect()
+            ),
+            Self::OutOfRange {
+                name,
+                range,
+                received,
+            } => write!(
+                f,
+                "The value of \"{name}\" is out of range. It must be {range}. Received {}",
+                received.inspect_range()
+            ),
+            Self::MissingArgs { names } => {
+                let names: Vec<String> = names.iter().map(|name| format!("\"{name}\"")).collect();
+                match names.as_slice() {
+                    [] => f.write_str("The arguments must be specified"),
+                    [name] => write!(f, "The {name} argument must be specified"),
+                    [first, second] => {
+                        write!(f, "The {first} and {second} arguments must be specified")
+                    }
+                    [rest @ .., last] => write!(
+                        f,
+                        "The {}, and {last} arguments must be specified",
+                        rest.join(", ")
+                    ),
+                }
+            }
+            Self::InvalidThis { expected } => {
+                write!(f, "Value of \"this\" must be of type {expected}")
+            }
+        }
+    }
+}
+
+impl From<Error> for crate::Error {
+    fn from(err: Error) -> Self {
+        let code = err.code();
+        let name = err.exception_type();
+        Self::new(&name.to_string(), &err.to_string()).with_property("code", code)
+    }
+}
+
+/// A description of the value received by a function, used in error messages.
+///
+/// Primitives are formatted like `util.inspect()` in Node.js. Objects are described by their
+/// constructor and functions by their name; their contents are not inspected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Received {
+    Undefined,
+    Null,
+    Boolean(bool),
+    Number(f64),
+    /// The decimal digits of a `BigInt`, e.g. `-42`.
+    BigInt(String),
+    String(String),
+    /// A symbol with its description, if any.
+    Symbol(Option<String>),
+    /// A function with its name, or `None` if it is anonymous.
+    Function(Option<String>),
+    /// An object with the name of its constructor, or `None` if it has none.
+    Object(Option<String>),
+}
+
+impl Received {
+    /// Formats the value like `util.inspect()`.
+    fn inspect(&self) -> String {
+        match self {
+            Self::Undefined => "undefined".to_owned(),
+            Self::Null => "null".to_owned(),
+            Self::Boolean(value) => value.to_string(),
+            Self::Number(value) if *value == 0.0 && value.is_sign_negative() => "-0".to_owned(),
+            Self::Number(value) => number_to_string(*value),
+            Self::BigInt(digits) => format!("{digits}n"),
+            Self::String(value) => quote_string(value),
+            Self::Symbol(description) => {
+                format!("Symbol({})", description.as_deref().unwrap_or(""))
+            }
+            Self::Function(Some(name)) => format!("[Function: {name}]"),
+            Self::Function(None) => "[Function (anonymous)]".to_owned(),
+            Self::Object(Some(constructor)) => format!("[{constructor}]"),
+            Self::Object(None) => "[Object: null prototype]".to_owned(),
+        }
+    }
+
+    /// Describes the type of the value for `ERR_INVALID_ARG_TYPE`.
+    fn describe_type(&self) -> String {
+        let type_of = match self {
+            Self::Undefined | Self::Null | Self::Object(None) => {
+                return format!("Received {}", self.inspect());
+            }
+            Self::Function(Some(name)) => return format!("Received function {name}"),
+            Self::Object(Some(constructor)) => {
+                return format!("Received an instance of {constructor}");
+            }
+            Self::Boolean(_) => "boolean",
+            Self::Number(_) => "number",
+            Self::BigInt(_) => "bigint",
+            Self::String(_) => "string",
+            Self::Symbol(_) => "symbol",
+            Self::Function(None) => "function",
+        };
+        let mut inspected = self.inspect();
+        if inspected.chars().count() > 25 {
+            inspected = inspected.chars().take(25).collect::<String>() + "...";
+        }
+        format!("Received type {type_of} ({inspected})")
+    }
+
+    /// Formats the value for `ERR_OUT_OF_RANGE`, which separates the digits of large integers.
+    fn inspect_range(&self) -> String {
+        const LIMIT: f64 = 4_294_967_296.0;
+        match self {
+            Self::Number(value) if value.fract() == 0.0 && value.abs() > LIMIT => {
+                add_numerical_separator(&number_to_string(*value))
+            }
+            Self::BigInt(digits) => {
+                let magnitude = digits.trim_start_matches('-');
+                if magnitude.len() > 10 || (magnitude.len() == 10 && magnitude > "4294967296") {
+                    format!("{}n", add_numerical_separator(digits))
+                } else {
+                    format!("{digits}n")
+                }
+            }
+            _ => self.inspect(),
+        }
+    }
+}
+
+impl From<bool> for Received {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<f64> for Received {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i32> for Received {
+    fn from(value: i32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<u32> for Received {
+    fn from(value: u32) -> Self {
+        Self::Number(value.into())
+    }
+}
+
+impl From<&str> for Received {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for Received {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl FromJS for Received {
+    type ResultType = Self;
+
+    /// Describes any JavaScript value. Like Node.js, this reads the `constructor` of objects and
+    /// the `name` of functions.
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> crate::Result<Self> {
+        if value.is_undefined() {
+            return Ok(Self::Undefined);
+        }
+        if value.is_null() {
+            return Ok(Self::Null);
+        }
+        if value.is_boolean() {
+            return Ok(Self::Boolean(bool::from_js(lock, value)?));
+        }
+        if value.is_number() {
+            return Ok(Self::Number(f64::from_js(lock, value)?));
+        }
+        if value.is_big_int() {
+            return Ok(Self::BigInt(String::from_js(lock, value)?));
+        }
+        if value.is_string() {
+            return Ok(Self::String(String::from_js(lock, value)?));
+        }
+        if let Some(symbol) = value.try_cast::<v8::Symbol>() {
+            return Ok(Self::Symbol(symbol.description(lock)));
+        }
+
+        let is_function = value.is_function();
+        let object = value.try_into_object()?;
+        let name = if is_function {
+            object.get(lock, "name").ok()
+        } else {
+            object
+                .get(lock, "constructor")
+                .ok()
+                .and_then(|constructor| constructor.try_into_object().ok())
+                .and_then(|constructor| constructor.get(lock, "name").ok())
+        };
+        let name = name
+            .filter(v8::Local::is_string)
+            .map(|name| String::from_js(lock, name))
+            .transpose()?
+            .filter(|name| !name.is_empty());
+        Ok(if is_function {
+            Self::Function(name)
+        } else {
+            Self::Object(name)
+        })
+    }
+}
+
+/// Returns `"property"` for dotted names such as `options.port`, and `"argument"` otherwise.
+fn argument_kind(name: &str) -> &'static str {
+    if name.contains('.') {
+        "property"
+    } else {
+        "argument"
+    }
+}
+
+/// Formats the first sentence of `ERR_INVALID_ARG_TYPE`, see `createInvalidArgType()` in
+/// `internal_errors.ts`.
+fn invalid_arg_type_message(name: &str, expected: &[String]) -> String {
+    const TYPES: [&str; 9] = [
+        "string", "function", "number", "object", "Function", "Object", "boolean", "bigint",
+        "symbol",
+    ];
+
+    let mut message = if name.ends_with(" argument") {
+        format!("The {name} must be ")
+    } else {
+        format!("The \"{name}\" {} must be ", argument_kind(name))
+    };
+
+    let mut types = Vec::new();
+    let mut instances = Vec::new();
+    let mut other = Vec::new();
+    for value in expected {
+        if TYPES.contains(&value.as_str()) {
+            types.push(value.to_lowercase());
+        } else if is_class_name(value) {
+            instances.push(value.clone());
+        } else {
+            other.push(value.clone());
+        }
+    }
+
+    // Special handle `object` in case other instances are allowed to outline the differences
+    // between each other.
+    if !instances.is_empty()
+        && let Some(pos) = types.iter().position(|value| value == "object")
+    {
+        types.remove(pos);
+        instances.push("Object".to_owned());
+    }
+
+    if !types.is_empty() {
+        message += &match types.as_slice() {
+            [single] => format!("of type {single}"),
+            [first, second] => format!("one of type {first} or {second}"),
+            [rest @ .., last] => format!("one of type {}, or {last}", rest.join(", ")),
+            [] => unreachable!(),
+        };
+        if !instances.is_empty() || !other.is_empty() {
+            message += " or ";
+        }
+    }
+
+    if !instances.is_empty() {
+        message += &match instances.as_slice() {
+            [single] => format!("an instance of {single}"),
+            [first, second] => format!("an instance of {first} or {second}"),
+            [rest @ .., last] => format!("an instance of {}, or {last}", rest.join(", ")),
+            [] => unreachable!(),
+        };
+        if !other.is_empty() {
+            message += " or ";
+        }
+    }
+
+    message += &match other.as_slice() {
+        [] => String::new(),
+        [single] if single.to_lowercase() != *single => format!("an {single}"),
+        [single] => single.clone(),
+        [first, second] => format!("one of {first} or {second}"),
+        [rest @ .., last] => format!("one of {}, or {last}", rest.join(", ")),
+    };
+    message
+}
+
+/// Matches `/^([A-Z][a-z0-9]*)+$/`, the names that Node.js treats as classes.
+fn is_class_name(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_uppercase())
+        && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// Formats a number like `Number.prototype.toString()`.
+fn number_to_string(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_owned();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_owned();
+    }
+    if value == 0.0 {
+        return "0".to_owned();
+    }
+    let magnitude = value.abs();
+    if (1e-6..1e21).contains(&magnitude) {
+        return value.to_string();
+    }
+    // Both use the shortest representation that round-trips, but JavaScript signs the exponent.
+    let formatted = format!("{value:e}");
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) if !exponent.starts_with('-') => {
+            format!("{mantissa}e+{exponent}")
+        }
+        _ => formatted,
+    }
+}
+
+/// Quotes a string like `util.inspect()`, preferring single quotes.
+fn quote_string(value: &str) -> String {
+    let quote = if !value.contains('\'') {
+        '\''
+    } else if !value.contains('"') {
+        '"'
+    } else if !value.contains('`') && !value.contains("${") {
+        '`'
+    } else {
+        '\''
+    };
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push(quote);
+    for c in value.chars() {
+        match c {
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            '\u{8}' => quoted.push_str("\\b"),
+            '\u{c}' => quoted.push_str("\\f"),
+            '\u{b}' => quoted.push_str("\\v"),
+            '\\' => quoted.push_str("\\\\"),
+            c if c == quote => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            c if c.is_ascii_control() => quoted.push_str(&format!("\\x{:02X}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push(quote);
+    quoted
+}
+
+/// Separates groups of three digits with underscores, e.g. `1_000_000`.
+fn add_numerical_separator(value: &str) -> String {
+    let start = usize::from(value.starts_with('-'));
+    let mut end = value.len();
+    let mut groups = Vec::new();
+    while end >= start + 4 {
+        groups.push(&value[end - 3..end]);
+        end -= 3;
+    }
+    let mut result = value[..end].to_owned();
+    for group in groups.iter().rev() {
+        result.push('_');
+        result.push_str(group);
+    }
+    result
+}
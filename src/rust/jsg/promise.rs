@@ -0,0 +1,338 @@
+//! JavaScript promises, analogous to `jsg::Promise<T>` in C++ JSG.
+//!
+//! A `Promise<T>` holds a strong reference to a `v8::Promise` whose fulfillment value converts
+//! to `T`. Continuations registered with `then` and `catch` run as promise reactions on the V8
+//! microtask queue, under the isolate lock. `into_future` lets Rust code await a promise, e.g.
+//! one passed in from JavaScript, from an `async fn` method.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::rc::Weak;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use crate::Error;
+use crate::FromJS;
+use crate::Lock;
+use crate::MemoryTracker;
+use crate::Result;
+use crate::ToJS;
+use crate::Type;
+use crate::v8;
+
+/// A reaction to a settled promise. Receives the fulfillment value as `Ok` or the rejection
+/// reason as `Err`, and sets the result of the derived promise through the callback info.
+type Reaction = Box<
+    dyn FnOnce(
+        &mut Lock,
+        std::result::Result<v8::Local<'_, v8::Value>, v8::Local<'_, v8::Value>>,
+        &v8::FunctionCallbackInfo<'_>,
+    ),
+>;
+
+/// Holds a reaction until it runs. The data of the reaction functions owns the slot, and the Realm
+/// of the context empties it on disposal if the reaction is still pending by then.
+type ReactionSlot = RefCell<Option<Reaction>>;
+
+/// The reactions registered in a context that have neither run nor been dropped with their
+/// functions. V8 does not collect the functions of a promise that is still pending when its context
+/// is disposed, so the Realm drops these reactions itself.
+#[derive(Default)]
+pub struct PendingReactions {
+    reactions: Vec<Weak<ReactionSlot>>,
+    /// The length at which the reactions that are gone are next pruned from the list.
+    prune_at: usize,
+}
+
+impl PendingReactions {
+    fn track(&mut self, slot: &Rc<ReactionSlot>) {
+        if self.reactions.len() >= self.prune_at {
+            self.reactions
+                .retain(|reaction| reaction.strong_count() > 0);
+            self.prune_at = (self.reactions.len() * 2).max(64);
+        }
+        self.reactions.push(Rc::downgrade(slot));
+    }
+
+    /// Drops every reaction that has not run yet. Their functions do nothing if V8 calls them
+    /// later.
+    pub fn clear(&mut self) {
+        for reaction in std::mem::take(&mut self.reactions) {
+            if let Some(slot) = reaction.upgrade() {
+                let reaction = slot.borrow_mut().take();
+                drop(reaction);
+            }
+        }
+    }
+}
+
+/// A JavaScript promise that is expected to fulfill with a value of type `T`.
+pub struct Promise<T> {
+    promise: v8::Global<v8::Promise>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+/// Settles the promise created alongside it by `Lock::new_promise_and_resolver`.
+pub struct PromiseResolver<T> {
+    resolver: v8::Global<v8::PromiseResolver>,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<T> Promise<T> {
+    fn from_local(promise: v8::Local<'_, v8::Promise>) -> Self {
+        Self {
+            promise: promise.into(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a promise already fulfilled with `value`.
+    pub fn resolved(lock: &mut Lock, value: T) -> Self
+    where
+        T: ToJS,
+    {
+        let (promise, resolver) = lock.new_promise_and_resolver();
+        resolver.resolve(lock, value);
+        promise
+    }
+
+    /// Returns a promise already rejected with `error`.
+    pub fn rejected(lock: &mut Lock, error: &Error) -> Self {
+        let (promise, resolver) = lock.new_promise_and_resolver::<T>();
+        resolver.reject(lock, error);
+        promise
+    }
+
+    /// Returns the underlying `v8::Promise`.
+    pub fn as_local<'s>(&self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Promise> {
+        self.promise.as_local(lock)
+    }
+
+    /// Registers `reaction` to run when this promise settles, returning the derived promise.
+    fn react<U>(&self, lock: &mut Lock, reaction: Reaction) -> Promise<U> {
+        let promise = self.as_local(lock);
+        let slot = Rc::new(RefCell::new(Some(reaction)));
+        lock.realm().pending_reactions.track(&slot);
+        let data = Box::into_raw(Box::new(slot)) as usize;
+        let derived = unsafe {
+            v8::Local::from_ffi(
+                lock.isolate(),
+                v8::ffi::promise_then(
+                    lock.isolate().as_ffi(),
+                    promise.as_ffi(),
+                    data,
+                    run_reaction,
+                    drop_reaction,
+                ),
+            )
+        };
+        Promise::from_local(derived)
+    }
+}
+
+impl<T: FromJS<ResultType = T> + 'static> Promise<T> {
+    /// Calls `on_fulfilled` with the fulfillment value once this promise fulfills.
+    ///
+    /// The returned promise is resolved with the callback's result, or rejected if the callback
+    /// returns an error. Rejections of this promise propagate to the returned promise.
+    pub fn then<U, F>(&self, lock: &mut Lock, on_fulfilled: F) -> Promise<U>
+    where
+        U: ToJS,
+        F: FnOnce(&mut Lock, T) -> Result<U> + 'static,
+    {
+        self.react(
+            lock,
+            Box::new(move |lock, settled, args| match settled {
+                Ok(value) => {
+                    match T::from_js(lock, value).and_then(|value| on_fulfilled(lock, value)) {
+                        Ok(result) => args.set_return_value(result.to_js(lock)),
+                        Err(err) => lock.throw_exception(&err),
+                    }
+                }
+                Err(reason) => rethrow(lock, reason),
+            }),
+        )
+    }
+
+    /// Calls `on_rejected` with the rejection reason once this promise rejects.
+    ///
+    /// The returned promise is resolved with the callback's result, or rejected if the callback
+    /// returns an error. If this promise fulfills, the returned promise fulfills with the same
+    /// value.
+    #[must_use]
+    pub fn catch<F>(&self, lock: &mut Lock, on_rejected: F) -> Self
+    where
+        T: ToJS,
+        F: FnOnce(&mut Lock, Error) -> Result<T> + 'static,
+    {
+        self.react(
+            lock,
+            Box::new(move |lock, settled, args| match settled {
+                Ok(value) => args.set_return_value(value),
+                Err(reason) => {
+                    let error = Error::from_js(lock, reason).unwrap_or_else(|err| err);
+                    match on_rejected(lock, error) {
+                        Ok(result) => args.set_return_value(result.to_js(lock)),
+                        Err(err) => lock.throw_exception(&err),
+                    }
+                }
+            }),
+        )
+    }
+
+    /// Converts this promise into a Rust future that completes once the promise settles.
+    ///
+    /// Promise reactions run on the V8 microtask queue, so the future only makes progress while
+    /// the isolate runs microtasks, e.g. after JavaScript returns or `Lock::await_io` resumes.
+    pub fn into_future(self, lock: &mut Lock) -> PromiseFuture<T> {
+        let state = Rc::new(RefCell::new(PromiseFutureState {
+            result: None,
+            waker: None,
+        }));
+        let reaction_state = state.clone();
+        let _: Promise<()> = self.react(
+            lock,
+            Box::new(move |lock, settled, _args| {
+                let result = match settled {
+                    Ok(value) => T::from_js(lock, value),
+                    Err(reason) => Err(Error::from_js(lock, reason).unwrap_or_else(|err| err)),
+                };
+                let mut state = reaction_state.borrow_mut();
+                state.result = Some(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }),
+        );
+        PromiseFuture { state }
+    }
+}
+
+impl<T> Clone for Promise<T> {
+    fn clone(&self) -> Self {
+        Self {
+            promise: self.promise.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> PromiseResolver<T> {
+    /// Fulfills the promise with `value`.
+    pub fn resolve(self, lock: &mut Lock, value: T)
+    where
+        T: ToJS,
+    {
+        let value = value.to_js(lock);
+        self.resolver.as_local(lock).resolve(lock, value);
+    }
+
+    /// Rejects the promise with `error`.
+    pub fn reject(self, lock: &mut Lock, error: &Error) {
+        let exception = error.to_local(lock);
+        self.resolver.as_local(lock).reject(lock, exception);
+    }
+}
+
+impl Lock<'_> {
+    /// Creates a new pending promise along with the resolver that settles it.
+    pub fn new_promise_and_resolver<T>(&mut self) -> (Promise<T>, PromiseResolver<T>) {
+        let resolver = v8::Local::<v8::PromiseResolver>::new(self);
+        let promise = Promise::from_local(resolver.get_promise(self));
+        let resolver = PromiseResolver {
+            resolver: resolver.into(),
+            _marker: PhantomData,
+        };
+        (promise, resolver)
+    }
+}
+
+struct PromiseFutureState<T> {
+    result: Option<Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// A future that completes with the result of a `Promise<T>`. Created by `Promise::into_future`.
+pub struct PromiseFuture<T> {
+    state: Rc<RefCell<PromiseFutureState<T>>>,
+}
+
+impl<T> Future for PromiseFuture<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.borrow_mut();
+        if let Some(result) = state.result.take() {
+            return Poll::Ready(result);
+        }
+        state.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Rejects the derived promise of a reaction with `reason`.
+fn rethrow(lock: &mut Lock, reason: v8::Local<'_, v8::Value>) {
+    unsafe { v8::ffi::isolate_throw_exception(lock.isolate().as_ffi(), reason.into_ffi()) };
+}
+
+unsafe fn run_reaction(
+    isolate: *mut v8::ffi::Isolate,
+    data: usize,
+    value: v8::ffi::Local,
+    rejected: bool,
+    args: *mut v8::ffi::FunctionCallbackInfo,
+) {
+    let slot = unsafe { Box::from_raw(data as *mut Rc<ReactionSlot>) };
+    // The reaction is gone if the Realm was disposed before the promise settled.
+    let Some(reaction) = slot.borrow_mut().take() else {
+        return;
+    };
+    let mut lock = unsafe { Lock::from_isolate_ptr(isolate) };
+    let args = unsafe { v8::FunctionCallbackInfo::from_ffi(args) };
+    let value = unsafe { v8::Local::from_ffi(lock.isolate(), value) };
+    let settled = if rejected { Err(value) } else { Ok(value) };
+    reaction(&mut lock, settled, &args);
+}
+
+unsafe fn drop_reaction(data: usize) {
+    drop(unsafe { Box::from_raw(data as *mut Rc<ReactionSlot>) });
+}
+
+impl<T> Type for Promise<T> {
+    fn class_name() -> &'static str {
+        "Promise"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_promise()
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        tracker.track_global("promise", &self.promise);
+    }
+}
+
+impl<T> ToJS for Promise<T> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        self.as_local(lock).into()
+    }
+}
+
+impl<T> FromJS for Promise<T> {
+    type ResultType = Self;
+
+    /// Wraps a JavaScript promise. Like C++ JSG, any other value is converted to a promise
+    /// fulfilled with that value, as if by `Promise.resolve()`.
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        if let Some(promise) = value.try_cast::<v8::Promise>() {
+            return Ok(Self::from_local(promise));
+        }
+        let resolver = v8::Local::<v8::PromiseResolver>::new(lock);
+        resolver.resolve(lock, value);
+        Ok(Self::from_local(resolver.get_promise(lock)))
+    }
+}
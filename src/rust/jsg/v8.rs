@@ -2,6 +2,9 @@ use core::ffi::c_void;
 use std::fmt::Display;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
 use crate::FromJS;
 use crate::Lock;
@@ -19,6 +22,23 @@ pub mod ffi {
         ptr: usize,
     }
 
+    /// The outcome of calling a JavaScript function. `value` is set if the call returned and
+    /// `exception` if it threw; neither is set if execution was terminated.
+    struct FunctionCallResult {
+        value: KjMaybe<Local>,
+        exception: KjMaybe<Local>,
+    }
+
+    /// The exception caught by `isolate_try_catch`. `caught` is set if the callback threw or
+    /// execution was terminated while it ran, in which case `exception` is not set. `stack` is
+    /// empty if the exception has no stack trace.
+    struct TryCatchResult {
+        caught: bool,
+        exception: KjMaybe<Local>,
+        stack: String,
+        message: String,
+    }
+
     #[derive(Debug, PartialEq, Eq, Copy, Clone)]
     pub enum ExceptionType {
         OperationError,
@@ -39,6 +59,32 @@ pub mod ffi {
         ReferenceError,
     }
 
+    /// The attributes of a property defined with `Local<Object>::define_property()`, like the
+    /// fields of a JavaScript property descriptor. Each defaults to `false`, as with
+    /// `Object.defineProperty()`.
+    #[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+    pub struct PropertyAttributes {
+        pub writable: bool,
+        pub enumerable: bool,
+        pub configurable: bool,
+    }
+
+    /// The well-known symbols of the ECMAScript specification, e.g. `Symbol.iterator`.
+    #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+    pub enum WellKnownSymbol {
+        AsyncIterator,
+        HasInstance,
+        IsConcatSpreadable,
+        Iterator,
+        Match,
+        Replace,
+        Search,
+        Split,
+        ToPrimitive,
+        ToStringTag,
+        Unscopables,
+    }
+
     /// Module visibility level, corresponds to `workerd::jsg::ModuleType` from modules.capnp.
     /// Values are automatically assigned by `cxx` because of extern declaration below.
     #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -65,6 +111,8 @@ pub mod ffi {
         pub unsafe fn local_new_number(isolate: *mut Isolate, value: f64) -> Local;
         pub unsafe fn local_new_string(isolate: *mut Isolate, value: &str) -> Local;
         pub unsafe fn local_new_boolean(isolate: *mut Isolate, value: bool) -> Local;
+        pub unsafe fn local_new_big_int(isolate: *mut Isolate, value: i64) -> Local;
+        pub unsafe fn local_new_big_int_from_unsigned(isolate: *mut Isolate, value: u64) -> Local;
         pub unsafe fn local_new_object(isolate: *mut Isolate) -> Local;
         pub unsafe fn local_new_null(isolate: *mut Isolate) -> Local;
         pub unsafe fn local_new_undefined(isolate: *mut Isolate) -> Local;
@@ -73,46 +121,131 @@ pub mod ffi {
         pub unsafe fn local_is_string(value: &Local) -> bool;
         pub unsafe fn local_is_boolean(value: &Local) -> bool;
         pub unsafe fn local_is_number(value: &Local) -> bool;
+        pub unsafe fn local_is_big_int(value: &Local) -> bool;
         pub unsafe fn local_is_null(value: &Local) -> bool;
         pub unsafe fn local_is_undefined(value: &Local) -> bool;
         pub unsafe fn local_is_null_or_undefined(value: &Local) -> bool;
         pub unsafe fn local_is_object(value: &Local) -> bool;
         pub unsafe fn local_is_native_error(value: &Local) -> bool;
+        pub unsafe fn local_is_promise(value: &Local) -> bool;
+        pub unsafe fn local_is_function(value: &Local) -> bool;
+        pub unsafe fn local_is_array(value: &Local) -> bool;
+        pub unsafe fn local_is_array_buffer(value: &Local) -> bool;
+        pub unsafe fn local_is_array_buffer_view(value: &Local) -> bool;
+        pub unsafe fn local_is_uint8_array(value: &Local) -> bool;
+        pub unsafe fn local_is_symbol(value: &Local) -> bool;
+        pub unsafe fn local_is_date(value: &Local) -> bool;
+        pub unsafe fn local_is_reg_exp(value: &Local) -> bool;
         pub unsafe fn local_type_of(isolate: *mut Isolate, value: &Local) -> String;
 
         // Local<Object>
-        pub unsafe fn local_object_set_property(
+        pub unsafe fn local_new_internalized_string(isolate: *mut Isolate, value: &str) -> Local;
+        pub unsafe fn local_object_set(
             isolate: *mut Isolate,
-            object: &mut Local,
-            key: &str,
+            object: &Local,
+            key: Local, /* v8::Local<Value> */
             value: Local,
         );
-        pub unsafe fn local_object_has_property(
+        pub unsafe fn local_object_has(isolate: *mut Isolate, object: &Local, key: Local) -> bool;
+        pub unsafe fn local_object_get(
             isolate: *mut Isolate,
             object: &Local,
-            key: &str,
+            key: Local,
+        ) -> KjMaybe<Local>;
+        pub unsafe fn local_object_delete(
+            isolate: *mut Isolate,
+            object: &Local,
+            key: Local,
         ) -> bool;
-        pub unsafe fn local_object_get_property(
+        pub unsafe fn local_object_define_property(
+            isolate: *mut Isolate,
+            object: &Local,
+            key: Local, /* v8::Local<Value> */
+            value: Local,
+            attributes: PropertyAttributes,
+        );
+        pub unsafe fn local_object_get_own_property_names(
+            isolate: *mut Isolate,
+            object: &Local,
+        ) -> Vec<String>;
+        pub unsafe fn local_object_get_own_property_keys(
+            isolate: *mut Isolate,
+            object: &Local,
+        ) -> Vec<Local>;
+        pub unsafe fn local_object_get_prototype(isolate: *mut Isolate, object: &Local) -> Local;
+        pub unsafe fn local_object_set_prototype(
             isolate: *mut Isolate,
             object: &Local,
-            key: &str,
+            prototype: Local,
+        ) -> bool;
+
+        // Local<Array>
+        pub unsafe fn local_new_array(isolate: *mut Isolate, values: Vec<Local>) -> Local;
+        pub unsafe fn local_iterate(
+            isolate: *mut Isolate,
+            value: &Local,
+            values: &mut Vec<Local>,
         ) -> KjMaybe<Local>;
 
+        // Local<Function>
+        pub unsafe fn local_function_call(
+            isolate: *mut Isolate,
+            function: &Local, /* v8::Local<Function> */
+            receiver: Local,
+            args: Vec<Local>,
+        ) -> FunctionCallResult;
+
+        // Local<Uint8Array>
+        pub unsafe fn local_new_uint8_array(isolate: *mut Isolate, data: &[u8]) -> Local;
+
+        // Local<Symbol>
+        pub unsafe fn local_new_symbol(isolate: *mut Isolate, description: &str) -> Local;
+        pub unsafe fn local_symbol_well_known(
+            isolate: *mut Isolate,
+            symbol: WellKnownSymbol,
+        ) -> Local;
+        pub unsafe fn local_symbol_description(
+            isolate: *mut Isolate,
+            symbol: &Local, /* v8::Local<Symbol> */
+        ) -> Local /* v8::Local<Value> */;
+
+        // Local<Date>
+        pub unsafe fn local_new_date(isolate: *mut Isolate, time: f64) -> Local;
+        pub unsafe fn local_date_value_of(date: &Local /* v8::Local<Date> */) -> f64;
+
+        // Local<RegExp>
+        pub unsafe fn local_new_reg_exp(isolate: *mut Isolate, pattern: &str, flags: &str)
+        -> Local;
+        pub unsafe fn local_reg_exp_source(
+            isolate: *mut Isolate,
+            regexp: &Local, /* v8::Local<RegExp> */
+        ) -> String;
+        pub unsafe fn local_reg_exp_flags(regexp: &Local /* v8::Local<RegExp> */) -> String;
+
         // Global<T>
         pub unsafe fn global_drop(value: Global);
         pub unsafe fn global_clone(value: &Global) -> Global;
         pub unsafe fn global_to_local(isolate: *mut Isolate, value: &Global) -> Local;
+        pub unsafe fn global_buffer_source_bytes<'a>(
+            isolate: *mut Isolate,
+            value: &'a Global,
+        ) -> &'a [u8];
         pub unsafe fn global_make_weak(
             isolate: *mut Isolate,
             value: *mut Global,
             data: usize, /* void* */
             callback: unsafe fn(isolate: *mut Isolate, data: usize) -> (),
         );
+        pub unsafe fn global_clear_weak(value: *mut Global);
 
         // Unwrappers
         pub unsafe fn unwrap_string(isolate: *mut Isolate, value: Local) -> String;
         pub unsafe fn unwrap_boolean(isolate: *mut Isolate, value: Local) -> bool;
         pub unsafe fn unwrap_number(isolate: *mut Isolate, value: Local) -> f64;
+        pub unsafe fn unwrap_big_int_i64(value: &Local, lossless: &mut bool) -> i64;
+        pub unsafe fn unwrap_big_int_u64(value: &Local, lossless: &mut bool) -> u64;
+        pub unsafe fn unwrap_big_int_usize(value: &Local, lossless: &mut bool) -> usize;
+        pub unsafe fn unwrap_big_int_is_negative(value: &Local) -> bool;
 
         // FunctionCallbackInfo
         pub unsafe fn fci_get_isolate(args: *mut FunctionCallbackInfo) -> *mut Isolate;
@@ -120,6 +253,49 @@ pub mod ffi {
         pub unsafe fn fci_get_length(args: *mut FunctionCallbackInfo) -> usize;
         pub unsafe fn fci_get_arg(args: *mut FunctionCallbackInfo, index: usize) -> Local;
         pub unsafe fn fci_set_return_value(args: *mut FunctionCallbackInfo, value: Local);
+        pub unsafe fn fci_is_construct_call(args: *mut FunctionCallbackInfo) -> bool;
+
+        // Local<FunctionTemplate>
+        pub unsafe fn function_template_get_function(
+            isolate: *mut Isolate,
+            template: &Local, /* v8::Local<FunctionTemplate> */
+        ) -> Local /* v8::Local<Function> */;
+        pub unsafe fn function_template_has_instance(
+            template: &Local, /* v8::Local<FunctionTemplate> */
+            value: &Local,    /* v8::Local<Value> */
+        ) -> bool;
+
+        // Local<PromiseResolver>
+        pub unsafe fn promise_resolver_new(isolate: *mut Isolate) -> Local;
+        pub unsafe fn promise_resolver_get_promise(
+            isolate: *mut Isolate,
+            resolver: &Local, /* v8::Local<Promise::Resolver> */
+        ) -> Local /* v8::Local<Promise> */;
+        pub unsafe fn promise_resolver_resolve(
+            isolate: *mut Isolate,
+            resolver: &Local, /* v8::Local<Promise::Resolver> */
+            value: Local,
+        );
+        pub unsafe fn promise_resolver_reject(
+            isolate: *mut Isolate,
+            resolver: &Local, /* v8::Local<Promise::Resolver> */
+            value: Local,
+        );
+
+        // Local<Promise>
+        pub unsafe fn promise_then(
+            isolate: *mut Isolate,
+            promise: &Local, /* v8::Local<Promise> */
+            data: usize,     /* reaction */
+            callback: unsafe fn(
+                isolate: *mut Isolate,
+                data: usize,
+                value: Local,
+                rejected: bool,
+                args: *mut FunctionCallbackInfo,
+            ),
+            drop_data: unsafe fn(data: usize),
+        ) -> Local /* v8::Local<Promise> */;
 
         // Errors
         pub unsafe fn exception_create(
@@ -127,10 +303,21 @@ pub mod ffi {
             exception_type: ExceptionType,
             message: &str,
         ) -> Local;
+        pub unsafe fn exception_set_cause(isolate: *mut Isolate, exception: &Local, cause: Local);
+        pub unsafe fn exception_is_dom_exception(isolate: *mut Isolate, value: &Local) -> bool;
+
+        // Local<Context>
+        pub unsafe fn local_context_global(context: &Local /* v8::Local<Context> */) -> Local /* v8::Local<Object> */;
 
         // Isolate
+        pub unsafe fn isolate_get_current_context(isolate: *mut Isolate) -> Local /* v8::Local<Context> */;
         pub unsafe fn isolate_throw_exception(isolate: *mut Isolate, exception: Local);
         pub unsafe fn isolate_throw_error(isolate: *mut Isolate, message: &str);
+        pub unsafe fn isolate_try_catch(
+            isolate: *mut Isolate,
+            data: usize,
+            callback: unsafe fn(isolate: *mut Isolate, data: usize),
+        ) -> TryCatchResult;
         pub unsafe fn isolate_is_locked(isolate: *mut Isolate) -> bool;
     }
 
@@ -148,11 +335,25 @@ pub mod ffi {
         callback: usize,
     }
 
+    pub struct StaticConstantDescriptor {
+        name: String,
+        value: f64,
+    }
+
+    pub struct PropertyDescriptor {
+        name: String,
+        getter_callback: usize,
+        setter_callback: usize, /* 0 if the property is read-only */
+    }
+
     pub struct ResourceDescriptor {
         pub name: String,
         pub constructor: KjMaybe<ConstructorDescriptor>,
         pub methods: Vec<MethodDescriptor>,
         pub static_methods: Vec<StaticMethodDescriptor>,
+        pub static_constants: Vec<StaticConstantDescriptor>,
+        pub prototype_properties: Vec<PropertyDescriptor>,
+        pub instance_properties: Vec<PropertyDescriptor>,
     }
 
     // Resources
@@ -169,6 +370,12 @@ pub mod ffi {
             drop_callback: usize, /* R* -> () */
         ) -> Local /* v8::Local<Value> */;
 
+        pub unsafe fn attach_resource(
+            isolate: *mut Isolate,
+            resource: usize, /* R* */
+            object: &Local,  /* v8::Local<Object> */
+        );
+
         pub unsafe fn unwrap_resource(
             isolate: *mut Isolate,
             value: Local, /* v8::LocalValue */
@@ -218,7 +425,7 @@ pub struct Value;
 impl Display for Local<'_, Value> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut lock = unsafe { Lock::from_isolate_ptr(self.isolate.as_ffi()) };
-        match String::from_js(&mut lock, self.clone()) {
+        match std::string::String::from_js(&mut lock, self.clone()) {
             Ok(value) => write!(f, "{value}"),
             Err(e) => write!(f, "{e:?}"),
         }
@@ -227,6 +434,27 @@ impl Display for Local<'_, Value> {
 #[derive(Debug)]
 pub struct Object;
 pub struct FunctionTemplate;
+#[derive(Debug)]
+pub struct Array;
+#[derive(Debug)]
+pub struct Uint8Array;
+#[derive(Debug)]
+pub struct Promise;
+pub struct PromiseResolver;
+#[derive(Debug)]
+pub struct Function;
+#[derive(Debug)]
+pub struct String;
+#[derive(Debug)]
+pub struct Symbol;
+#[derive(Debug)]
+pub struct Date;
+#[derive(Debug)]
+pub struct Context;
+#[derive(Debug)]
+pub struct RegExp;
+
+pub use ffi::WellKnownSymbol;
 
 // Generic Local<'a, T> handle with lifetime
 #[derive(Debug)]
@@ -281,11 +509,11 @@ impl<'a, T> Local<'a, T> {
         &self.handle
     }
 
-    pub fn null(lock: &mut crate::Lock) -> Local<'a, Value> {
+    pub fn null(lock: &mut crate::Lock<'a>) -> Local<'a, Value> {
         unsafe { Local::from_ffi(lock.isolate(), ffi::local_new_null(lock.isolate().as_ffi())) }
     }
 
-    pub fn undefined(lock: &mut crate::Lock) -> Local<'a, Value> {
+    pub fn undefined(lock: &mut crate::Lock<'a>) -> Local<'a, Value> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
@@ -310,6 +538,11 @@ impl<'a, T> Local<'a, T> {
         unsafe { ffi::local_is_number(&self.handle) }
     }
 
+    /// Returns true if the value is a JavaScript `BigInt`.
+    pub fn is_big_int(&self) -> bool {
+        unsafe { ffi::local_is_big_int(&self.handle) }
+    }
+
     pub fn is_null(&self) -> bool {
         unsafe { ffi::local_is_null(&self.handle) }
     }
@@ -336,6 +569,51 @@ impl<'a, T> Local<'a, T> {
         unsafe { ffi::local_is_native_error(&self.handle) }
     }
 
+    /// Returns true if the value is a JavaScript promise.
+    pub fn is_promise(&self) -> bool {
+        unsafe { ffi::local_is_promise(&self.handle) }
+    }
+
+    /// Returns true if the value is a JavaScript function.
+    pub fn is_function(&self) -> bool {
+        unsafe { ffi::local_is_function(&self.handle) }
+    }
+
+    /// Returns true if the value is a JavaScript array.
+    pub fn is_array(&self) -> bool {
+        unsafe { ffi::local_is_array(&self.handle) }
+    }
+
+    /// Returns true if the value is an `ArrayBuffer`.
+    pub fn is_array_buffer(&self) -> bool {
+        unsafe { ffi::local_is_array_buffer(&self.handle) }
+    }
+
+    /// Returns true if the value is an `ArrayBufferView`, i.e. a typed array or `DataView`.
+    pub fn is_array_buffer_view(&self) -> bool {
+        unsafe { ffi::local_is_array_buffer_view(&self.handle) }
+    }
+
+    /// Returns true if the value is a `Uint8Array`.
+    pub fn is_uint8_array(&self) -> bool {
+        unsafe { ffi::local_is_uint8_array(&self.handle) }
+    }
+
+    /// Returns true if the value is a JavaScript symbol.
+    pub fn is_symbol(&self) -> bool {
+        unsafe { ffi::local_is_symbol(&self.handle) }
+    }
+
+    /// Returns true if the value is a `Date`.
+    pub fn is_date(&self) -> bool {
+        unsafe { ffi::local_is_date(&self.handle) }
+    }
+
+    /// Returns true if the value is a `RegExp`.
+    pub fn is_reg_exp(&self) -> bool {
+        unsafe { ffi::local_is_reg_exp(&self.handle) }
+    }
+
     /// Returns the JavaScript type of the underlying value as a string.
     ///
     /// Uses V8's native `TypeOf` method which returns the same result as
@@ -343,7 +621,7 @@ impl<'a, T> Local<'a, T> {
     /// "bigint", "string", "symbol", "function", or "object".
     ///
     /// Note: For `null`, this returns "object" (JavaScript's historical behavior).
-    pub fn type_of(&self) -> String {
+    pub fn type_of(&self) -> std::string::String {
         unsafe { ffi::local_type_of(self.isolate.as_ffi(), &self.handle) }
     }
 
@@ -364,8 +642,125 @@ impl<'a> Local<'a, Value> {
     pub fn to_global(self, lock: &'a mut Lock) -> Global<Value> {
         unsafe { ffi::local_to_global(lock.isolate().as_ffi(), self.into_ffi()).into() }
     }
+
+    /// Collects the values of an iterable, following the Web IDL `sequence<T>` conversion.
+    ///
+    /// Arrays are read by index; any other object is iterated through its `Symbol.iterator`
+    /// method. Returns the thrown exception as `Err` if the value is not an iterable object or
+    /// iteration throws.
+    pub fn iterate<'s>(
+        &self,
+        lock: &mut Lock<'s>,
+    ) -> Result<Vec<Local<'s, Value>>, Local<'s, Value>> {
+        let mut values = Vec::new();
+        let exception: Option<ffi::Local> =
+            unsafe { ffi::local_iterate(lock.isolate().as_ffi(), &self.handle, &mut values) }
+                .into();
+        let values = values
+            .into_iter()
+            .map(|value| unsafe { Local::from_ffi(lock.isolate(), value) })
+            .collect();
+        match exception {
+            Some(exception) => Err(unsafe { Local::from_ffi(lock.isolate(), exception) }),
+            None => Ok(values),
+        }
+    }
+
+    /// Returns the value as a `Local<T>` if it is a `T`, e.g. `value.try_cast::<v8::Date>()`.
+    ///
+    /// Unlike the `try_into_*()` methods, this does not consume the value, so that it can still
+    /// be used if it is not a `T`.
+    pub fn try_cast<T: Cast>(&self) -> Option<Local<'a, T>> {
+        T::is_type_of(self)
+            .then(|| unsafe { Local::from_ffi(self.isolate, ffi::local_clone(&self.handle)) })
+    }
+
+    /// Converts the value into a `Local<Object>`, or returns a `TypeError` if it is not an
+    /// object. Functions, arrays and other objects are all objects.
+    pub fn try_into_object(self) -> Result<Local<'a, Object>, crate::Error> {
+        self.try_into_type()
+    }
+
+    /// Converts the value into a `Local<Array>`, or returns a `TypeError` if it is not an array.
+    pub fn try_into_array(self) -> Result<Local<'a, Array>, crate::Error> {
+        self.try_into_type()
+    }
+
+    /// Converts the value into a `Local<Function>`, or returns a `TypeError` if it is not a
+    /// function.
+    pub fn try_into_function(self) -> Result<Local<'a, Function>, crate::Error> {
+        self.try_into_type()
+    }
+
+    /// Converts the value into a `Local<String>`, or returns a `TypeError` if it is not a
+    /// string. Unlike `String::from_js()`, other values are not converted to strings.
+    pub fn try_into_string(self) -> Result<Local<'a, String>, crate::Error> {
+        self.try_into_type()
+    }
+
+    /// Converts the value into a `Local<Promise>`, or returns a `TypeError` if it is not a
+    /// promise.
+    pub fn try_into_promise(self) -> Result<Local<'a, Promise>, crate::Error> {
+        self.try_into_type()
+    }
+
+    fn try_into_type<T: Cast>(self) -> Result<Local<'a, T>, crate::Error> {
+        if !T::is_type_of(&self) {
+            let msg = format!("Expected {} but got {}", T::description(), self.type_of());
+            return Err(crate::Error::new_type_error(msg));
+        }
+        Ok(unsafe { self.cast_unchecked() })
+    }
+
+    /// Reinterprets the value as a `Local<T>` without checking its type.
+    ///
+    /// # Safety
+    /// The value must be a `T`, e.g. an object for `Local<Object>`. V8 does not check the type
+    /// of a handle when it is used, so using a handle of the wrong type is undefined behavior.
+    pub unsafe fn cast_unchecked<T>(self) -> Local<'a, T> {
+        unsafe { Local::from_ffi(self.isolate, self.into_ffi()) }
+    }
 }
 
+/// Marker types that a `Local<Value>` can be checked against and cast to, see
+/// `Local::try_cast()`.
+pub trait Cast {
+    /// Returns true if `value` is of this type.
+    fn is_type_of(value: &Local<'_, Value>) -> bool;
+
+    /// Describes the type in error messages, e.g. "an object".
+    fn description() -> &'static str;
+}
+
+/// Implements `Cast` for marker types using the corresponding `Local::is_*()` check.
+macro_rules! impl_cast {
+    ($($type:ty => $is_type_of:ident, $description:literal);* $(;)?) => {
+        $(
+            impl Cast for $type {
+                fn is_type_of(value: &Local<'_, Value>) -> bool {
+                    value.$is_type_of()
+                }
+
+                fn description() -> &'static str {
+                    $description
+                }
+            }
+        )*
+    };
+}
+
+impl_cast!(
+    Object => is_object, "an object";
+    Array => is_array, "an Array";
+    Uint8Array => is_uint8_array, "a Uint8Array";
+    Promise => is_promise, "a Promise";
+    Function => is_function, "a Function";
+    String => is_string, "a string";
+    Symbol => is_symbol, "a symbol";
+    Date => is_date, "a Date";
+    RegExp => is_reg_exp, "a RegExp";
+);
+
 impl PartialEq for Local<'_, Value> {
     fn eq(&self, other: &Self) -> bool {
         unsafe { ffi::local_eq(&self.handle, &other.handle) }
@@ -385,40 +780,503 @@ impl<'a> From<Local<'a, FunctionTemplate>> for Local<'a, Value> {
     }
 }
 
+impl<'a> From<Local<'a, Array>> for Local<'a, Value> {
+    fn from(value: Local<'a, Array>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, Uint8Array>> for Local<'a, Value> {
+    fn from(value: Local<'a, Uint8Array>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, Promise>> for Local<'a, Value> {
+    fn from(value: Local<'a, Promise>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, Function>> for Local<'a, Value> {
+    fn from(value: Local<'a, Function>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, String>> for Local<'a, Value> {
+    fn from(value: Local<'a, String>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, Symbol>> for Local<'a, Value> {
+    fn from(value: Local<'a, Symbol>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, Date>> for Local<'a, Value> {
+    fn from(value: Local<'a, Date>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+impl<'a> From<Local<'a, RegExp>> for Local<'a, Value> {
+    fn from(value: Local<'a, RegExp>) -> Self {
+        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+    }
+}
+
+/// The key of an object property: a string, an integer index or a symbol.
+#[derive(Debug, Clone, Copy)]
+pub enum PropertyKey<'k> {
+    String(&'k str),
+    Index(u32),
+    Symbol(&'k Local<'k, Symbol>),
+}
+
+impl PropertyKey<'_> {
+    /// Returns the key as a V8 value to pass to the FFI functions.
+    fn to_ffi(self, lock: &mut Lock) -> ffi::Local {
+        match self {
+            Self::String(name) => unsafe {
+                ffi::local_new_internalized_string(lock.isolate().as_ffi(), name)
+            },
+            Self::Index(index) => unsafe {
+                ffi::local_new_number(lock.isolate().as_ffi(), f64::from(index))
+            },
+            Self::Symbol(symbol) => ffi::Local {
+                ptr: symbol.handle.ptr,
+            },
+        }
+    }
+}
+
+impl<'k> From<&'k str> for PropertyKey<'k> {
+    fn from(name: &'k str) -> Self {
+        Self::String(name)
+    }
+}
+
+impl<'k> From<&'k std::string::String> for PropertyKey<'k> {
+    fn from(name: &'k std::string::String) -> Self {
+        Self::String(name)
+    }
+}
+
+impl From<u32> for PropertyKey<'_> {
+    fn from(index: u32) -> Self {
+        Self::Index(index)
+    }
+}
+
+impl<'k> From<&'k Local<'k, Symbol>> for PropertyKey<'k> {
+    fn from(symbol: &'k Local<'k, Symbol>) -> Self {
+        Self::Symbol(symbol)
+    }
+}
+
+pub use ffi::PropertyAttributes;
+
 // Object-specific implementations
-impl<'a> Local<'a, Object> {
-    pub fn set(&mut self, lock: &mut Lock, key: &str, value: Local<'a, Value>) {
+impl Local<'_, Object> {
+    /// Sets a property, like `object[key] = value`, which runs any setter.
+    pub fn set<'k>(
+        &mut self,
+        lock: &mut Lock,
+        key: impl Into<PropertyKey<'k>>,
+        value: Local<'_, Value>,
+    ) {
+        let key = key.into().to_ffi(lock);
+        unsafe {
+            ffi::local_object_set(lock.isolate().as_ffi(), &self.handle, key, value.into_ffi());
+        }
+    }
+
+    /// Returns true if the object or its prototype chain has the property, like `key in object`.
+    pub fn has<'k>(&self, lock: &mut Lock, key: impl Into<PropertyKey<'k>>) -> bool {
+        let key = key.into().to_ffi(lock);
+        unsafe { ffi::local_object_has(lock.isolate().as_ffi(), &self.handle, key) }
+    }
+
+    /// Returns the value of a property, like `object[key]`, which runs any getter. The value is
+    /// `undefined` if neither the object nor its prototype chain has the property.
+    ///
+    /// Returns the exception as `Err` if a getter or a `Proxy` trap threw.
+    pub fn get<'k, 's>(
+        &self,
+        lock: &mut Lock<'s>,
+        key: impl Into<PropertyKey<'k>>,
+    ) -> Result<Local<'s, Value>, crate::Error> {
+        let key = key.into().to_ffi(lock);
+        let value: Option<ffi::Local> = lock
+            .try_catch(|lock| unsafe {
+                ffi::local_object_get(lock.isolate().as_ffi(), &self.handle, key).into()
+            })
+            .map_err(|exception| exception.to_error(lock))?;
+        let Some(value) = value else {
+            unreachable!("getting a property only fails by throwing");
+        };
+        Ok(unsafe { Local::from_ffi(lock.isolate(), value) })
+    }
+
+    /// Deletes an own property, like `delete object[key]`. Returns false if the property is not
+    /// configurable.
+    pub fn delete<'k>(&mut self, lock: &mut Lock, key: impl Into<PropertyKey<'k>>) -> bool {
+        let key = key.into().to_ffi(lock);
+        unsafe { ffi::local_object_delete(lock.isolate().as_ffi(), &self.handle, key) }
+    }
+
+    /// Defines an own data property with the given attributes, like `Object.defineProperty()`,
+    /// replacing any existing property. Unlike `set()`, this does not run setters.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // A read-only property that is skipped by `Object.keys()`.
+    /// object.define_property(lock, "kind", value, v8::PropertyAttributes::default());
+    /// ```
+    pub fn define_property<'k>(
+        &mut self,
+        lock: &mut Lock,
+        key: impl Into<PropertyKey<'k>>,
+        value: Local<'_, Value>,
+        attributes: PropertyAttributes,
+    ) {
+        let key = key.into().to_ffi(lock);
         unsafe {
-            ffi::local_object_set_property(
+            ffi::local_object_define_property(
                 lock.isolate().as_ffi(),
-                &mut self.handle,
+                &self.handle,
                 key,
                 value.into_ffi(),
+                attributes,
             );
         }
     }
 
-    pub fn has(&self, lock: &mut Lock, key: &str) -> bool {
-        unsafe { ffi::local_object_has_property(lock.isolate().as_ffi(), &self.handle, key) }
+    /// Returns the names of the object's own enumerable string-keyed properties, in property
+    /// order, like `Object.keys()`. Integer indices are converted to strings and symbols are
+    /// skipped.
+    pub fn get_own_property_names(&self, lock: &mut Lock) -> Vec<std::string::String> {
+        unsafe { ffi::local_object_get_own_property_names(lock.isolate().as_ffi(), &self.handle) }
+    }
+
+    /// Returns the keys of all of the object's own properties, enumerable or not, like
+    /// `Reflect.ownKeys()`. Keys are strings, with integer indices converted to strings, or
+    /// symbols.
+    pub fn get_own_property_keys<'s>(&self, lock: &mut Lock<'s>) -> Vec<Local<'s, Value>> {
+        unsafe { ffi::local_object_get_own_property_keys(lock.isolate().as_ffi(), &self.handle) }
+            .into_iter()
+            .map(|key| unsafe { Local::from_ffi(lock.isolate(), key) })
+            .collect()
+    }
+
+    /// Returns the prototype of the object, which is an object or `null`, like
+    /// `Object.getPrototypeOf()`.
+    pub fn get_prototype<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_object_get_prototype(lock.isolate().as_ffi(), &self.handle),
+            )
+        }
+    }
+
+    /// Sets the prototype of the object to an object or `null`, like `Object.setPrototypeOf()`.
+    ///
+    /// Returns a `TypeError` if `prototype` is neither, if the object is not extensible, or if
+    /// the prototype chain would contain a cycle.
+    pub fn set_prototype(
+        &mut self,
+        lock: &mut Lock,
+        prototype: Local<'_, Value>,
+    ) -> Result<(), crate::Error> {
+        if !prototype.is_object() && !prototype.is_null() {
+            let msg = format!("Expected an object or null but got {}", prototype.type_of());
+            return Err(crate::Error::new_type_error(msg));
+        }
+        let prototype = unsafe { prototype.into_ffi() };
+        let set = lock
+            .try_catch(|lock| unsafe {
+                ffi::local_object_set_prototype(lock.isolate().as_ffi(), &self.handle, prototype)
+            })
+            .map_err(|exception| exception.to_error(lock))?;
+        if !set {
+            return Err(crate::Error::new_type_error(
+                "Failed to set the object's prototype",
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Array-specific implementations
+impl<'a> Local<'a, Array> {
+    /// Creates an array holding `values` in the current context.
+    pub fn new(lock: &mut Lock<'a>, values: Vec<Local<'_, Value>>) -> Self {
+        let values = values
+            .into_iter()
+            .map(|value| unsafe { value.into_ffi() })
+            .collect();
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_new_array(lock.isolate().as_ffi(), values),
+            )
+        }
+    }
+}
+
+// Uint8Array-specific implementations
+impl<'a> Local<'a, Uint8Array> {
+    /// Creates a `Uint8Array` over a new `ArrayBuffer` holding a copy of `data`.
+    pub fn new(lock: &mut Lock<'a>, data: &[u8]) -> Self {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_new_uint8_array(lock.isolate().as_ffi(), data),
+            )
+        }
+    }
+}
+
+// Function-specific implementations
+impl Local<'_, Function> {
+    /// Calls the function with `receiver` as `this`.
+    ///
+    /// Returns the thrown exception as `Err`, or `Err(None)` if execution was terminated while
+    /// the function ran.
+    pub fn call<'s>(
+        &self,
+        lock: &mut Lock<'s>,
+        receiver: Local<'_, Value>,
+        args: Vec<Local<'_, Value>>,
+    ) -> Result<Local<'s, Value>, Option<Local<'s, Value>>> {
+        let args = args
+            .into_iter()
+            .map(|arg| unsafe { arg.into_ffi() })
+            .collect();
+        let result = unsafe {
+            ffi::local_function_call(
+                lock.isolate().as_ffi(),
+                &self.handle,
+                receiver.into_ffi(),
+                args,
+            )
+        };
+        let value: Option<ffi::Local> = result.value.into();
+        let exception: Option<ffi::Local> = result.exception.into();
+        match value {
+            Some(value) => Ok(unsafe { Local::from_ffi(lock.isolate(), value) }),
+            None => {
+                Err(exception
+                    .map(|exception| unsafe { Local::from_ffi(lock.isolate(), exception) }))
+            }
+        }
+    }
+}
+
+// String-specific implementations
+impl<'a> Local<'a, String> {
+    /// Creates a string from UTF-8 `value`.
+    pub fn new(lock: &mut Lock<'a>, value: &str) -> Self {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_new_string(lock.isolate().as_ffi(), value),
+            )
+        }
+    }
+
+    /// Returns the contents of the string.
+    pub fn value(&self, lock: &mut Lock) -> std::string::String {
+        unsafe { ffi::unwrap_string(lock.isolate().as_ffi(), ffi::local_clone(&self.handle)) }
+    }
+}
+
+// Symbol-specific implementations
+impl<'a> Local<'a, Symbol> {
+    /// Creates a new unique symbol, like `Symbol(description)`.
+    pub fn new(lock: &mut Lock<'a>, description: &str) -> Self {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_new_symbol(lock.isolate().as_ffi(), description),
+            )
+        }
+    }
+
+    /// Returns a well-known symbol, e.g. `WellKnownSymbol::ToStringTag` for
+    /// `Symbol.toStringTag`.
+    pub fn well_known(lock: &mut Lock<'a>, symbol: WellKnownSymbol) -> Self {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_symbol_well_known(lock.isolate().as_ffi(), symbol),
+            )
+        }
     }
 
-    pub fn get(&self, lock: &mut Lock, key: &str) -> Option<Local<'a, Value>> {
-        if !self.has(lock, key) {
+    /// Returns the symbol's description, or `None` if it was created without one.
+    pub fn description(&self, lock: &mut Lock) -> Option<std::string::String> {
+        let description: Local<Value> = unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_symbol_description(lock.isolate().as_ffi(), &self.handle),
+            )
+        };
+        if description.is_undefined() {
             return None;
         }
+        Some(unsafe { ffi::unwrap_string(lock.isolate().as_ffi(), description.into_ffi()) })
+    }
+}
 
+/// The time value of the latest and earliest `Date`, in milliseconds since the epoch.
+const MAX_TIME_VALUE: f64 = 8.64e15;
+
+// Date-specific implementations
+impl<'a> Local<'a, Date> {
+    /// Creates a `Date` for `time`, truncated to milliseconds. Times outside the range of a
+    /// `Date` result in an invalid `Date`.
+    pub fn new(lock: &mut Lock<'a>, time: SystemTime) -> Self {
+        #[expect(clippy::cast_precision_loss)]
+        let millis = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_millis() as f64,
+            Err(err) => -(err.duration().as_millis() as f64),
+        };
         unsafe {
-            let maybe_local =
-                ffi::local_object_get_property(lock.isolate().as_ffi(), &self.handle, key);
-            let opt_local: Option<ffi::Local> = maybe_local.into();
-            opt_local.map(|local| Local::from_ffi(lock.isolate(), local))
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_new_date(lock.isolate().as_ffi(), millis),
+            )
         }
     }
+
+    /// Returns the time value of the `Date` in milliseconds since the epoch, which is `NaN` for
+    /// an invalid `Date`, like `Date.prototype.valueOf()`.
+    pub fn value_of(&self) -> f64 {
+        unsafe { ffi::local_date_value_of(&self.handle) }
+    }
+
+    /// Returns the time of the `Date`, or `None` if it is invalid.
+    pub fn to_system_time(&self) -> Option<SystemTime> {
+        system_time_from_millis(self.value_of())
+    }
 }
 
-impl<'a> From<Local<'a, Value>> for Local<'a, Object> {
-    fn from(value: Local<'a, Value>) -> Self {
-        unsafe { Self::from_ffi(value.isolate, value.into_ffi()) }
+/// Converts a time value in milliseconds since the epoch to a `SystemTime`, following the
+/// `TimeClip` operation used by `Date`. Returns `None` if the time value is not finite or
+/// outside the range of a `Date`.
+pub(crate) fn system_time_from_millis(millis: f64) -> Option<SystemTime> {
+    if !millis.is_finite() || millis.abs() > MAX_TIME_VALUE {
+        return None;
+    }
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_millis(millis.abs() as u64);
+    if millis < 0.0 {
+        UNIX_EPOCH.checked_sub(duration)
+    } else {
+        UNIX_EPOCH.checked_add(duration)
+    }
+}
+
+// RegExp-specific implementations
+impl<'a> Local<'a, RegExp> {
+    /// Creates a regular expression, like `new RegExp(pattern, flags)`.
+    ///
+    /// Returns a `SyntaxError` if the pattern or the flags are invalid.
+    pub fn new(lock: &mut Lock<'a>, pattern: &str, flags: &str) -> Result<Self, crate::Error> {
+        lock.try_catch(|lock| unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::local_new_reg_exp(lock.isolate().as_ffi(), pattern, flags),
+            )
+        })
+        .map_err(|exception| exception.to_error(lock))
+    }
+
+    /// Returns the pattern of the regular expression, like `RegExp.prototype.source`.
+    pub fn source(&self, lock: &mut Lock) -> std::string::String {
+        unsafe { ffi::local_reg_exp_source(lock.isolate().as_ffi(), &self.handle) }
+    }
+
+    /// Returns the flags of the regular expression in canonical order, like
+    /// `RegExp.prototype.flags`.
+    pub fn flags(&self) -> std::string::String {
+        unsafe { ffi::local_reg_exp_flags(&self.handle) }
+    }
+}
+
+// Context-specific implementations
+impl Local<'_, Context> {
+    /// Returns the global object of the context, like `globalThis` in its scripts.
+    pub fn global<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Object> {
+        unsafe { Local::from_ffi(lock.isolate(), ffi::local_context_global(&self.handle)) }
+    }
+}
+
+impl PartialEq for Local<'_, Context> {
+    fn eq(&self, other: &Self) -> bool {
+        unsafe { ffi::local_eq(&self.handle, &other.handle) }
+    }
+}
+
+// FunctionTemplate-specific implementations
+impl Local<'_, FunctionTemplate> {
+    /// Returns the JavaScript constructor function for this template in the current context.
+    pub fn get_function<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::function_template_get_function(lock.isolate().as_ffi(), &self.handle),
+            )
+        }
+    }
+
+    /// Returns true if `value` is an object instantiated from this template, analogous to
+    /// `v8::FunctionTemplate::HasInstance`.
+    pub fn has_instance(&self, value: &Local<Value>) -> bool {
+        unsafe { ffi::function_template_has_instance(&self.handle, &value.handle) }
+    }
+}
+
+// PromiseResolver-specific implementations
+impl<'a> Local<'a, PromiseResolver> {
+    /// Creates a resolver for a new pending promise in the current context.
+    pub fn new(lock: &mut Lock<'a>) -> Self {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::promise_resolver_new(lock.isolate().as_ffi()),
+            )
+        }
+    }
+
+    /// Returns the promise controlled by this resolver.
+    pub fn get_promise<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Promise> {
+        unsafe {
+            Local::from_ffi(
+                lock.isolate(),
+                ffi::promise_resolver_get_promise(lock.isolate().as_ffi(), &self.handle),
+            )
+        }
+    }
+
+    /// Resolves the promise with `value`. Has no effect if the promise is already settled.
+    pub fn resolve(&self, lock: &mut Lock, value: Local<'_, Value>) {
+        unsafe {
+            ffi::promise_resolver_resolve(lock.isolate().as_ffi(), &self.handle, value.into_ffi());
+        }
+    }
+
+    /// Rejects the promise with `value`. Has no effect if the promise is already settled.
+    pub fn reject(&self, lock: &mut Lock, value: Local<'_, Value>) {
+        unsafe {
+            ffi::promise_resolver_reject(lock.isolate().as_ffi(), &self.handle, value.into_ffi());
+        }
     }
 }
 
@@ -449,7 +1307,7 @@ impl<T> Global<T> {
         &self.handle
     }
 
-    pub fn as_local<'a>(&self, lock: &mut Lock) -> Local<'a, FunctionTemplate> {
+    pub fn as_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, T> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
@@ -461,6 +1319,9 @@ impl<T> Global<T> {
     /// Makes this global handle weak, allowing V8 to garbage collect the object
     /// and invoke the callback when the object is being collected.
     ///
+    /// The callback runs during garbage collection and must reset this handle, e.g. by dropping
+    /// the `Global`. It must not call into JavaScript.
+    ///
     /// # Safety
     /// The caller must ensure:
     /// - `isolate` is a valid V8 isolate wrapper
@@ -481,6 +1342,11 @@ impl<T> Global<T> {
             );
         }
     }
+
+    /// Makes a weak handle strong again, so that its callback is no longer invoked.
+    pub fn clear_weak(&mut self) {
+        unsafe { ffi::global_clear_weak(&raw mut self.handle) };
+    }
 }
 
 impl<T> From<Local<'_, T>> for Global<T> {
@@ -520,39 +1386,65 @@ impl<T> Clone for Global<T> {
 }
 
 pub trait ToLocalValue {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value>;
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value>;
 }
 
-impl ToLocalValue for u8 {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value> {
+/// Implements `ToLocalValue` for integer types that are losslessly representable as a JS number.
+macro_rules! impl_to_local_number {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl ToLocalValue for $type {
+                fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
+                    unsafe {
+                        Local::from_ffi(
+                            lock.isolate(),
+                            ffi::local_new_number(lock.isolate().as_ffi(), f64::from(*self)),
+                        )
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_to_local_number!(i8, i16, i32, u8, u16, u32);
+
+impl ToLocalValue for i64 {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
-                ffi::local_new_number(lock.isolate().as_ffi(), f64::from(*self)),
+                ffi::local_new_big_int(lock.isolate().as_ffi(), *self),
             )
         }
     }
 }
 
-impl ToLocalValue for u32 {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value> {
+impl ToLocalValue for u64 {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
-                ffi::local_new_number(lock.isolate().as_ffi(), f64::from(*self)),
+                ffi::local_new_big_int_from_unsigned(lock.isolate().as_ffi(), *self),
             )
         }
     }
 }
 
-impl ToLocalValue for String {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value> {
+impl ToLocalValue for usize {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
+        (*self as u64).to_local(lock)
+    }
+}
+
+impl ToLocalValue for std::string::String {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
         self.as_str().to_local(lock)
     }
 }
 
 impl ToLocalValue for &str {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value> {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
@@ -563,7 +1455,7 @@ impl ToLocalValue for &str {
 }
 
 impl ToLocalValue for bool {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value> {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
@@ -574,7 +1466,7 @@ impl ToLocalValue for bool {
 }
 
 impl ToLocalValue for f64 {
-    fn to_local<'a>(&self, lock: &mut Lock) -> Local<'a, Value> {
+    fn to_local<'s>(&self, lock: &mut Lock<'s>) -> Local<'s, Value> {
         unsafe {
             Local::from_ffi(
                 lock.isolate(),
@@ -602,6 +1494,8 @@ impl<'a> FunctionCallbackInfo<'a> {
         unsafe { Local::from_ffi(self.isolate(), ffi::fci_get_this(self.0)) }
     }
 
+    /// Returns the number of arguments the function was called with, which may be more or fewer
+    /// than it declares.
     pub fn len(&self) -> usize {
         unsafe { ffi::fci_get_length(self.0) }
     }
@@ -610,11 +1504,17 @@ impl<'a> FunctionCallbackInfo<'a> {
         self.len() == 0
     }
 
+    /// Returns the argument at `index`, or `undefined` if fewer arguments were passed, like
+    /// `info[index]` in C++.
     pub fn get(&self, index: usize) -> Local<'a, Value> {
-        debug_assert!(index <= self.len());
         unsafe { Local::from_ffi(self.isolate(), ffi::fci_get_arg(self.0, index)) }
     }
 
+    /// Returns true if the function was invoked with `new`.
+    pub fn is_construct_call(&self) -> bool {
+        unsafe { ffi::fci_is_construct_call(self.0) }
+    }
+
     pub fn set_return_value(&self, value: Local<Value>) {
         unsafe {
             ffi::fci_set_return_value(self.0, value.into_ffi());
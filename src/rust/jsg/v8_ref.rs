@@ -0,0 +1,132 @@
+//! Handles to JavaScript values stored in Rust objects, analogous to `jsg::V8Ref<T>` in C++ JSG.
+
+use std::marker::PhantomData;
+
+use kj_rs::KjOwn;
+
+use crate::Error;
+use crate::FromJS;
+use crate::GcVisitor;
+use crate::Lock;
+use crate::MemoryTracker;
+use crate::ToJS;
+use crate::Type;
+use crate::ffi;
+use crate::v8;
+
+/// A persistent handle to a JavaScript value, meant to be stored in resources.
+///
+/// Unlike `v8::Global<T>`, a `V8Ref<T>` held by a resource does not keep its value alive on its
+/// own while the resource is only reachable from JavaScript. Instead, V8 traces the value through
+/// the resource's wrapper, see `Type::visit_for_gc()`, so that reference cycles between
+/// JavaScript and Rust, such as a resource holding a callback that refers back to the resource,
+/// can be collected.
+///
+/// # Example
+///
+/// ```ignore
+/// #[jsg_resource]
+/// struct EventTarget {
+///     _state: jsg::ResourceState,
+///     listener: Option<jsg::V8Ref<jsg::v8::Object>>,
+/// }
+///
+/// #[jsg_resource]
+/// impl EventTarget {
+///     #[jsg_method]
+///     pub fn set_listener(&mut self, listener: jsg::V8Ref<jsg::v8::Object>) {
+///         self.listener = Some(listener);
+///     }
+/// }
+/// ```
+pub struct V8Ref<T> {
+    data: KjOwn<ffi::TracedData>,
+    _marker: PhantomData<T>,
+}
+
+/// A handle to any JavaScript value, analogous to `jsg::Value` in C++ JSG.
+pub type Value = V8Ref<v8::Value>;
+
+impl<T> V8Ref<T> {
+    /// Returns a local handle to the value.
+    pub fn as_local<'s>(&self, lock: &mut Lock<'s>) -> v8::Local<'s, T> {
+        unsafe {
+            v8::Local::from_ffi(
+                lock.isolate(),
+                ffi::traced_data_to_local(lock.isolate().as_ffi(), &self.data),
+            )
+        }
+    }
+
+    pub(crate) fn data(&self) -> &ffi::TracedData {
+        &self.data
+    }
+}
+
+impl<T> From<v8::Local<'_, T>> for V8Ref<T> {
+    fn from(local: v8::Local<'_, T>) -> Self {
+        let isolate = local.isolate();
+        Self {
+            data: unsafe { ffi::traced_data_create(isolate.as_ffi(), local.into_ffi()) },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for V8Ref<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: unsafe { ffi::traced_data_clone(&self.data) },
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Implements `Type`, `ToJS`, and `FromJS` for `V8Ref<T>` of a V8 type.
+macro_rules! impl_v8_ref {
+    ($type:ty, $class_name:literal, |$value:pat_param| $is_exact:expr) => {
+        impl Type for V8Ref<$type> {
+            fn class_name() -> &'static str {
+                $class_name
+            }
+
+            fn is_exact($value: &v8::Local<v8::Value>) -> bool {
+                $is_exact
+            }
+
+            fn track_memory(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+                tracker.track_v8_ref(edge_name, self);
+            }
+
+            fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+                visitor.visit_v8_ref(self);
+            }
+        }
+
+        impl ToJS for V8Ref<$type> {
+            fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+                self.as_local(lock).into()
+            }
+        }
+
+        impl FromJS for V8Ref<$type> {
+            type ResultType = Self;
+
+            fn from_js(
+                _lock: &mut Lock,
+                value: v8::Local<v8::Value>,
+            ) -> Result<Self::ResultType, Error> {
+                if !Self::is_exact(&value) {
+                    let msg = format!("Expected {} but got {}", $class_name, value.type_of());
+                    return Err(Error::new_type_error(msg));
+                }
+                let value: v8::Local<$type> = unsafe { value.cast_unchecked() };
+                Ok(value.into())
+            }
+        }
+    };
+}
+
+impl_v8_ref!(v8::Value, "any", |_| true);
+impl_v8_ref!(v8::Object, "object", |value| value.is_object());
+impl_v8_ref!(v8::Function, "Function", |value| value.is_function());
@@ -13,16 +13,44 @@
 //! | `&str` | `string` |
 //! | `bool` | `boolean` |
 //! | `f64` | `number` |
+//! | `i8`, `i16`, `i32`, `u8`, `u16`, `u32` | `number` |
+//! | `i64`, `u64`, `usize` | `bigint` (unwrap also accepts `number`) |
+//! | `EnforceRange<T>`, `Clamp<T>` | integer `T` with `[EnforceRange]` / `[Clamp]` |
 //! | `Option<T>` | `T` or `undefined` |
 //! | `Nullable<T>` | `T`, `null`, or `undefined` |
 //! | `Result<T, E>` | `T` or throws |
 //! | `NonCoercible<T>` | `T` (strict type checking) |
 //! | `T: Struct` | `object` |
-
+//! | `SystemTime` | `Date` (unwrap also accepts `number`) |
+//! | `Vec<T>` | `Array` |
+//! | `Vec<u8>` | `BufferSource` (unwrap, copied), `Uint8Array` (wrap) |
+//! | `BufferSource`, `&[u8]` | `ArrayBuffer` or `ArrayBufferView` (borrowed) |
+//! | `Sequence<T>` | any iterable (unwrap), `Array` (wrap) |
+//! | `HashMap<String, T>`, `BTreeMap<String, T>` | `object` (record) |
+//! | `Promise<T>` | `Promise` |
+//! | `Function<fn(A, B) -> R>` | `Function` |
+//! | `Ref<R>`, `R: Resource` | instance of the resource's class |
+//! | `Value` (`V8Ref<v8::Value>`) | any value |
+//! | `V8Ref<v8::Object>` | `object` |
+//! | `V8Ref<v8::Function>` | `Function` |
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+use std::time::SystemTime;
+
+use crate::BufferSource;
+use crate::Clamp;
+use crate::EnforceRange;
 use crate::Error;
+use crate::GcVisitor;
 use crate::Lock;
+use crate::MemoryTracker;
 use crate::NonCoercible;
 use crate::Nullable;
+use crate::Ref;
+use crate::Resource;
+use crate::Sequence;
 use crate::Type;
 use crate::v8;
 use crate::v8::ToLocalValue;
@@ -36,9 +64,14 @@ use crate::v8::ToLocalValue;
 /// Provides Rust → JavaScript conversion.
 pub trait ToJS: Sized {
     /// Converts this Rust value into a JavaScript value.
-    fn to_js<'a, 'b>(self, lock: &'a mut Lock) -> v8::Local<'b, v8::Value>
-    where
-        'b: 'a;
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value>;
+
+    /// Converts a `Vec` of this type into a JavaScript value. Produces an `Array` by default;
+    /// `Vec<u8>` produces a `Uint8Array` instead.
+    #[doc(hidden)]
+    fn vec_to_js<'s>(values: Vec<Self>, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        wrap_array(values, lock)
+    }
 }
 
 // =============================================================================
@@ -55,6 +88,32 @@ pub trait FromJS: Sized {
     /// Converts a JavaScript value into this Rust type.
     fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error>;
 
+    /// Converts a JavaScript value into a `Vec` of this type. Accepts an `Array` by default;
+    /// `Vec<u8>` accepts a `BufferSource` instead.
+    #[doc(hidden)]
+    fn vec_from_js(
+        lock: &mut Lock,
+        value: v8::Local<v8::Value>,
+    ) -> Result<Vec<Self::ResultType>, Error> {
+        if !value.is_array() {
+            let msg = format!("Expected an Array but got {}", value.type_of());
+            return Err(Error::new_type_error(msg));
+        }
+        unwrap_elements::<Self>(lock, &value)
+    }
+
+    /// The JavaScript class name of `Vec<Self>`, used in error messages.
+    #[doc(hidden)]
+    fn vec_class_name() -> &'static str {
+        "Array"
+    }
+
+    /// Returns true if the V8 value is exactly the JavaScript type of `Vec<Self>`.
+    #[doc(hidden)]
+    fn vec_is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_array()
+    }
+
     /// Tries to convert only if the JavaScript type matches exactly.
     /// Returns `None` if the type doesn't match, `Some(result)` if conversion was attempted.
     /// Used by `#[jsg_oneof]` macro to try each variant without coercion.
@@ -79,7 +138,7 @@ pub trait FromJS: Sized {
 
 /// Implements `Type`, `ToJS`, and `FromJS` for primitive types.
 macro_rules! impl_primitive {
-    { $type:ty, $class_name:literal, $is_exact:ident, $unwrap_fn:ident } => {
+    { $type:ty, $class_name:literal, $is_exact:ident, $unwrap_fn:ident $(, $memory_info:ident)? } => {
         impl Type for $type {
             fn class_name() -> &'static str {
                 $class_name
@@ -88,13 +147,16 @@ macro_rules! impl_primitive {
             fn is_exact(value: &v8::Local<v8::Value>) -> bool {
                 value.$is_exact()
             }
+
+            $(
+                fn memory_info(&self, tracker: &mut MemoryTracker) {
+                    $memory_info(self, tracker);
+                }
+            )?
         }
 
         impl ToJS for $type {
-            fn to_js<'a, 'b>(self, lock: &'a mut Lock) -> v8::Local<'b, v8::Value>
-            where
-                'b: 'a,
-            {
+            fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
                 self.to_local(lock)
             }
         }
@@ -109,10 +171,302 @@ macro_rules! impl_primitive {
     };
 }
 
-impl_primitive!(String, "string", is_string, unwrap_string);
+impl_primitive!(
+    String,
+    "string",
+    is_string,
+    unwrap_string,
+    string_memory_info
+);
 impl_primitive!(bool, "boolean", is_boolean, unwrap_boolean);
 impl_primitive!(f64, "number", is_number, unwrap_number);
 
+/// Tracks the heap buffer of a `String`.
+fn string_memory_info(value: &String, tracker: &mut MemoryTracker) {
+    tracker.track_field_with_size("data", value.capacity());
+}
+
+/// The range of an integer type, used to implement the Web IDL integer conversions.
+#[derive(Clone, Copy)]
+struct IntegerRange {
+    bits: u32,
+    signed: bool,
+    name: &'static str,
+}
+
+impl IntegerRange {
+    /// The default Web IDL conversion: NaN and infinities become 0, and the value is truncated
+    /// and wrapped modulo 2^N into the range of the type.
+    fn wrap(self, number: f64) -> f64 {
+        if !number.is_finite() {
+            return 0.0;
+        }
+        let modulo = f64::from(self.bits).exp2();
+        let value = number.trunc().rem_euclid(modulo);
+        if self.signed && value >= modulo / 2.0 {
+            value - modulo
+        } else {
+            value
+        }
+    }
+
+    /// The bounds used by `[EnforceRange]` and `[Clamp]`. As in Web IDL, 64-bit types are
+    /// limited to the range of integers that a JS number represents exactly.
+    fn bounds(self) -> (f64, f64) {
+        if self.bits == 64 {
+            let upper = 53_f64.exp2() - 1.0;
+            return (if self.signed { -upper } else { 0.0 }, upper);
+        }
+        if self.signed {
+            let half = f64::from(self.bits - 1).exp2();
+            (-half, half - 1.0)
+        } else {
+            (0.0, f64::from(self.bits).exp2() - 1.0)
+        }
+    }
+
+    /// The Web IDL `[EnforceRange]` conversion: throws unless the truncated value is in range.
+    fn enforce_range(self, number: f64) -> Result<f64, Error> {
+        if !number.is_finite() {
+            return Err(Error::new_type_error(format!(
+                "Value must be a finite number to convert to {}",
+                self.name
+            )));
+        }
+        let (lower, upper) = self.bounds();
+        let value = number.trunc();
+        if value < lower || value > upper {
+            return Err(Error::new_type_error(format!(
+                "Value {number} is outside the range of {} ({lower} to {upper})",
+                self.name
+            )));
+        }
+        Ok(value)
+    }
+
+    /// The Web IDL `[Clamp]` conversion: clamps to the range and rounds ties to even.
+    fn clamp(self, number: f64) -> f64 {
+        if number.is_nan() {
+            return 0.0;
+        }
+        let (lower, upper) = self.bounds();
+        number.clamp(lower, upper).round_ties_even()
+    }
+}
+
+/// Converts an integral `value` within the range of `T` into `T`.
+fn to_integer<T: TryFrom<i128> + Default>(value: f64) -> T {
+    T::try_from(value as i128).unwrap_or_default()
+}
+
+/// Implements `Type`, `ToJS`, and `FromJS` for integer types that are converted to and from
+/// JS numbers, along with `FromJS` for their `EnforceRange` and `Clamp` wrappers.
+///
+/// Unwrapping follows the default Web IDL integer conversion: the value is coerced to a
+/// number, NaN and infinities become 0, and the result is truncated and wrapped modulo 2^N.
+///
+/// `$type => $bytes` converts `Vec<$type>` as binary data through `$bytes` instead of as an
+/// `Array` of numbers.
+macro_rules! impl_integer {
+    ($($type:ty $(=> $bytes:ident)?),* $(,)?) => {
+        $(
+            impl Type for $type {
+                fn class_name() -> &'static str {
+                    "number"
+                }
+
+                fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+                    value.is_number()
+                }
+            }
+
+            impl ToJS for $type {
+                fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+                    self.to_local(lock)
+                }
+
+                $(
+                    fn vec_to_js<'s>(values: Vec<Self>, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+                        $bytes::wrap_bytes(&values, lock)
+                    }
+                )?
+            }
+
+            impl FromJS for $type {
+                type ResultType = Self;
+
+                fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+                    let number = f64::from_js(lock, value)?;
+                    Ok(to_integer(integer_range!($type).wrap(number)))
+                }
+
+                $(
+                    fn vec_from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Vec<Self>, Error> {
+                        $bytes::unwrap_bytes(lock, value)
+                    }
+
+                    fn vec_class_name() -> &'static str {
+                        $bytes::class_name()
+                    }
+
+                    fn vec_is_exact(value: &v8::Local<v8::Value>) -> bool {
+                        $bytes::is_exact(value)
+                    }
+                )?
+            }
+
+            impl FromJS for EnforceRange<$type> {
+                type ResultType = Self;
+
+                fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+                    let number = f64::from_js(lock, value)?;
+                    let value = integer_range!($type).enforce_range(number)?;
+                    Ok(Self::new(to_integer(value)))
+                }
+            }
+
+            impl FromJS for Clamp<$type> {
+                type ResultType = Self;
+
+                fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+                    let number = f64::from_js(lock, value)?;
+                    Ok(Self::new(to_integer(integer_range!($type).clamp(number))))
+                }
+            }
+        )*
+    };
+}
+
+/// Returns the `IntegerRange` of an integer type.
+macro_rules! integer_range {
+    ($type:ty) => {
+        IntegerRange {
+            bits: <$type>::BITS,
+            signed: <$type>::MIN != 0,
+            name: stringify!($type),
+        }
+    };
+}
+
+impl_integer!(i8, i16, i32, u8 => BufferSource, u16, u32);
+
+/// Implements `Type`, `ToJS`, and `FromJS` for 64-bit integer types that are converted to and
+/// from JS `BigInt`s, along with `FromJS` for their `EnforceRange` and `Clamp` wrappers.
+///
+/// A `BigInt` must fit the type exactly; otherwise a `RangeError` is thrown, or with `Clamp`
+/// the value saturates. Any other value is converted like a Web IDL `long long` or
+/// `unsigned long long`.
+macro_rules! impl_big_int {
+    ($($type:ty => $unwrap_fn:ident),* $(,)?) => {
+        $(
+            impl Type for $type {
+                fn class_name() -> &'static str {
+                    "bigint"
+                }
+
+                fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+                    value.is_big_int()
+                }
+            }
+
+            impl ToJS for $type {
+                fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+                    self.to_local(lock)
+                }
+            }
+
+            impl FromJS for $type {
+                type ResultType = Self;
+
+                fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+                    if value.is_big_int() {
+                        let mut lossless = false;
+                        let result = unsafe { v8::ffi::$unwrap_fn(value.as_ffi(), &mut lossless) };
+                        if !lossless {
+                            return Err(Error::new_range_error(format!(
+                                "BigInt value {value} is out of range for {}",
+                                stringify!($type)
+                            )));
+                        }
+                        return Ok(result);
+                    }
+                    let number = f64::from_js(lock, value)?;
+                    Ok(to_integer(integer_range!($type).wrap(number)))
+                }
+            }
+
+            impl FromJS for EnforceRange<$type> {
+                type ResultType = Self;
+
+                fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+                    if value.is_big_int() {
+                        return Ok(Self::new(<$type>::from_js(lock, value)?));
+                    }
+                    let number = f64::from_js(lock, value)?;
+                    let value = integer_range!($type).enforce_range(number)?;
+                    Ok(Self::new(to_integer(value)))
+                }
+            }
+
+            impl FromJS for Clamp<$type> {
+                type ResultType = Self;
+
+                fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+                    if value.is_big_int() {
+                        let mut lossless = false;
+                        let result = unsafe { v8::ffi::$unwrap_fn(value.as_ffi(), &mut lossless) };
+                        if lossless {
+                            return Ok(Self::new(result));
+                        }
+                        let negative = unsafe { v8::ffi::unwrap_big_int_is_negative(value.as_ffi()) };
+                        return Ok(Self::new(if negative { <$type>::MIN } else { <$type>::MAX }));
+                    }
+                    let number = f64::from_js(lock, value)?;
+                    Ok(Self::new(to_integer(integer_range!($type).clamp(number))))
+                }
+            }
+        )*
+    };
+}
+
+impl_big_int!(
+    i64 => unwrap_big_int_i64,
+    u64 => unwrap_big_int_u64,
+    usize => unwrap_big_int_usize,
+);
+
+impl<T: Type> Type for EnforceRange<T> {
+    fn class_name() -> &'static str {
+        T::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        T::is_exact(value)
+    }
+}
+
+impl<T: Type> Type for Clamp<T> {
+    fn class_name() -> &'static str {
+        T::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        T::is_exact(value)
+    }
+}
+
+impl<T: ToJS> ToJS for EnforceRange<T> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        self.into_inner().to_js(lock)
+    }
+}
+
+impl<T: ToJS> ToJS for Clamp<T> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        self.into_inner().to_js(lock)
+    }
+}
+
 // Special implementation for &str - allows functions to accept &str parameters
 // by converting JavaScript strings to owned Strings, then borrowing.
 // The macro handles passing &arg instead of arg for reference types.zs
@@ -142,24 +496,119 @@ impl<T: FromJS<ResultType = T>> FromJS for &T {
     }
 }
 
+// =============================================================================
+// Date implementations
+// =============================================================================
+
+impl Type for SystemTime {
+    fn class_name() -> &'static str {
+        "Date"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_date()
+    }
+}
+
+impl ToJS for SystemTime {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        v8::Local::<v8::Date>::new(lock, self).into()
+    }
+}
+
+/// Accepts a `Date` or a number of milliseconds since the epoch, like `kj::Date` in C++ JSG.
+impl FromJS for SystemTime {
+    type ResultType = Self;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        let millis = if let Some(date) = value.try_cast::<v8::Date>() {
+            date.value_of()
+        } else if value.is_number() {
+            f64::from_js(lock, value)?
+        } else {
+            let msg = format!("Expected a Date but got {}", value.type_of());
+            return Err(Error::new_type_error(msg));
+        };
+        v8::system_time_from_millis(millis).ok_or_else(|| {
+            Error::new_type_error("The value cannot be converted because it is not a valid Date.")
+        })
+    }
+}
+
 // =============================================================================
 // Wrapper type implementations
 // =============================================================================
 
+impl<T: Type> Type for Option<T> {
+    fn class_name() -> &'static str {
+        T::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_undefined() || T::is_exact(value)
+    }
+
+    fn track_memory(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+        if let Some(value) = self {
+            value.track_memory(tracker, edge_name);
+        }
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        if let Some(value) = self {
+            value.visit_for_gc(visitor);
+        }
+    }
+}
+
+impl<T: Type> Type for NonCoercible<T> {
+    fn class_name() -> &'static str {
+        T::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        T::is_exact(value)
+    }
+
+    fn track_memory(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+        self.as_ref().track_memory(tracker, edge_name);
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        self.as_ref().visit_for_gc(visitor);
+    }
+}
+
+impl<T: Type> Type for Nullable<T> {
+    fn class_name() -> &'static str {
+        T::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_null_or_undefined() || T::is_exact(value)
+    }
+
+    fn track_memory(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+        if let Self::Some(value) = self {
+            value.track_memory(tracker, edge_name);
+        }
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        if let Self::Some(value) = self {
+            value.visit_for_gc(visitor);
+        }
+    }
+}
+
 impl ToJS for () {
-    fn to_js<'a, 'b>(self, lock: &'a mut Lock) -> v8::Local<'b, v8::Value>
-    where
-        'b: 'a,
-    {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
         v8::Local::<v8::Value>::undefined(lock)
     }
 }
 
 impl<T: ToJS> ToJS for Option<T> {
-    fn to_js<'a, 'b>(self, lock: &'a mut Lock) -> v8::Local<'b, v8::Value>
-    where
-        'b: 'a,
-    {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
         match self {
             Some(value) => value.to_js(lock),
             None => v8::Local::<v8::Value>::undefined(lock),
@@ -168,19 +617,13 @@ impl<T: ToJS> ToJS for Option<T> {
 }
 
 impl<T: Type + ToJS> ToJS for NonCoercible<T> {
-    fn to_js<'a, 'b>(self, lock: &'a mut Lock) -> v8::Local<'b, v8::Value>
-    where
-        'b: 'a,
-    {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
         self.into_inner().to_js(lock)
     }
 }
 
 impl<T: ToJS> ToJS for Nullable<T> {
-    fn to_js<'a, 'b>(self, lock: &'a mut Lock) -> v8::Local<'b, v8::Value>
-    where
-        'b: 'a,
-    {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
         match self {
             Self::Some(value) => value.to_js(lock),
             Self::Null => v8::Local::<v8::Value>::null(lock),
@@ -189,6 +632,15 @@ impl<T: ToJS> ToJS for Nullable<T> {
     }
 }
 
+impl FromJS for () {
+    type ResultType = Self;
+
+    /// Ignores the value, like a `void` result in C++ JSG, e.g. of a `Function<fn()>`.
+    fn from_js(_lock: &mut Lock, _value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        Ok(())
+    }
+}
+
 impl<T: Type + FromJS> FromJS for Option<T> {
     type ResultType = Option<T::ResultType>;
 
@@ -233,3 +685,312 @@ impl<T: Type + FromJS> FromJS for Nullable<T> {
         }
     }
 }
+
+// =============================================================================
+// Array and sequence implementations
+// =============================================================================
+
+impl<T: Type + FromJS> Type for Vec<T> {
+    fn class_name() -> &'static str {
+        T::vec_class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        T::vec_is_exact(value)
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        track_elements(tracker, self.capacity(), self);
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        for element in self {
+            visitor.visit(element);
+        }
+    }
+}
+
+impl<T: Type> Type for Sequence<T> {
+    fn class_name() -> &'static str {
+        "Sequence"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_object()
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        track_elements(tracker, self.capacity(), self.iter());
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        for element in self.iter() {
+            visitor.visit(element);
+        }
+    }
+}
+
+/// Tracks the buffer of a collection with room for `capacity` elements of type `T`, followed by
+/// the memory owned by each element.
+fn track_elements<'a, T: Type + 'a>(
+    tracker: &mut MemoryTracker,
+    capacity: usize,
+    elements: impl IntoIterator<Item = &'a T>,
+) {
+    tracker.track_field_with_size("elements", capacity * std::mem::size_of::<T>());
+    if std::mem::needs_drop::<T>() {
+        for element in elements {
+            element.track_memory(tracker, "element");
+        }
+    }
+}
+
+impl<T: ToJS> ToJS for Vec<T> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        T::vec_to_js(self, lock)
+    }
+}
+
+impl<T: ToJS> ToJS for Sequence<T> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        wrap_array(self.into_inner(), lock)
+    }
+}
+
+impl<T: FromJS> FromJS for Vec<T> {
+    type ResultType = Vec<T::ResultType>;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        T::vec_from_js(lock, value)
+    }
+}
+
+impl<T: FromJS> FromJS for Sequence<T> {
+    type ResultType = Sequence<T::ResultType>;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        if !value.is_object() {
+            let msg = format!("Expected a Sequence but got {}", value.type_of());
+            return Err(Error::new_type_error(msg));
+        }
+        Ok(Sequence::new(unwrap_elements::<T>(lock, &value)?))
+    }
+}
+
+/// Creates an `Array` holding each of `values` converted to JavaScript.
+fn wrap_array<'s, T: ToJS>(values: Vec<T>, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+    let values = values
+        .into_iter()
+        .map(|value| {
+            // Releases the handles created while converting the element, except its own.
+            lock.within_escapable_handle_scope(|lock| value.to_js(lock))
+        })
+        .collect();
+    v8::Local::<v8::Array>::new(lock, values).into()
+}
+
+/// Unwraps each value produced by iterating `value` as `T`, naming the index of the element
+/// that failed to convert in the error message.
+fn unwrap_elements<T: FromJS>(
+    lock: &mut Lock,
+    value: &v8::Local<v8::Value>,
+) -> Result<Vec<T::ResultType>, Error> {
+    let elements = match value.iterate(lock) {
+        Ok(elements) => elements,
+        Err(exception) => return Err(Error::from_js(lock, exception).unwrap_or_else(|err| err)),
+    };
+    elements
+        .into_iter()
+        .enumerate()
+        .map(|(index, element)| {
+            lock.within_handle_scope(|lock| T::from_js(lock, element))
+                .map_err(|err| Error {
+                    message: format!(
+                        "Incorrect type for the element at index {index}: {}",
+                        err.message
+                    ),
+                    ..err
+                })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Record implementations
+// =============================================================================
+
+impl<T: Type, S> Type for HashMap<String, T, S> {
+    fn class_name() -> &'static str {
+        "object"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_object()
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        track_entries(tracker, self.capacity(), self);
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        for value in self.values() {
+            visitor.visit(value);
+        }
+    }
+}
+
+impl<T: Type> Type for BTreeMap<String, T> {
+    fn class_name() -> &'static str {
+        "object"
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        value.is_object()
+    }
+
+    fn memory_info(&self, tracker: &mut MemoryTracker) {
+        track_entries(tracker, self.len(), self);
+    }
+
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        for value in self.values() {
+            visitor.visit(value);
+        }
+    }
+}
+
+/// Tracks the storage of a map with room for `capacity` entries, followed by the memory owned by
+/// each key and value. The storage size is an estimate, as the layout of the map is not exposed.
+fn track_entries<'a, T: Type + 'a>(
+    tracker: &mut MemoryTracker,
+    capacity: usize,
+    entries: impl IntoIterator<Item = (&'a String, &'a T)>,
+) {
+    tracker.track_field_with_size("entries", capacity * std::mem::size_of::<(String, T)>());
+    for (key, value) in entries {
+        key.track_memory(tracker, "key");
+        value.track_memory(tracker, "value");
+    }
+}
+
+impl<T: ToJS, S> ToJS for HashMap<String, T, S> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        wrap_record(lock, self)
+    }
+}
+
+impl<T: ToJS> ToJS for BTreeMap<String, T> {
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        wrap_record(lock, self)
+    }
+}
+
+impl<T: FromJS, S: BuildHasher + Default> FromJS for HashMap<String, T, S> {
+    type ResultType = HashMap<String, T::ResultType, S>;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        unwrap_record::<T, _>(lock, value)
+    }
+}
+
+impl<T: FromJS> FromJS for BTreeMap<String, T> {
+    type ResultType = BTreeMap<String, T::ResultType>;
+
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        unwrap_record::<T, _>(lock, value)
+    }
+}
+
+/// Creates a plain object with one property per entry, analogous to wrapping a `jsg::Dict`.
+fn wrap_record<'s, T: ToJS>(
+    lock: &mut Lock<'s>,
+    entries: impl IntoIterator<Item = (String, T)>,
+) -> v8::Local<'s, v8::Value> {
+    let mut object = lock.new_object();
+    for (key, value) in entries {
+        lock.within_handle_scope(|lock| {
+            let value = value.to_js(lock);
+            object.set(lock, &key, value);
+        });
+    }
+    object.into()
+}
+
+/// Unwraps an object's own enumerable string-keyed properties, following the Web IDL
+/// `record<DOMString, T>` conversion used by `jsg::Dict` in C++ JSG. Like there, an exception
+/// thrown by a getter is returned as the error.
+fn unwrap_record<T: FromJS, M: FromIterator<(String, T::ResultType)>>(
+    lock: &mut Lock,
+    value: v8::Local<v8::Value>,
+) -> Result<M, Error> {
+    let object = value.try_into_object()?;
+    object
+        .get_own_property_names(lock)
+        .into_iter()
+        .map(|key| {
+            lock.within_handle_scope(|lock| {
+                let value = object.get(lock, &key)?;
+                match T::from_js(lock, value) {
+                    Ok(value) => Ok((key, value)),
+                    Err(err) => Err(Error {
+                        message: format!(
+                            "Incorrect type for the '{key}' property: {}",
+                            err.message
+                        ),
+                        ..err
+                    }),
+                }
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Resource implementations
+// =============================================================================
+
+impl<R: Resource> Type for Ref<R> {
+    fn class_name() -> &'static str {
+        R::class_name()
+    }
+
+    fn is_exact(value: &v8::Local<v8::Value>) -> bool {
+        R::is_exact(value)
+    }
+
+    fn track_memory(&self, tracker: &mut MemoryTracker, edge_name: &str) {
+        tracker.track_resource(edge_name, &**self, self.wrapper());
+    }
+
+    /// Visits the referenced resource if this `Ref` is its only owner, as the resource is then
+    /// only reachable through the object being visited. Otherwise, the values it holds stay
+    /// strong, or are traced through its own wrapper.
+    fn visit_for_gc(&self, visitor: &mut GcVisitor) {
+        if self.is_unique() {
+            (**self).visit_for_gc(visitor);
+        }
+    }
+}
+
+impl<R: Resource + 'static> ToJS for Ref<R> {
+    /// Returns the resource's existing wrapper, or wraps it using the Realm's template for `R`.
+    fn to_js<'s>(self, lock: &mut Lock<'s>) -> v8::Local<'s, v8::Value> {
+        let constructor = lock.get_template::<R>();
+        unsafe { crate::wrap_resource_with(lock, self, &constructor) }
+    }
+}
+
+impl<R: Resource + 'static> FromJS for Ref<R> {
+    type ResultType = Self;
+
+    /// Unwraps an object created from the Realm's template for `R`, throwing a `TypeError` for
+    /// any other value.
+    fn from_js(lock: &mut Lock, value: v8::Local<v8::Value>) -> Result<Self::ResultType, Error> {
+        let constructor = lock.get_template::<R>().as_local(lock);
+        if !value.is_object() || !constructor.has_instance(&value) {
+            let msg = format!("Expected {} but got {}", R::class_name(), value.type_of());
+            return Err(Error::new_type_error(msg));
+        }
+        Ok(crate::unwrap_resource_ref(lock, value))
+    }
+}
@@ -529,6 +529,33 @@ struct Worker::Impl {
   kj::Maybe<kj::Exception> permanentException;
 };
 
+namespace {
+
+// Resumes I/O awaited by Rust code through the IoContext of the request that started it, like
+// IoContext::awaitIo() does for C++ APIs, so that the continuation takes the isolate lock in turn
+// with other events and is canceled along with the request.
+class RustIoScheduler final: public ::workerd::rust::jsg::IoScheduler {
+ public:
+  bool awaitIo(kj::Promise<kj::Function<void(jsg::Lock&)>> promise) override {
+    KJ_IF_SOME(context, IoContext::tryCurrent()) {
+      auto& js = jsg::Lock::current();
+      context.addTask(promise.attach(context.registerPendingEvent())
+              .then([&context, maybeAsyncContext = jsg::AsyncContextFrame::currentRef(js)](
+                        kj::Function<void(jsg::Lock&)> continuation) mutable {
+        return context.run([maybeAsyncContext = kj::mv(maybeAsyncContext),
+                               continuation = kj::mv(continuation)](Worker::Lock& lock) mutable {
+          jsg::AsyncContextFrame::Scope asyncScope(lock, maybeAsyncContext);
+          continuation(lock);
+        });
+      }));
+      return true;
+    }
+    return false;
+  }
+};
+
+}  // namespace
+
 // Note that Isolate mutable state is protected by locking the JsgWorkerIsolate unless otherwise
 // noted.
 struct Worker::Isolate::Impl {
@@ -539,9 +566,6 @@ struct Worker::Isolate::Impl {
   kj::Maybe<kj::Own<v8::CpuProfiler>> profiler;
   ActorCache::SharedLru actorCacheLru;
 
-  // Used by JSG/Rust integration.
-  ::rust::Box<::workerd::rust::jsg::Realm> realm;
-
   // UUID for this isolate, initialized first time getUuid() is called.
   kj::Lazy<kj::String> uuid;
 
@@ -635,6 +659,10 @@ struct Worker::Isolate::Impl {
     KJ_DISALLOW_COPY_AND_MOVE(Lock);
 
     void setupContext(v8::Local<v8::Context> context) {
+      // Rust resources are tracked per context, so that they are dropped when it is disposed.
+      ::workerd::rust::jsg::realm_create_for_context(
+          lock->v8Isolate, context, kj::heap<RustIoScheduler>());
+
       // The V8Inspector implements the `console` object.
       KJ_IF_SOME(i, impl.inspector) {
         i.get()->contextCreated(
@@ -647,6 +675,9 @@ struct Worker::Isolate::Impl {
       lock->withinHandleScope([&] {
         auto v8Context = context.getHandle(*lock);
         context->clear();
+        // The Rust Realm must be dropped under lock since Realm::drop() accesses V8 globals
+        // and calls drop functions that may interact with V8.
+        ::workerd::rust::jsg::realm_dispose(v8Context);
         KJ_IF_SOME(i, impl.inspector) {
           i.get()->contextDestroyed(v8Context);
         }
@@ -708,20 +739,14 @@ struct Worker::Isolate::Impl {
   struct IsolateState {
     kj::Own<InspectorClient> inspectorClient;
     kj::Maybe<std::unique_ptr<v8_inspector::V8Inspector>> inspector;
-    ::rust::Box<::workerd::rust::jsg::Realm> realm;
   };
 
   static IsolateState initIsolate(
       const Api& api, IsolateLimitEnforcer& limitEnforcer, InspectorPolicy inspectorPolicy) {
     auto inspectorClient = kj::heap<InspectorClient>();
-    // Default constructor of ::rust::Box is deleted, so we use a Maybe to delay initialization.
-    kj::Maybe<::rust::Box<::workerd::rust::jsg::Realm>> realm;
     kj::Maybe<std::unique_ptr<v8_inspector::V8Inspector>> inspector;
     jsg::runInV8Stack([&](jsg::V8StackScope& stackScope) {
       auto lock = api.lock(stackScope);
-      realm = ::workerd::rust::jsg::realm_create(lock->v8Isolate);
-      lock->v8Isolate->SetData(
-          ::workerd::jsg::SetDataIndex::SET_DATA_RUST_REALM, &*KJ_REQUIRE_NONNULL(realm));
       limitEnforcer.customizeIsolate(lock->v8Isolate);
       if (inspectorPolicy != InspectorPolicy::DISALLOW) {
         // We just created our isolate, so we don't need to use Isolate::Impl::Lock.
@@ -729,7 +754,7 @@ struct Worker::Isolate::Impl {
         inspector = v8_inspector::V8Inspector::create(lock->v8Isolate, inspectorClient.get());
       }
     });
-    return {kj::mv(inspectorClient), kj::mv(inspector), kj::mv(KJ_REQUIRE_NONNULL(realm))};
+    return {kj::mv(inspectorClient), kj::mv(inspector)};
   }
 
   Impl(IsolateObserver& metrics,
@@ -740,8 +765,7 @@ struct Worker::Isolate::Impl {
         inspectorClient(kj::mv(state.inspectorClient)),
         inspector(kj::mv(state.inspector)),
         inspectorPolicy(inspectorPolicy),
-        actorCacheLru(limitEnforcer.getActorCacheLruOptions()),
-        realm(kj::mv(state.realm)) {}
+        actorCacheLru(limitEnforcer.getActorCacheLruOptions()) {}
 
   Impl(const Api& api,
       IsolateObserver& metrics,
@@ -1546,9 +1570,6 @@ Worker::Isolate::~Isolate() noexcept(false) {
     metrics->teardownLockAcquired();
     auto inspector = kj::mv(impl->inspector);
     auto dropTraceAsyncContextKey = kj::mv(traceAsyncContextKey);
-    // The Rust Realm must be dropped under lock since Realm::drop() accesses V8 globals
-    // and calls drop functions that may interact with V8.
-    auto dropRealm = kj::mv(impl->realm);
   });
 }
 
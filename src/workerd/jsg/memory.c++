@@ -186,6 +186,35 @@ void MemoryTracker::addEdge(MemoryRetainerNode* node, kj::StringPtr edgeName) {
   }
 }
 
+void MemoryTracker::trackForeign(kj::Maybe<kj::StringPtr> edgeName,
+    const void* retainer,
+    kj::StringPtr name,
+    size_t size,
+    v8::Local<v8::Object> wrapper,
+    kj::FunctionParam<void(MemoryTracker&)> getMemoryInfo) {
+  MemoryRetainerNode* n;
+  if (retainer == nullptr) {
+    n = addNode(name, size, edgeName);
+  } else {
+    KJ_IF_SOME(found, seen_.find(retainer)) {
+      addEdge(found, edgeName.orDefault(nullptr));
+      return;
+    }
+    n = addNode(
+        retainer, name, size, wrapper, kj::none, MemoryInfoDetachedState::UNKNOWN, edgeName);
+  }
+  nodeStack_.push(n);
+  KJ_DEFER(nodeStack_.pop());
+  getMemoryInfo(*this);
+}
+
+void MemoryTracker::buildGraph(v8::Isolate* isolate,
+    v8::EmbedderGraph* graph,
+    kj::FunctionParam<void(MemoryTracker&)> callback) {
+  MemoryTracker tracker(isolate, graph);
+  callback(tracker);
+}
+
 // ======================================================================================
 
 HeapSnapshotActivity::HeapSnapshotActivity(Callback callback): callback(kj::mv(callback)) {}
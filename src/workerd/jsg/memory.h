@@ -13,6 +13,7 @@
 #include <kj/common.h>
 #include <kj/debug.h>
 #include <kj/exception.h>
+#include <kj/function.h>
 #include <kj/hash.h>
 #include <kj/map.h>
 #include <kj/string.h>
@@ -296,6 +297,22 @@ class MemoryTracker final {
   template <MemoryRetainer T>
   inline void trackInlineField(const T* retainer, kj::Maybe<kj::StringPtr> edgeName = kj::none);
 
+  // Tracks an object that is not a C++ MemoryRetainer, such as a resource implemented in Rust.
+  // `retainer` identifies the object so that it is only tracked once; it may be null for values
+  // that are never shared. `getMemoryInfo` is called with the object's node as the current node
+  // so that it can track the object's fields.
+  void trackForeign(kj::Maybe<kj::StringPtr> edgeName,
+      const void* retainer,
+      kj::StringPtr name,
+      size_t size,
+      v8::Local<v8::Object> wrapper,
+      kj::FunctionParam<void(MemoryTracker&)> getMemoryInfo);
+
+  // Creates a tracker for an embedder graph callback registered outside of IsolateBase.
+  static void buildGraph(v8::Isolate* isolate,
+      v8::EmbedderGraph* graph,
+      kj::FunctionParam<void(MemoryTracker&)> callback);
+
   inline v8::Isolate* isolate() {
     return isolate_;
   }